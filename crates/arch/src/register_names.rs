@@ -7,11 +7,139 @@ pub static X86_64_REGISTERS: phf::Map<UniCase<&'static str>, i8> = phf::phf_map!
     UniCase::ascii("rbx") => 1, UniCase::ascii("ebx") => 1, UniCase::ascii("bx") => 1, UniCase::ascii("bh") => 1, UniCase::ascii("bl") => 1,
     UniCase::ascii("rcx") => 2, UniCase::ascii("ecx") => 2, UniCase::ascii("cx") => 2, UniCase::ascii("ch") => 2, UniCase::ascii("cl") => 2,
     UniCase::ascii("rdx") => 3, UniCase::ascii("edx") => 3, UniCase::ascii("dx") => 3, UniCase::ascii("dh") => 3, UniCase::ascii("dl") => 3,
-    UniCase::ascii("rbp") => 4,
-    UniCase::ascii("rsp") => 5,
-    UniCase::ascii("rsi") => 6,
-    UniCase::ascii("rdi") => 7, UniCase::ascii("edi") => 7,
-    UniCase::ascii("rip") => 8,
+    UniCase::ascii("rbp") => 4, UniCase::ascii("ebp") => 4, UniCase::ascii("bp") => 4, UniCase::ascii("bpl") => 4,
+    UniCase::ascii("rsp") => 5, UniCase::ascii("esp") => 5, UniCase::ascii("sp") => 5, UniCase::ascii("spl") => 5,
+    UniCase::ascii("rsi") => 6, UniCase::ascii("esi") => 6, UniCase::ascii("si") => 6, UniCase::ascii("sil") => 6,
+    UniCase::ascii("rdi") => 7, UniCase::ascii("edi") => 7, UniCase::ascii("di") => 7, UniCase::ascii("dil") => 7,
+    UniCase::ascii("rip") => 8, UniCase::ascii("eip") => 8, UniCase::ascii("ip") => 8,
+    UniCase::ascii("r8") => 9, UniCase::ascii("r8d") => 9, UniCase::ascii("r8w") => 9, UniCase::ascii("r8b") => 9, UniCase::ascii("r8l") => 9,
+    UniCase::ascii("r9") => 10, UniCase::ascii("r9d") => 10, UniCase::ascii("r9w") => 10, UniCase::ascii("r9b") => 10, UniCase::ascii("r9l") => 10,
+    UniCase::ascii("r10") => 11, UniCase::ascii("r10d") => 11, UniCase::ascii("r10w") => 11, UniCase::ascii("r10b") => 11, UniCase::ascii("r10l") => 11,
+    UniCase::ascii("r11") => 12, UniCase::ascii("r11d") => 12, UniCase::ascii("r11w") => 12, UniCase::ascii("r11b") => 12, UniCase::ascii("r11l") => 12,
+    UniCase::ascii("r12") => 13, UniCase::ascii("r12d") => 13, UniCase::ascii("r12w") => 13, UniCase::ascii("r12b") => 13, UniCase::ascii("r12l") => 13,
+    UniCase::ascii("r13") => 14, UniCase::ascii("r13d") => 14, UniCase::ascii("r13w") => 14, UniCase::ascii("r13b") => 14, UniCase::ascii("r13l") => 14,
+    UniCase::ascii("r14") => 15, UniCase::ascii("r14d") => 15, UniCase::ascii("r14w") => 15, UniCase::ascii("r14b") => 15, UniCase::ascii("r14l") => 15,
+    UniCase::ascii("r15") => 16, UniCase::ascii("r15d") => 16, UniCase::ascii("r15w") => 16, UniCase::ascii("r15b") => 16, UniCase::ascii("r15l") => 16,
+    UniCase::ascii("xmm0") => 17, UniCase::ascii("ymm0") => 17, UniCase::ascii("zmm0") => 17,
+    UniCase::ascii("xmm1") => 18, UniCase::ascii("ymm1") => 18, UniCase::ascii("zmm1") => 18,
+    UniCase::ascii("xmm2") => 19, UniCase::ascii("ymm2") => 19, UniCase::ascii("zmm2") => 19,
+    UniCase::ascii("xmm3") => 20, UniCase::ascii("ymm3") => 20, UniCase::ascii("zmm3") => 20,
+    UniCase::ascii("xmm4") => 21, UniCase::ascii("ymm4") => 21, UniCase::ascii("zmm4") => 21,
+    UniCase::ascii("xmm5") => 22, UniCase::ascii("ymm5") => 22, UniCase::ascii("zmm5") => 22,
+    UniCase::ascii("xmm6") => 23, UniCase::ascii("ymm6") => 23, UniCase::ascii("zmm6") => 23,
+    UniCase::ascii("xmm7") => 24, UniCase::ascii("ymm7") => 24, UniCase::ascii("zmm7") => 24,
+    UniCase::ascii("xmm8") => 25, UniCase::ascii("ymm8") => 25, UniCase::ascii("zmm8") => 25,
+    UniCase::ascii("xmm9") => 26, UniCase::ascii("ymm9") => 26, UniCase::ascii("zmm9") => 26,
+    UniCase::ascii("xmm10") => 27, UniCase::ascii("ymm10") => 27, UniCase::ascii("zmm10") => 27,
+    UniCase::ascii("xmm11") => 28, UniCase::ascii("ymm11") => 28, UniCase::ascii("zmm11") => 28,
+    UniCase::ascii("xmm12") => 29, UniCase::ascii("ymm12") => 29, UniCase::ascii("zmm12") => 29,
+    UniCase::ascii("xmm13") => 30, UniCase::ascii("ymm13") => 30, UniCase::ascii("zmm13") => 30,
+    UniCase::ascii("xmm14") => 31, UniCase::ascii("ymm14") => 31, UniCase::ascii("zmm14") => 31,
+    UniCase::ascii("xmm15") => 32, UniCase::ascii("ymm15") => 32, UniCase::ascii("zmm15") => 32,
+    UniCase::ascii("xmm16") => 33, UniCase::ascii("ymm16") => 33, UniCase::ascii("zmm16") => 33,
+    UniCase::ascii("xmm17") => 34, UniCase::ascii("ymm17") => 34, UniCase::ascii("zmm17") => 34,
+    UniCase::ascii("xmm18") => 35, UniCase::ascii("ymm18") => 35, UniCase::ascii("zmm18") => 35,
+    UniCase::ascii("xmm19") => 36, UniCase::ascii("ymm19") => 36, UniCase::ascii("zmm19") => 36,
+    UniCase::ascii("xmm20") => 37, UniCase::ascii("ymm20") => 37, UniCase::ascii("zmm20") => 37,
+    UniCase::ascii("xmm21") => 38, UniCase::ascii("ymm21") => 38, UniCase::ascii("zmm21") => 38,
+    UniCase::ascii("xmm22") => 39, UniCase::ascii("ymm22") => 39, UniCase::ascii("zmm22") => 39,
+    UniCase::ascii("xmm23") => 40, UniCase::ascii("ymm23") => 40, UniCase::ascii("zmm23") => 40,
+    UniCase::ascii("xmm24") => 41, UniCase::ascii("ymm24") => 41, UniCase::ascii("zmm24") => 41,
+    UniCase::ascii("xmm25") => 42, UniCase::ascii("ymm25") => 42, UniCase::ascii("zmm25") => 42,
+    UniCase::ascii("xmm26") => 43, UniCase::ascii("ymm26") => 43, UniCase::ascii("zmm26") => 43,
+    UniCase::ascii("xmm27") => 44, UniCase::ascii("ymm27") => 44, UniCase::ascii("zmm27") => 44,
+    UniCase::ascii("xmm28") => 45, UniCase::ascii("ymm28") => 45, UniCase::ascii("zmm28") => 45,
+    UniCase::ascii("xmm29") => 46, UniCase::ascii("ymm29") => 46, UniCase::ascii("zmm29") => 46,
+    UniCase::ascii("xmm30") => 47, UniCase::ascii("ymm30") => 47, UniCase::ascii("zmm30") => 47,
+    UniCase::ascii("xmm31") => 48, UniCase::ascii("ymm31") => 48, UniCase::ascii("zmm31") => 48,
+    UniCase::ascii("k0") => 49,
+    UniCase::ascii("k1") => 50,
+    UniCase::ascii("k2") => 51,
+    UniCase::ascii("k3") => 52,
+    UniCase::ascii("k4") => 53,
+    UniCase::ascii("k5") => 54,
+    UniCase::ascii("k6") => 55,
+    UniCase::ascii("k7") => 56,
+    UniCase::ascii("es") => 57,
+    UniCase::ascii("cs") => 58,
+    UniCase::ascii("ss") => 59,
+    UniCase::ascii("ds") => 60,
+    UniCase::ascii("fs") => 61,
+    UniCase::ascii("gs") => 62,
+    UniCase::ascii("cr0") => 63,
+    UniCase::ascii("cr1") => 64,
+    UniCase::ascii("cr2") => 65,
+    UniCase::ascii("cr3") => 66,
+    UniCase::ascii("cr4") => 67,
+    UniCase::ascii("cr5") => 68,
+    UniCase::ascii("cr6") => 69,
+    UniCase::ascii("cr7") => 70,
+    UniCase::ascii("cr8") => 71,
+    UniCase::ascii("cr9") => 72,
+    UniCase::ascii("cr10") => 73,
+    UniCase::ascii("cr11") => 74,
+    UniCase::ascii("cr12") => 75,
+    UniCase::ascii("cr13") => 76,
+    UniCase::ascii("cr14") => 77,
+    UniCase::ascii("cr15") => 78,
+    UniCase::ascii("dr0") => 79,
+    UniCase::ascii("dr1") => 80,
+    UniCase::ascii("dr2") => 81,
+    UniCase::ascii("dr3") => 82,
+    UniCase::ascii("dr4") => 83,
+    UniCase::ascii("dr5") => 84,
+    UniCase::ascii("dr6") => 85,
+    UniCase::ascii("dr7") => 86,
+};
+
+pub static X86_REGISTERS: phf::Map<UniCase<&'static str>, i8> = phf::phf_map! {
+    UniCase::ascii("eax") => 0, UniCase::ascii("ax") => 0, UniCase::ascii("ah") => 0, UniCase::ascii("al") => 0,
+    UniCase::ascii("ebx") => 1, UniCase::ascii("bx") => 1, UniCase::ascii("bh") => 1, UniCase::ascii("bl") => 1,
+    UniCase::ascii("ecx") => 2, UniCase::ascii("cx") => 2, UniCase::ascii("ch") => 2, UniCase::ascii("cl") => 2,
+    UniCase::ascii("edx") => 3, UniCase::ascii("dx") => 3, UniCase::ascii("dh") => 3, UniCase::ascii("dl") => 3,
+    UniCase::ascii("ebp") => 4, UniCase::ascii("bp") => 4,
+    UniCase::ascii("esp") => 5, UniCase::ascii("sp") => 5,
+    UniCase::ascii("esi") => 6, UniCase::ascii("si") => 6,
+    UniCase::ascii("edi") => 7, UniCase::ascii("di") => 7,
+    UniCase::ascii("eip") => 8, UniCase::ascii("ip") => 8,
+    UniCase::ascii("xmm0") => 17, UniCase::ascii("ymm0") => 17, UniCase::ascii("zmm0") => 17,
+    UniCase::ascii("xmm1") => 18, UniCase::ascii("ymm1") => 18, UniCase::ascii("zmm1") => 18,
+    UniCase::ascii("xmm2") => 19, UniCase::ascii("ymm2") => 19, UniCase::ascii("zmm2") => 19,
+    UniCase::ascii("xmm3") => 20, UniCase::ascii("ymm3") => 20, UniCase::ascii("zmm3") => 20,
+    UniCase::ascii("xmm4") => 21, UniCase::ascii("ymm4") => 21, UniCase::ascii("zmm4") => 21,
+    UniCase::ascii("xmm5") => 22, UniCase::ascii("ymm5") => 22, UniCase::ascii("zmm5") => 22,
+    UniCase::ascii("xmm6") => 23, UniCase::ascii("ymm6") => 23, UniCase::ascii("zmm6") => 23,
+    UniCase::ascii("xmm7") => 24, UniCase::ascii("ymm7") => 24, UniCase::ascii("zmm7") => 24,
+    UniCase::ascii("k0") => 49,
+    UniCase::ascii("k1") => 50,
+    UniCase::ascii("k2") => 51,
+    UniCase::ascii("k3") => 52,
+    UniCase::ascii("k4") => 53,
+    UniCase::ascii("k5") => 54,
+    UniCase::ascii("k6") => 55,
+    UniCase::ascii("k7") => 56,
+    UniCase::ascii("es") => 57,
+    UniCase::ascii("cs") => 58,
+    UniCase::ascii("ss") => 59,
+    UniCase::ascii("ds") => 60,
+    UniCase::ascii("fs") => 61,
+    UniCase::ascii("gs") => 62,
+    UniCase::ascii("cr0") => 63,
+    UniCase::ascii("cr1") => 64,
+    UniCase::ascii("cr2") => 65,
+    UniCase::ascii("cr3") => 66,
+    UniCase::ascii("cr4") => 67,
+    UniCase::ascii("cr5") => 68,
+    UniCase::ascii("cr6") => 69,
+    UniCase::ascii("cr7") => 70,
+    UniCase::ascii("dr0") => 79,
+    UniCase::ascii("dr1") => 80,
+    UniCase::ascii("dr2") => 81,
+    UniCase::ascii("dr3") => 82,
+    UniCase::ascii("dr4") => 83,
+    UniCase::ascii("dr5") => 84,
+    UniCase::ascii("dr6") => 85,
+    UniCase::ascii("dr7") => 86,
 };
 
 pub static AARCH64_REGISTERS: phf::Map<UniCase<&'static str>, i8> = phf::phf_map! {
@@ -97,3 +225,641 @@ pub static AARCH64_REGISTERS: phf::Map<UniCase<&'static str>, i8> = phf::phf_map
     UniCase::ascii("p14") => 79, UniCase::ascii("p14/z") => 79, UniCase::ascii("p14/m") => 79, UniCase::ascii("p14.b") => 79, UniCase::ascii("p14.h") => 79, UniCase::ascii("p14.s") => 79, UniCase::ascii("p14.d") => 79, UniCase::ascii("p14.q") => 79,
     UniCase::ascii("p15") => 80, UniCase::ascii("p15/z") => 80, UniCase::ascii("p15/m") => 80, UniCase::ascii("p15.b") => 80, UniCase::ascii("p15.h") => 80, UniCase::ascii("p15.s") => 80, UniCase::ascii("p15.d") => 80, UniCase::ascii("p15.q") => 80,
 };
+
+pub static RISCV64_REGISTERS: phf::Map<UniCase<&'static str>, i8> = phf::phf_map! {
+    UniCase::ascii("x0") => 0, UniCase::ascii("zero") => 0,
+    UniCase::ascii("x1") => 1, UniCase::ascii("ra") => 1,
+    UniCase::ascii("x2") => 2, UniCase::ascii("sp") => 2,
+    UniCase::ascii("x3") => 3, UniCase::ascii("gp") => 3,
+    UniCase::ascii("x4") => 4, UniCase::ascii("tp") => 4,
+    UniCase::ascii("x5") => 5, UniCase::ascii("t0") => 5,
+    UniCase::ascii("x6") => 6, UniCase::ascii("t1") => 6,
+    UniCase::ascii("x7") => 7, UniCase::ascii("t2") => 7,
+    UniCase::ascii("x8") => 8, UniCase::ascii("s0") => 8, UniCase::ascii("fp") => 8,
+    UniCase::ascii("x9") => 9, UniCase::ascii("s1") => 9,
+    UniCase::ascii("x10") => 10, UniCase::ascii("a0") => 10,
+    UniCase::ascii("x11") => 11, UniCase::ascii("a1") => 11,
+    UniCase::ascii("x12") => 12, UniCase::ascii("a2") => 12,
+    UniCase::ascii("x13") => 13, UniCase::ascii("a3") => 13,
+    UniCase::ascii("x14") => 14, UniCase::ascii("a4") => 14,
+    UniCase::ascii("x15") => 15, UniCase::ascii("a5") => 15,
+    UniCase::ascii("x16") => 16, UniCase::ascii("a6") => 16,
+    UniCase::ascii("x17") => 17, UniCase::ascii("a7") => 17,
+    UniCase::ascii("x18") => 18, UniCase::ascii("s2") => 18,
+    UniCase::ascii("x19") => 19, UniCase::ascii("s3") => 19,
+    UniCase::ascii("x20") => 20, UniCase::ascii("s4") => 20,
+    UniCase::ascii("x21") => 21, UniCase::ascii("s5") => 21,
+    UniCase::ascii("x22") => 22, UniCase::ascii("s6") => 22,
+    UniCase::ascii("x23") => 23, UniCase::ascii("s7") => 23,
+    UniCase::ascii("x24") => 24, UniCase::ascii("s8") => 24,
+    UniCase::ascii("x25") => 25, UniCase::ascii("s9") => 25,
+    UniCase::ascii("x26") => 26, UniCase::ascii("s10") => 26,
+    UniCase::ascii("x27") => 27, UniCase::ascii("s11") => 27,
+    UniCase::ascii("x28") => 28, UniCase::ascii("t3") => 28,
+    UniCase::ascii("x29") => 29, UniCase::ascii("t4") => 29,
+    UniCase::ascii("x30") => 30, UniCase::ascii("t5") => 30,
+    UniCase::ascii("x31") => 31, UniCase::ascii("t6") => 31,
+    UniCase::ascii("f0") => 32, UniCase::ascii("ft0") => 32,
+    UniCase::ascii("f1") => 33, UniCase::ascii("ft1") => 33,
+    UniCase::ascii("f2") => 34, UniCase::ascii("ft2") => 34,
+    UniCase::ascii("f3") => 35, UniCase::ascii("ft3") => 35,
+    UniCase::ascii("f4") => 36, UniCase::ascii("ft4") => 36,
+    UniCase::ascii("f5") => 37, UniCase::ascii("ft5") => 37,
+    UniCase::ascii("f6") => 38, UniCase::ascii("ft6") => 38,
+    UniCase::ascii("f7") => 39, UniCase::ascii("ft7") => 39,
+    UniCase::ascii("f8") => 40, UniCase::ascii("fs0") => 40,
+    UniCase::ascii("f9") => 41, UniCase::ascii("fs1") => 41,
+    UniCase::ascii("f10") => 42, UniCase::ascii("fa0") => 42,
+    UniCase::ascii("f11") => 43, UniCase::ascii("fa1") => 43,
+    UniCase::ascii("f12") => 44, UniCase::ascii("fa2") => 44,
+    UniCase::ascii("f13") => 45, UniCase::ascii("fa3") => 45,
+    UniCase::ascii("f14") => 46, UniCase::ascii("fa4") => 46,
+    UniCase::ascii("f15") => 47, UniCase::ascii("fa5") => 47,
+    UniCase::ascii("f16") => 48, UniCase::ascii("fa6") => 48,
+    UniCase::ascii("f17") => 49, UniCase::ascii("fa7") => 49,
+    UniCase::ascii("f18") => 50, UniCase::ascii("fs2") => 50,
+    UniCase::ascii("f19") => 51, UniCase::ascii("fs3") => 51,
+    UniCase::ascii("f20") => 52, UniCase::ascii("fs4") => 52,
+    UniCase::ascii("f21") => 53, UniCase::ascii("fs5") => 53,
+    UniCase::ascii("f22") => 54, UniCase::ascii("fs6") => 54,
+    UniCase::ascii("f23") => 55, UniCase::ascii("fs7") => 55,
+    UniCase::ascii("f24") => 56, UniCase::ascii("fs8") => 56,
+    UniCase::ascii("f25") => 57, UniCase::ascii("fs9") => 57,
+    UniCase::ascii("f26") => 58, UniCase::ascii("fs10") => 58,
+    UniCase::ascii("f27") => 59, UniCase::ascii("fs11") => 59,
+    UniCase::ascii("f28") => 60, UniCase::ascii("ft8") => 60,
+    UniCase::ascii("f29") => 61, UniCase::ascii("ft9") => 61,
+    UniCase::ascii("f30") => 62, UniCase::ascii("ft10") => 62,
+    UniCase::ascii("f31") => 63, UniCase::ascii("ft11") => 63,
+};
+
+pub static AARCH32_REGISTERS: phf::Map<UniCase<&'static str>, i8> = phf::phf_map! {
+    UniCase::ascii("r0") => 0,
+    UniCase::ascii("r1") => 1,
+    UniCase::ascii("r2") => 2,
+    UniCase::ascii("r3") => 3,
+    UniCase::ascii("r4") => 4,
+    UniCase::ascii("r5") => 5,
+    UniCase::ascii("r6") => 6,
+    UniCase::ascii("r7") => 7,
+    UniCase::ascii("r8") => 8,
+    UniCase::ascii("r9") => 9, UniCase::ascii("sb") => 9,
+    UniCase::ascii("r10") => 10, UniCase::ascii("sl") => 10,
+    UniCase::ascii("r11") => 11, UniCase::ascii("fp") => 11,
+    UniCase::ascii("r12") => 12, UniCase::ascii("ip") => 12,
+    UniCase::ascii("r13") => 13, UniCase::ascii("sp") => 13,
+    UniCase::ascii("r14") => 14, UniCase::ascii("lr") => 14,
+    UniCase::ascii("r15") => 15, UniCase::ascii("pc") => 15,
+    UniCase::ascii("s0") => 16,
+    UniCase::ascii("s1") => 17,
+    UniCase::ascii("s2") => 18,
+    UniCase::ascii("s3") => 19,
+    UniCase::ascii("s4") => 20,
+    UniCase::ascii("s5") => 21,
+    UniCase::ascii("s6") => 22,
+    UniCase::ascii("s7") => 23,
+    UniCase::ascii("s8") => 24,
+    UniCase::ascii("s9") => 25,
+    UniCase::ascii("s10") => 26,
+    UniCase::ascii("s11") => 27,
+    UniCase::ascii("s12") => 28,
+    UniCase::ascii("s13") => 29,
+    UniCase::ascii("s14") => 30,
+    UniCase::ascii("s15") => 31,
+    UniCase::ascii("s16") => 32,
+    UniCase::ascii("s17") => 33,
+    UniCase::ascii("s18") => 34,
+    UniCase::ascii("s19") => 35,
+    UniCase::ascii("s20") => 36,
+    UniCase::ascii("s21") => 37,
+    UniCase::ascii("s22") => 38,
+    UniCase::ascii("s23") => 39,
+    UniCase::ascii("s24") => 40,
+    UniCase::ascii("s25") => 41,
+    UniCase::ascii("s26") => 42,
+    UniCase::ascii("s27") => 43,
+    UniCase::ascii("s28") => 44,
+    UniCase::ascii("s29") => 45,
+    UniCase::ascii("s30") => 46,
+    UniCase::ascii("s31") => 47,
+    UniCase::ascii("d0") => 48,
+    UniCase::ascii("d1") => 49,
+    UniCase::ascii("d2") => 50,
+    UniCase::ascii("d3") => 51,
+    UniCase::ascii("d4") => 52,
+    UniCase::ascii("d5") => 53,
+    UniCase::ascii("d6") => 54,
+    UniCase::ascii("d7") => 55,
+    UniCase::ascii("d8") => 56,
+    UniCase::ascii("d9") => 57,
+    UniCase::ascii("d10") => 58,
+    UniCase::ascii("d11") => 59,
+    UniCase::ascii("d12") => 60,
+    UniCase::ascii("d13") => 61,
+    UniCase::ascii("d14") => 62,
+    UniCase::ascii("d15") => 63,
+    UniCase::ascii("d16") => 64,
+    UniCase::ascii("d17") => 65,
+    UniCase::ascii("d18") => 66,
+    UniCase::ascii("d19") => 67,
+    UniCase::ascii("d20") => 68,
+    UniCase::ascii("d21") => 69,
+    UniCase::ascii("d22") => 70,
+    UniCase::ascii("d23") => 71,
+    UniCase::ascii("d24") => 72,
+    UniCase::ascii("d25") => 73,
+    UniCase::ascii("d26") => 74,
+    UniCase::ascii("d27") => 75,
+    UniCase::ascii("d28") => 76,
+    UniCase::ascii("d29") => 77,
+    UniCase::ascii("d30") => 78,
+    UniCase::ascii("d31") => 79,
+    UniCase::ascii("q0") => 80,
+    UniCase::ascii("q1") => 81,
+    UniCase::ascii("q2") => 82,
+    UniCase::ascii("q3") => 83,
+    UniCase::ascii("q4") => 84,
+    UniCase::ascii("q5") => 85,
+    UniCase::ascii("q6") => 86,
+    UniCase::ascii("q7") => 87,
+    UniCase::ascii("q8") => 88,
+    UniCase::ascii("q9") => 89,
+    UniCase::ascii("q10") => 90,
+    UniCase::ascii("q11") => 91,
+    UniCase::ascii("q12") => 92,
+    UniCase::ascii("q13") => 93,
+    UniCase::ascii("q14") => 94,
+    UniCase::ascii("q15") => 95,
+    UniCase::ascii("apsr") => 96, UniCase::ascii("cpsr") => 96,
+    UniCase::ascii("spsr") => 97,
+    UniCase::ascii("fpscr") => 98,
+};
+
+pub static POWERPC64_REGISTERS: phf::Map<UniCase<&'static str>, i8> = phf::phf_map! {
+    UniCase::ascii("r0") => 0,
+    UniCase::ascii("r1") => 1, UniCase::ascii("sp") => 1,
+    UniCase::ascii("r2") => 2, UniCase::ascii("rtoc") => 2,
+    UniCase::ascii("r3") => 3,
+    UniCase::ascii("r4") => 4,
+    UniCase::ascii("r5") => 5,
+    UniCase::ascii("r6") => 6,
+    UniCase::ascii("r7") => 7,
+    UniCase::ascii("r8") => 8,
+    UniCase::ascii("r9") => 9,
+    UniCase::ascii("r10") => 10,
+    UniCase::ascii("r11") => 11,
+    UniCase::ascii("r12") => 12,
+    UniCase::ascii("r13") => 13,
+    UniCase::ascii("r14") => 14,
+    UniCase::ascii("r15") => 15,
+    UniCase::ascii("r16") => 16,
+    UniCase::ascii("r17") => 17,
+    UniCase::ascii("r18") => 18,
+    UniCase::ascii("r19") => 19,
+    UniCase::ascii("r20") => 20,
+    UniCase::ascii("r21") => 21,
+    UniCase::ascii("r22") => 22,
+    UniCase::ascii("r23") => 23,
+    UniCase::ascii("r24") => 24,
+    UniCase::ascii("r25") => 25,
+    UniCase::ascii("r26") => 26,
+    UniCase::ascii("r27") => 27,
+    UniCase::ascii("r28") => 28,
+    UniCase::ascii("r29") => 29,
+    UniCase::ascii("r30") => 30,
+    UniCase::ascii("r31") => 31,
+    UniCase::ascii("f0") => 32, UniCase::ascii("vs0") => 32,
+    UniCase::ascii("f1") => 33, UniCase::ascii("vs1") => 33,
+    UniCase::ascii("f2") => 34, UniCase::ascii("vs2") => 34,
+    UniCase::ascii("f3") => 35, UniCase::ascii("vs3") => 35,
+    UniCase::ascii("f4") => 36, UniCase::ascii("vs4") => 36,
+    UniCase::ascii("f5") => 37, UniCase::ascii("vs5") => 37,
+    UniCase::ascii("f6") => 38, UniCase::ascii("vs6") => 38,
+    UniCase::ascii("f7") => 39, UniCase::ascii("vs7") => 39,
+    UniCase::ascii("f8") => 40, UniCase::ascii("vs8") => 40,
+    UniCase::ascii("f9") => 41, UniCase::ascii("vs9") => 41,
+    UniCase::ascii("f10") => 42, UniCase::ascii("vs10") => 42,
+    UniCase::ascii("f11") => 43, UniCase::ascii("vs11") => 43,
+    UniCase::ascii("f12") => 44, UniCase::ascii("vs12") => 44,
+    UniCase::ascii("f13") => 45, UniCase::ascii("vs13") => 45,
+    UniCase::ascii("f14") => 46, UniCase::ascii("vs14") => 46,
+    UniCase::ascii("f15") => 47, UniCase::ascii("vs15") => 47,
+    UniCase::ascii("f16") => 48, UniCase::ascii("vs16") => 48,
+    UniCase::ascii("f17") => 49, UniCase::ascii("vs17") => 49,
+    UniCase::ascii("f18") => 50, UniCase::ascii("vs18") => 50,
+    UniCase::ascii("f19") => 51, UniCase::ascii("vs19") => 51,
+    UniCase::ascii("f20") => 52, UniCase::ascii("vs20") => 52,
+    UniCase::ascii("f21") => 53, UniCase::ascii("vs21") => 53,
+    UniCase::ascii("f22") => 54, UniCase::ascii("vs22") => 54,
+    UniCase::ascii("f23") => 55, UniCase::ascii("vs23") => 55,
+    UniCase::ascii("f24") => 56, UniCase::ascii("vs24") => 56,
+    UniCase::ascii("f25") => 57, UniCase::ascii("vs25") => 57,
+    UniCase::ascii("f26") => 58, UniCase::ascii("vs26") => 58,
+    UniCase::ascii("f27") => 59, UniCase::ascii("vs27") => 59,
+    UniCase::ascii("f28") => 60, UniCase::ascii("vs28") => 60,
+    UniCase::ascii("f29") => 61, UniCase::ascii("vs29") => 61,
+    UniCase::ascii("f30") => 62, UniCase::ascii("vs30") => 62,
+    UniCase::ascii("f31") => 63, UniCase::ascii("vs31") => 63,
+    UniCase::ascii("v0") => 64, UniCase::ascii("vs32") => 64,
+    UniCase::ascii("v1") => 65, UniCase::ascii("vs33") => 65,
+    UniCase::ascii("v2") => 66, UniCase::ascii("vs34") => 66,
+    UniCase::ascii("v3") => 67, UniCase::ascii("vs35") => 67,
+    UniCase::ascii("v4") => 68, UniCase::ascii("vs36") => 68,
+    UniCase::ascii("v5") => 69, UniCase::ascii("vs37") => 69,
+    UniCase::ascii("v6") => 70, UniCase::ascii("vs38") => 70,
+    UniCase::ascii("v7") => 71, UniCase::ascii("vs39") => 71,
+    UniCase::ascii("v8") => 72, UniCase::ascii("vs40") => 72,
+    UniCase::ascii("v9") => 73, UniCase::ascii("vs41") => 73,
+    UniCase::ascii("v10") => 74, UniCase::ascii("vs42") => 74,
+    UniCase::ascii("v11") => 75, UniCase::ascii("vs43") => 75,
+    UniCase::ascii("v12") => 76, UniCase::ascii("vs44") => 76,
+    UniCase::ascii("v13") => 77, UniCase::ascii("vs45") => 77,
+    UniCase::ascii("v14") => 78, UniCase::ascii("vs46") => 78,
+    UniCase::ascii("v15") => 79, UniCase::ascii("vs47") => 79,
+    UniCase::ascii("v16") => 80, UniCase::ascii("vs48") => 80,
+    UniCase::ascii("v17") => 81, UniCase::ascii("vs49") => 81,
+    UniCase::ascii("v18") => 82, UniCase::ascii("vs50") => 82,
+    UniCase::ascii("v19") => 83, UniCase::ascii("vs51") => 83,
+    UniCase::ascii("v20") => 84, UniCase::ascii("vs52") => 84,
+    UniCase::ascii("v21") => 85, UniCase::ascii("vs53") => 85,
+    UniCase::ascii("v22") => 86, UniCase::ascii("vs54") => 86,
+    UniCase::ascii("v23") => 87, UniCase::ascii("vs55") => 87,
+    UniCase::ascii("v24") => 88, UniCase::ascii("vs56") => 88,
+    UniCase::ascii("v25") => 89, UniCase::ascii("vs57") => 89,
+    UniCase::ascii("v26") => 90, UniCase::ascii("vs58") => 90,
+    UniCase::ascii("v27") => 91, UniCase::ascii("vs59") => 91,
+    UniCase::ascii("v28") => 92, UniCase::ascii("vs60") => 92,
+    UniCase::ascii("v29") => 93, UniCase::ascii("vs61") => 93,
+    UniCase::ascii("v30") => 94, UniCase::ascii("vs62") => 94,
+    UniCase::ascii("v31") => 95, UniCase::ascii("vs63") => 95,
+    UniCase::ascii("cr0") => 96,
+    UniCase::ascii("cr1") => 97,
+    UniCase::ascii("cr2") => 98,
+    UniCase::ascii("cr3") => 99,
+    UniCase::ascii("cr4") => 100,
+    UniCase::ascii("cr5") => 101,
+    UniCase::ascii("cr6") => 102,
+    UniCase::ascii("cr7") => 103,
+    UniCase::ascii("lr") => 104,
+    UniCase::ascii("ctr") => 105,
+    UniCase::ascii("xer") => 106,
+};
+
+/// The operands for PowerPC instructions that take registers, this is used
+/// to determine which register file a bare number operand refers to.
+/// * r: General purpose register
+/// * f: Floating point register
+/// * v: Vector register
+/// * x: Vector-scalar register
+/// * c: Condition register field
+/// * C: Optional condition register field, defaults to cr0 when omitted
+/// * i: Not a register, e.g. an immediate or a label
+/// * m: A `D(RA)` memory operand, the base is always a general purpose register
+pub static POWERPC64_OPERANDS: phf::Map<UniCase<&'static str>, &'static str> = phf::phf_map! {
+    UniCase::ascii("add") => "rrr",
+    UniCase::ascii("addc") => "rrr",
+    UniCase::ascii("adde") => "rrr",
+    UniCase::ascii("subf") => "rrr",
+    UniCase::ascii("subfc") => "rrr",
+    UniCase::ascii("subfe") => "rrr",
+    UniCase::ascii("mullw") => "rrr",
+    UniCase::ascii("mulld") => "rrr",
+    UniCase::ascii("mulhw") => "rrr",
+    UniCase::ascii("mulhd") => "rrr",
+    UniCase::ascii("mulhwu") => "rrr",
+    UniCase::ascii("mulhdu") => "rrr",
+    UniCase::ascii("divw") => "rrr",
+    UniCase::ascii("divd") => "rrr",
+    UniCase::ascii("divwu") => "rrr",
+    UniCase::ascii("divdu") => "rrr",
+    UniCase::ascii("modsw") => "rrr",
+    UniCase::ascii("modsd") => "rrr",
+    UniCase::ascii("moduw") => "rrr",
+    UniCase::ascii("modud") => "rrr",
+    UniCase::ascii("and") => "rrr",
+    UniCase::ascii("andc") => "rrr",
+    UniCase::ascii("or") => "rrr",
+    UniCase::ascii("orc") => "rrr",
+    UniCase::ascii("xor") => "rrr",
+    UniCase::ascii("nor") => "rrr",
+    UniCase::ascii("nand") => "rrr",
+    UniCase::ascii("eqv") => "rrr",
+    UniCase::ascii("slw") => "rrr",
+    UniCase::ascii("srw") => "rrr",
+    UniCase::ascii("sraw") => "rrr",
+    UniCase::ascii("sld") => "rrr",
+    UniCase::ascii("srd") => "rrr",
+    UniCase::ascii("srad") => "rrr",
+    UniCase::ascii("cmpb") => "rrr",
+    UniCase::ascii("ldx") => "rrr",
+    UniCase::ascii("ldux") => "rrr",
+    UniCase::ascii("lwzx") => "rrr",
+    UniCase::ascii("lwzux") => "rrr",
+    UniCase::ascii("lwax") => "rrr",
+    UniCase::ascii("lhzx") => "rrr",
+    UniCase::ascii("lhax") => "rrr",
+    UniCase::ascii("lbzx") => "rrr",
+    UniCase::ascii("stdx") => "rrr",
+    UniCase::ascii("stdux") => "rrr",
+    UniCase::ascii("stwx") => "rrr",
+    UniCase::ascii("stwux") => "rrr",
+    UniCase::ascii("sthx") => "rrr",
+    UniCase::ascii("stbx") => "rrr",
+    UniCase::ascii("ldbrx") => "rrr",
+    UniCase::ascii("lwbrx") => "rrr",
+    UniCase::ascii("stdbrx") => "rrr",
+    UniCase::ascii("stwbrx") => "rrr",
+    UniCase::ascii("addi") => "rri",
+    UniCase::ascii("addis") => "rri",
+    UniCase::ascii("addic") => "rri",
+    UniCase::ascii("subfic") => "rri",
+    UniCase::ascii("mulli") => "rri",
+    UniCase::ascii("andi") => "rri",
+    UniCase::ascii("andis") => "rri",
+    UniCase::ascii("ori") => "rri",
+    UniCase::ascii("oris") => "rri",
+    UniCase::ascii("xori") => "rri",
+    UniCase::ascii("xoris") => "rri",
+    UniCase::ascii("srawi") => "rri",
+    UniCase::ascii("sradi") => "rri",
+    UniCase::ascii("rldicl") => "rri",
+    UniCase::ascii("rldicr") => "rri",
+    UniCase::ascii("rldic") => "rri",
+    UniCase::ascii("rldimi") => "rri",
+    UniCase::ascii("slwi") => "rri",
+    UniCase::ascii("srwi") => "rri",
+    UniCase::ascii("sldi") => "rri",
+    UniCase::ascii("srdi") => "rri",
+    UniCase::ascii("clrldi") => "rri",
+    UniCase::ascii("clrlwi") => "rri",
+    UniCase::ascii("extswsli") => "rri",
+    UniCase::ascii("ld") => "rm",
+    UniCase::ascii("ldu") => "rm",
+    UniCase::ascii("lwz") => "rm",
+    UniCase::ascii("lwzu") => "rm",
+    UniCase::ascii("lwa") => "rm",
+    UniCase::ascii("lha") => "rm",
+    UniCase::ascii("lhau") => "rm",
+    UniCase::ascii("lhz") => "rm",
+    UniCase::ascii("lhzu") => "rm",
+    UniCase::ascii("lbz") => "rm",
+    UniCase::ascii("lbzu") => "rm",
+    UniCase::ascii("std") => "rm",
+    UniCase::ascii("stdu") => "rm",
+    UniCase::ascii("stw") => "rm",
+    UniCase::ascii("stwu") => "rm",
+    UniCase::ascii("sth") => "rm",
+    UniCase::ascii("sthu") => "rm",
+    UniCase::ascii("stb") => "rm",
+    UniCase::ascii("stbu") => "rm",
+    UniCase::ascii("lmw") => "rm",
+    UniCase::ascii("stmw") => "rm",
+    UniCase::ascii("rlwinm") => "rriii",
+    UniCase::ascii("rlwimi") => "rriii",
+    UniCase::ascii("isel") => "rrri",
+    UniCase::ascii("mr") => "rr",
+    UniCase::ascii("neg") => "rr",
+    UniCase::ascii("not") => "rr",
+    UniCase::ascii("extsw") => "rr",
+    UniCase::ascii("extsh") => "rr",
+    UniCase::ascii("extsb") => "rr",
+    UniCase::ascii("cntlzw") => "rr",
+    UniCase::ascii("cntlzd") => "rr",
+    UniCase::ascii("cnttzw") => "rr",
+    UniCase::ascii("cnttzd") => "rr",
+    UniCase::ascii("popcntw") => "rr",
+    UniCase::ascii("popcntd") => "rr",
+    UniCase::ascii("popcntb") => "rr",
+    UniCase::ascii("li") => "ri",
+    UniCase::ascii("lis") => "ri",
+    UniCase::ascii("la") => "rm",
+    UniCase::ascii("mflr") => "r",
+    UniCase::ascii("mtlr") => "r",
+    UniCase::ascii("mfctr") => "r",
+    UniCase::ascii("mtctr") => "r",
+    UniCase::ascii("mfcr") => "r",
+    UniCase::ascii("mfxer") => "r",
+    UniCase::ascii("mtxer") => "r",
+    UniCase::ascii("mtcrf") => "ir",
+    UniCase::ascii("setb") => "rc",
+    UniCase::ascii("mfocrf") => "rc",
+    UniCase::ascii("lfd") => "fm",
+    UniCase::ascii("lfdu") => "fm",
+    UniCase::ascii("lfs") => "fm",
+    UniCase::ascii("lfsu") => "fm",
+    UniCase::ascii("stfd") => "fm",
+    UniCase::ascii("stfdu") => "fm",
+    UniCase::ascii("stfs") => "fm",
+    UniCase::ascii("stfsu") => "fm",
+    UniCase::ascii("lfdx") => "frr",
+    UniCase::ascii("lfsx") => "frr",
+    UniCase::ascii("stfdx") => "frr",
+    UniCase::ascii("stfsx") => "frr",
+    UniCase::ascii("lfiwax") => "frr",
+    UniCase::ascii("lfiwzx") => "frr",
+    UniCase::ascii("stfiwx") => "frr",
+    UniCase::ascii("fadd") => "fff",
+    UniCase::ascii("fsub") => "fff",
+    UniCase::ascii("fmul") => "fff",
+    UniCase::ascii("fdiv") => "fff",
+    UniCase::ascii("fadds") => "fff",
+    UniCase::ascii("fsubs") => "fff",
+    UniCase::ascii("fmuls") => "fff",
+    UniCase::ascii("fdivs") => "fff",
+    UniCase::ascii("fcpsgn") => "fff",
+    UniCase::ascii("fmadd") => "ffff",
+    UniCase::ascii("fmsub") => "ffff",
+    UniCase::ascii("fnmadd") => "ffff",
+    UniCase::ascii("fnmsub") => "ffff",
+    UniCase::ascii("fmadds") => "ffff",
+    UniCase::ascii("fmsubs") => "ffff",
+    UniCase::ascii("fnmadds") => "ffff",
+    UniCase::ascii("fnmsubs") => "ffff",
+    UniCase::ascii("fsel") => "ffff",
+    UniCase::ascii("fmr") => "ff",
+    UniCase::ascii("fneg") => "ff",
+    UniCase::ascii("fabs") => "ff",
+    UniCase::ascii("fnabs") => "ff",
+    UniCase::ascii("frsp") => "ff",
+    UniCase::ascii("fsqrt") => "ff",
+    UniCase::ascii("fsqrts") => "ff",
+    UniCase::ascii("fctiw") => "ff",
+    UniCase::ascii("fctiwz") => "ff",
+    UniCase::ascii("fctid") => "ff",
+    UniCase::ascii("fctidz") => "ff",
+    UniCase::ascii("fctiwuz") => "ff",
+    UniCase::ascii("fctiduz") => "ff",
+    UniCase::ascii("fcfid") => "ff",
+    UniCase::ascii("fcfids") => "ff",
+    UniCase::ascii("fcfidu") => "ff",
+    UniCase::ascii("fcfidus") => "ff",
+    UniCase::ascii("frim") => "ff",
+    UniCase::ascii("frip") => "ff",
+    UniCase::ascii("friz") => "ff",
+    UniCase::ascii("frin") => "ff",
+    UniCase::ascii("fcmpu") => "cff",
+    UniCase::ascii("fcmpo") => "cff",
+    UniCase::ascii("cmpwi") => "Cri",
+    UniCase::ascii("cmpdi") => "Cri",
+    UniCase::ascii("cmplwi") => "Cri",
+    UniCase::ascii("cmpldi") => "Cri",
+    UniCase::ascii("cmpw") => "Crr",
+    UniCase::ascii("cmpd") => "Crr",
+    UniCase::ascii("cmplw") => "Crr",
+    UniCase::ascii("cmpld") => "Crr",
+    UniCase::ascii("beq") => "Ci",
+    UniCase::ascii("bne") => "Ci",
+    UniCase::ascii("blt") => "Ci",
+    UniCase::ascii("bgt") => "Ci",
+    UniCase::ascii("ble") => "Ci",
+    UniCase::ascii("bge") => "Ci",
+    UniCase::ascii("bso") => "Ci",
+    UniCase::ascii("bns") => "Ci",
+    UniCase::ascii("beqlr") => "C",
+    UniCase::ascii("bnelr") => "C",
+    UniCase::ascii("bltlr") => "C",
+    UniCase::ascii("bgtlr") => "C",
+    UniCase::ascii("blelr") => "C",
+    UniCase::ascii("bgelr") => "C",
+    UniCase::ascii("mcrf") => "cc",
+    UniCase::ascii("xxland") => "xxx",
+    UniCase::ascii("xxlandc") => "xxx",
+    UniCase::ascii("xxlor") => "xxx",
+    UniCase::ascii("xxlxor") => "xxx",
+    UniCase::ascii("xxlnor") => "xxx",
+    UniCase::ascii("xxlorc") => "xxx",
+    UniCase::ascii("xxmrghw") => "xxx",
+    UniCase::ascii("xxmrglw") => "xxx",
+    UniCase::ascii("xxmrghd") => "xxx",
+    UniCase::ascii("xxmrgld") => "xxx",
+    UniCase::ascii("xsadddp") => "xxx",
+    UniCase::ascii("xssubdp") => "xxx",
+    UniCase::ascii("xsmuldp") => "xxx",
+    UniCase::ascii("xsdivdp") => "xxx",
+    UniCase::ascii("xsmaddadp") => "xxx",
+    UniCase::ascii("xsmaddmdp") => "xxx",
+    UniCase::ascii("xsmsubadp") => "xxx",
+    UniCase::ascii("xsnmaddadp") => "xxx",
+    UniCase::ascii("xsmaxdp") => "xxx",
+    UniCase::ascii("xsmindp") => "xxx",
+    UniCase::ascii("xvadddp") => "xxx",
+    UniCase::ascii("xvsubdp") => "xxx",
+    UniCase::ascii("xvmuldp") => "xxx",
+    UniCase::ascii("xvdivdp") => "xxx",
+    UniCase::ascii("xvaddsp") => "xxx",
+    UniCase::ascii("xvsubsp") => "xxx",
+    UniCase::ascii("xvmulsp") => "xxx",
+    UniCase::ascii("xvdivsp") => "xxx",
+    UniCase::ascii("xvmaddadp") => "xxx",
+    UniCase::ascii("xvmaddasp") => "xxx",
+    UniCase::ascii("xxsel") => "xxxx",
+    UniCase::ascii("xxpermdi") => "xxxi",
+    UniCase::ascii("xxsldwi") => "xxxi",
+    UniCase::ascii("xxspltw") => "xxi",
+    UniCase::ascii("xxspltib") => "xi",
+    UniCase::ascii("lxv") => "xm",
+    UniCase::ascii("stxv") => "xm",
+    UniCase::ascii("xxswapd") => "xx",
+    UniCase::ascii("xsabsdp") => "xx",
+    UniCase::ascii("xsnegdp") => "xx",
+    UniCase::ascii("xssqrtdp") => "xx",
+    UniCase::ascii("xscvdpsp") => "xx",
+    UniCase::ascii("xscvspdp") => "xx",
+    UniCase::ascii("xscvspdpn") => "xx",
+    UniCase::ascii("xscvdpspn") => "xx",
+    UniCase::ascii("xscvdpsxws") => "xx",
+    UniCase::ascii("xscvdpsxds") => "xx",
+    UniCase::ascii("xscvdpuxws") => "xx",
+    UniCase::ascii("xscvdpuxds") => "xx",
+    UniCase::ascii("xscvsxddp") => "xx",
+    UniCase::ascii("xscvuxddp") => "xx",
+    UniCase::ascii("xvabsdp") => "xx",
+    UniCase::ascii("xvnegdp") => "xx",
+    UniCase::ascii("xvsqrtdp") => "xx",
+    UniCase::ascii("xscmpudp") => "cxx",
+    UniCase::ascii("xscmpodp") => "cxx",
+    UniCase::ascii("lxvd2x") => "xrr",
+    UniCase::ascii("lxvw4x") => "xrr",
+    UniCase::ascii("stxvd2x") => "xrr",
+    UniCase::ascii("stxvw4x") => "xrr",
+    UniCase::ascii("lxvx") => "xrr",
+    UniCase::ascii("stxvx") => "xrr",
+    UniCase::ascii("lxsdx") => "xrr",
+    UniCase::ascii("stxsdx") => "xrr",
+    UniCase::ascii("lxsspx") => "xrr",
+    UniCase::ascii("stxsspx") => "xrr",
+    UniCase::ascii("lxsiwax") => "xrr",
+    UniCase::ascii("lxsiwzx") => "xrr",
+    UniCase::ascii("stxsiwx") => "xrr",
+    UniCase::ascii("mtvsrd") => "xr",
+    UniCase::ascii("mtvsrwz") => "xr",
+    UniCase::ascii("mtvsrwa") => "xr",
+    UniCase::ascii("mtvsrdd") => "xr",
+    UniCase::ascii("mtvsrws") => "xr",
+    UniCase::ascii("mfvsrd") => "rx",
+    UniCase::ascii("mfvsrwz") => "rx",
+    UniCase::ascii("mfvsrld") => "rx",
+    UniCase::ascii("lxsd") => "vm",
+    UniCase::ascii("stxsd") => "vm",
+    UniCase::ascii("lxssp") => "vm",
+    UniCase::ascii("stxssp") => "vm",
+    UniCase::ascii("vspltisw") => "vi",
+    UniCase::ascii("vspltish") => "vi",
+    UniCase::ascii("vspltisb") => "vi",
+    UniCase::ascii("vaddubm") => "vvv",
+    UniCase::ascii("vadduhm") => "vvv",
+    UniCase::ascii("vadduwm") => "vvv",
+    UniCase::ascii("vaddudm") => "vvv",
+    UniCase::ascii("vsububm") => "vvv",
+    UniCase::ascii("vsubuhm") => "vvv",
+    UniCase::ascii("vsubuwm") => "vvv",
+    UniCase::ascii("vsubudm") => "vvv",
+    UniCase::ascii("vand") => "vvv",
+    UniCase::ascii("vandc") => "vvv",
+    UniCase::ascii("vor") => "vvv",
+    UniCase::ascii("vnor") => "vvv",
+    UniCase::ascii("vxor") => "vvv",
+    UniCase::ascii("vmrghw") => "vvv",
+    UniCase::ascii("vmrglw") => "vvv",
+    UniCase::ascii("vmrghb") => "vvv",
+    UniCase::ascii("vmrglb") => "vvv",
+    UniCase::ascii("vmrghh") => "vvv",
+    UniCase::ascii("vmrglh") => "vvv",
+    UniCase::ascii("vslw") => "vvv",
+    UniCase::ascii("vsrw") => "vvv",
+    UniCase::ascii("vsraw") => "vvv",
+    UniCase::ascii("vsld") => "vvv",
+    UniCase::ascii("vsrd") => "vvv",
+    UniCase::ascii("vsrad") => "vvv",
+    UniCase::ascii("vcmpequb") => "vvv",
+    UniCase::ascii("vcmpequh") => "vvv",
+    UniCase::ascii("vcmpequw") => "vvv",
+    UniCase::ascii("vcmpequd") => "vvv",
+    UniCase::ascii("vcmpgtsw") => "vvv",
+    UniCase::ascii("vcmpgtuw") => "vvv",
+    UniCase::ascii("vmaxsw") => "vvv",
+    UniCase::ascii("vminsw") => "vvv",
+    UniCase::ascii("vmaxuw") => "vvv",
+    UniCase::ascii("vminuw") => "vvv",
+    UniCase::ascii("vmulouw") => "vvv",
+    UniCase::ascii("vmulosw") => "vvv",
+    UniCase::ascii("vmuleuw") => "vvv",
+    UniCase::ascii("vmulesw") => "vvv",
+    UniCase::ascii("vpkuwum") => "vvv",
+    UniCase::ascii("vpkudum") => "vvv",
+    UniCase::ascii("vperm") => "vvvv",
+    UniCase::ascii("vsel") => "vvvv",
+    UniCase::ascii("vmsumubm") => "vvvv",
+    UniCase::ascii("vsldoi") => "vvvi",
+    UniCase::ascii("vspltw") => "vvi",
+    UniCase::ascii("vsplth") => "vvi",
+    UniCase::ascii("vspltb") => "vvi",
+    UniCase::ascii("lvx") => "vrr",
+    UniCase::ascii("lvxl") => "vrr",
+    UniCase::ascii("stvx") => "vrr",
+    UniCase::ascii("stvxl") => "vrr",
+    UniCase::ascii("lvsl") => "vrr",
+    UniCase::ascii("lvsr") => "vrr",
+    UniCase::ascii("lvewx") => "vrr",
+    UniCase::ascii("stvewx") => "vrr",
+};
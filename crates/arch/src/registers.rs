@@ -67,6 +67,221 @@ impl Registers for AArch64 {
     }
 }
 
+pub struct X86_64 {}
+impl X86_64 {
+    /// Looks up the register number, any AT&T `%` prefix is removed before
+    /// the lookup is performed.
+    fn register_number(name: &str) -> Option<i8> {
+        crate::register_names::X86_64_REGISTERS
+            .get(&UniCase::ascii(parser::register_name(name)))
+            .cloned()
+    }
+
+    /// Gets the size of one of the general purpose registers (including the
+    /// instruction pointer) based on the name that was used to access it.
+    fn general_purpose_size(name: &str) -> RegisterSize {
+        let is_numbered =
+            name.starts_with('r') && name[1..].starts_with(|c: char| c.is_ascii_digit());
+
+        match name.chars().last().unwrap_or('\0') {
+            'd' if is_numbered => RegisterSize::Bits32,
+            'w' if is_numbered => RegisterSize::Bits16,
+            'b' | 'l' if is_numbered => RegisterSize::Bits8,
+            _ if name.starts_with('r') => RegisterSize::Bits64,
+            _ if name.starts_with('e') => RegisterSize::Bits32,
+            'l' | 'h' => RegisterSize::Bits8,
+            _ => RegisterSize::Bits16,
+        }
+    }
+}
+
+impl Registers for X86_64 {
+    fn get_kind(&self, name: &str) -> RegisterKind {
+        match Self::register_number(name) {
+            Some(_) if self.is_sp(name) => RegisterKind::GP_OR_SP,
+            Some(0..=7) | Some(9..=16) => RegisterKind::GENERAL_PURPOSE,
+            Some(17..=48) => RegisterKind::FLOATING_POINT | RegisterKind::SIMD,
+            Some(49..=56) => RegisterKind::PREDICATE,
+            _ => RegisterKind::NONE,
+        }
+    }
+
+    fn get_size(&self, name: &str) -> RegisterSize {
+        let name = parser::register_name(name).to_lowercase();
+
+        match Self::register_number(&name) {
+            Some(0..=16) => Self::general_purpose_size(&name),
+            Some(17..=48) if name.starts_with('x') => RegisterSize::Bits128,
+            Some(17..=48) => RegisterSize::Vector,
+            Some(49..=56) => RegisterSize::Bits64,
+            Some(57..=62) => RegisterSize::Bits16,
+            Some(_) => RegisterSize::Bits64,
+            None => RegisterSize::Unknown,
+        }
+    }
+
+    fn is_sp(&self, name: &str) -> bool {
+        matches!(
+            parser::register_name(name).to_lowercase().as_str(),
+            "rsp" | "esp" | "sp" | "spl"
+        )
+    }
+}
+
+/// 32 bit x86 shares the register numbering with x86-64, however only the
+/// registers that are available in 32 bit mode are considered to be registers.
+pub struct X86 {}
+impl X86 {
+    fn is_register(name: &str) -> bool {
+        crate::register_names::X86_REGISTERS
+            .contains_key(&UniCase::ascii(parser::register_name(name)))
+    }
+}
+
+impl Registers for X86 {
+    fn get_kind(&self, name: &str) -> RegisterKind {
+        if Self::is_register(name) {
+            X86_64 {}.get_kind(name)
+        } else {
+            RegisterKind::NONE
+        }
+    }
+
+    fn get_size(&self, name: &str) -> RegisterSize {
+        if Self::is_register(name) {
+            X86_64 {}.get_size(name)
+        } else {
+            RegisterSize::Unknown
+        }
+    }
+
+    fn is_sp(&self, name: &str) -> bool {
+        Self::is_register(name) && X86_64 {}.is_sp(name)
+    }
+}
+
+pub struct AArch32 {}
+impl AArch32 {
+    /// Looks up the register number, this allows the aliased names for the core
+    /// registers (sp, lr, pc, ...) to be handled.
+    fn register_number(name: &str) -> Option<i8> {
+        crate::register_names::AARCH32_REGISTERS
+            .get(&UniCase::ascii(name))
+            .cloned()
+    }
+}
+
+impl Registers for AArch32 {
+    fn get_kind(&self, name: &str) -> RegisterKind {
+        match Self::register_number(name) {
+            Some(_) if self.is_sp(name) => RegisterKind::GP_OR_SP,
+            Some(0..=15) => RegisterKind::GENERAL_PURPOSE,
+            Some(16..=79) => RegisterKind::FLOATING_POINT,
+            Some(80..=95) => RegisterKind::SIMD,
+            _ => RegisterKind::NONE,
+        }
+    }
+
+    fn get_size(&self, name: &str) -> RegisterSize {
+        match Self::register_number(name) {
+            Some(0..=47) => RegisterSize::Bits32,
+            Some(48..=79) => RegisterSize::Bits64,
+            Some(80..=95) => RegisterSize::Bits128,
+            Some(_) => RegisterSize::Bits32,
+            None => RegisterSize::Unknown,
+        }
+    }
+
+    fn is_sp(&self, name: &str) -> bool {
+        name.eq_ignore_ascii_case("sp") || name.eq_ignore_ascii_case("r13")
+    }
+}
+
+pub struct RiscV64 {}
+impl RiscV64 {
+    /// Looks up the register number, this allows both the architectural names
+    /// (x0-x31, f0-f31) and the ABI names (a0, s1, ft0, ...) to be handled.
+    fn register_number(name: &str) -> Option<i8> {
+        crate::register_names::RISCV64_REGISTERS
+            .get(&UniCase::ascii(name))
+            .cloned()
+    }
+}
+
+impl Registers for RiscV64 {
+    fn get_kind(&self, name: &str) -> RegisterKind {
+        match Self::register_number(name) {
+            Some(_) if self.is_sp(name) => RegisterKind::GP_OR_SP,
+            Some(0..=31) => RegisterKind::GENERAL_PURPOSE,
+            Some(32..=63) => RegisterKind::FLOATING_POINT,
+            _ => RegisterKind::NONE,
+        }
+    }
+
+    fn get_size(&self, name: &str) -> RegisterSize {
+        // RV64GC has 64 bit integer registers, and the D extension makes the
+        // floating point registers 64 bits wide.
+        match Self::register_number(name) {
+            Some(0..=63) => RegisterSize::Bits64,
+            _ => RegisterSize::Unknown,
+        }
+    }
+
+    fn is_sp(&self, name: &str) -> bool {
+        name.eq_ignore_ascii_case("sp") || name.eq_ignore_ascii_case("x2")
+    }
+}
+
+/// Registers for 64 bit PowerPC, the names passed in are expected to have
+/// been resolved from any bare register numbers.
+pub struct PowerPC64 {}
+impl PowerPC64 {
+    fn register_number(name: &str) -> Option<i8> {
+        crate::register_names::POWERPC64_REGISTERS
+            .get(&UniCase::ascii(parser::register_name(name)))
+            .cloned()
+    }
+
+    fn is_vsx(name: &str) -> bool {
+        parser::register_name(name)
+            .get(0..2)
+            .map(|prefix| prefix.eq_ignore_ascii_case("vs"))
+            .unwrap_or(false)
+    }
+}
+
+impl Registers for PowerPC64 {
+    fn get_kind(&self, name: &str) -> RegisterKind {
+        match Self::register_number(name) {
+            Some(_) if self.is_sp(name) => RegisterKind::GP_OR_SP,
+            Some(0..=31) => RegisterKind::GENERAL_PURPOSE,
+            Some(32..=95) if Self::is_vsx(name) => {
+                RegisterKind::FLOATING_POINT | RegisterKind::SIMD
+            }
+            Some(32..=63) => RegisterKind::FLOATING_POINT,
+            Some(64..=95) => RegisterKind::SIMD,
+            _ => RegisterKind::NONE,
+        }
+    }
+
+    fn get_size(&self, name: &str) -> RegisterSize {
+        match Self::register_number(name) {
+            Some(32..=95) if Self::is_vsx(name) => RegisterSize::Bits128,
+            Some(0..=63) => RegisterSize::Bits64,
+            Some(64..=95) => RegisterSize::Bits128,
+            // The condition register fields are 4 bits
+            Some(96..=103) => RegisterSize::Unknown,
+            Some(_) => RegisterSize::Bits64,
+            None => RegisterSize::Unknown,
+        }
+    }
+
+    fn is_sp(&self, name: &str) -> bool {
+        let name = parser::register_name(name);
+        name.eq_ignore_ascii_case("sp") || name.eq_ignore_ascii_case("r1")
+    }
+}
+
 pub struct UnknownRegisters {}
 impl Registers for UnknownRegisters {
     fn get_kind(&self, _register: &str) -> RegisterKind {
@@ -84,10 +299,19 @@ impl Registers for UnknownRegisters {
 
 pub fn registers_for_architecture(arch: &Architecture) -> &dyn Registers {
     static REGISTER_AARCH64: &AArch64 = &AArch64 {};
+    static REGISTER_AARCH32: &AArch32 = &AArch32 {};
+    static REGISTER_X86_64: &X86_64 = &X86_64 {};
+    static REGISTER_X86: &X86 = &X86 {};
+    static REGISTER_RISCV64: &RiscV64 = &RiscV64 {};
+    static REGISTER_POWERPC64: &PowerPC64 = &PowerPC64 {};
     static REGISTER_NONE: &UnknownRegisters = &UnknownRegisters {};
     match arch {
         Architecture::AArch64 => REGISTER_AARCH64,
-        Architecture::X86_64 => REGISTER_NONE,
+        Architecture::AArch32 => REGISTER_AARCH32,
+        Architecture::X86_64 => REGISTER_X86_64,
+        Architecture::X86 => REGISTER_X86,
+        Architecture::RiscV64 => REGISTER_RISCV64,
+        Architecture::PowerPC64 => REGISTER_POWERPC64,
         Architecture::Unknown => REGISTER_NONE,
     }
 }
@@ -102,7 +326,11 @@ impl RegisterList {
         Self {
             map: match arch {
                 Architecture::AArch64 => &crate::register_names::AARCH64_REGISTERS,
+                Architecture::AArch32 => &crate::register_names::AARCH32_REGISTERS,
                 Architecture::X86_64 => &crate::register_names::X86_64_REGISTERS,
+                Architecture::X86 => &crate::register_names::X86_REGISTERS,
+                Architecture::RiscV64 => &crate::register_names::RISCV64_REGISTERS,
+                Architecture::PowerPC64 => &crate::register_names::POWERPC64_REGISTERS,
                 Architecture::Unknown => &crate::register_names::UNKNOWN_REGISTERS,
             },
         }
@@ -5,17 +5,47 @@ use macros::VariantsIter;
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, VariantsIter)]
 pub enum Architecture {
     AArch64,
+    AArch32,
     X86_64,
+    X86,
+    RiscV64,
+    PowerPC64,
     Unknown,
 }
 
+/// Prefixes of the 32 bit Arm architecture and CPU names that can be found in
+/// `.arch` and `.cpu` directives e.g. armv7-a, armv7e-m, thumbv7em, cortex-m4
+const AARCH32_PREFIXES: [&str; 10] = [
+    "armv4",
+    "armv5",
+    "armv6",
+    "armv7",
+    "armv8-m",
+    "armv8.1-m",
+    "armv8-r",
+    "thumbv",
+    "cortex-m",
+    "cortex-r",
+];
+
 impl From<&str> for Architecture {
     /// Converts known text based names for architectures into the `Architecture` enum variant for it.
     fn from(arch: &str) -> Self {
         debug!("Architecture::from: {:?}", arch);
         match arch.to_lowercase().as_str() {
             "x86_64" | "x86-64" | "64-bit x86-64" => Architecture::X86_64,
+            "x86" | "i386" | "i486" | "i586" | "i686" | "ia32" => Architecture::X86,
             "aarch64" | "littleaarch64" | "armv8-a" | "arm64" => Architecture::AArch64,
+            "arm" | "aarch32" | "littlearm" | "thumb" => Architecture::AArch32,
+            isa if AARCH32_PREFIXES.iter().any(|p| isa.starts_with(p)) => Architecture::AArch32,
+            "riscv64" | "littleriscv" => Architecture::RiscV64,
+            // ISA strings from `.attribute arch` e.g. rv64i2p1_m2p0_a2p1_f2p2_d2p2_c2p0
+            isa if isa.starts_with("rv64") => Architecture::RiscV64,
+            "powerpc64" | "powerpc64le" | "powerpcle" | "powerpc" | "ppc64" | "ppc64le" => {
+                Architecture::PowerPC64
+            }
+            // CPU names from `.machine` directives e.g. power8, power9
+            isa if isa.starts_with("power") => Architecture::PowerPC64,
             _ => Architecture::Unknown,
         }
     }
@@ -34,7 +64,11 @@ impl Display for Architecture {
             "{}",
             match *self {
                 Architecture::AArch64 => "aarch64",
+                Architecture::AArch32 => "arm",
                 Architecture::X86_64 => "x86-64",
+                Architecture::X86 => "x86",
+                Architecture::RiscV64 => "riscv64",
+                Architecture::PowerPC64 => "ppc64le",
                 Architecture::Unknown => "UNKNOWN",
             }
         )
@@ -45,7 +79,11 @@ impl Architecture {
     pub fn to_llvm(&self) -> String {
         String::from(match self {
             Architecture::X86_64 => "x86-64",
+            Architecture::X86 => "x86",
             Architecture::AArch64 => "aarch64",
+            Architecture::AArch32 => "arm",
+            Architecture::RiscV64 => "riscv64",
+            Architecture::PowerPC64 => "ppc64le",
             Architecture::Unknown => "native",
         })
     }
@@ -58,7 +96,11 @@ impl Architecture {
     pub fn default_comment_start(&self) -> &str {
         match self {
             Architecture::AArch64 => "//",
+            Architecture::AArch32 => "@",
             Architecture::X86_64 => "#",
+            Architecture::X86 => "#",
+            Architecture::RiscV64 => "#",
+            Architecture::PowerPC64 => "#",
             Architecture::Unknown => "#",
         }
     }
@@ -97,7 +139,24 @@ mod tests {
         assert_eq!(Architecture::from("aarch64"), Architecture::AArch64);
         assert_eq!(Architecture::from("littleaarch64"), Architecture::AArch64);
         assert_eq!(Architecture::from("arm64"), Architecture::AArch64);
+        assert_eq!(Architecture::from("arm"), Architecture::AArch32);
+        assert_eq!(Architecture::from("littlearm"), Architecture::AArch32);
+        assert_eq!(Architecture::from("armv7e-m"), Architecture::AArch32);
+        assert_eq!(Architecture::from("cortex-m4"), Architecture::AArch32);
         assert_eq!(Architecture::from("x86_64"), Architecture::X86_64);
+        assert_eq!(Architecture::from("i386"), Architecture::X86);
+        assert_eq!(Architecture::from("x86"), Architecture::X86);
+        assert_eq!(Architecture::from("riscv64"), Architecture::RiscV64);
+        assert_eq!(Architecture::from("littleriscv"), Architecture::RiscV64);
+        assert_eq!(
+            Architecture::from("rv64i2p1_m2p0_a2p1_f2p2_d2p2_c2p0"),
+            Architecture::RiscV64
+        );
+        assert_eq!(Architecture::from("powerpcle"), Architecture::PowerPC64);
+        assert_eq!(Architecture::from("ppc64le"), Architecture::PowerPC64);
+        assert_eq!(Architecture::from("power9"), Architecture::PowerPC64);
+        assert_eq!(Architecture::from("riscv"), Architecture::Unknown);
+        assert_eq!(Architecture::from("rv32imac"), Architecture::Unknown);
         assert_eq!(Architecture::from("something"), Architecture::Unknown);
     }
 }
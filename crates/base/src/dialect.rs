@@ -0,0 +1,164 @@
+use once_cell::sync::Lazy;
+
+/// The syntax dialect that is used by the assembly, this is only meaningful
+/// for x86 where the AT&T, Intel and NASM syntax are in common use.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Dialect {
+    /// AT&T syntax e.g. `movq $1, %rax`, operands are ordered source, destination
+    #[default]
+    Att,
+    /// Intel syntax e.g. `mov rax, 1`, operands are ordered destination, source
+    Intel,
+    /// NASM/YASM syntax e.g. `mov rax, [rel label]`, operands are ordered the
+    /// same as Intel syntax however the directives and comments differ.
+    Nasm,
+}
+
+impl Dialect {
+    /// NASM sources are conventionally given their own extension, the GAS
+    /// extensions aren't used as they don't determine the dialect.
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let extension = filename.rsplit_once('.')?.1;
+
+        (extension.eq_ignore_ascii_case("asm") || extension.eq_ignore_ascii_case("nasm"))
+            .then_some(Dialect::Nasm)
+    }
+
+    /// Determines if the operands are written in the Intel order and without
+    /// any register prefixes.
+    pub fn is_intel(&self) -> bool {
+        matches!(self, Dialect::Intel | Dialect::Nasm)
+    }
+
+    pub fn from_contents(contents: &str) -> Self {
+        use regex::Regex;
+
+        // Explicit selections take priority over anything that we have to guess
+        static DIALECT_SELECTION: Lazy<[Regex; 2]> = Lazy::new(|| {
+            [
+                Regex::new(r#"lsp-asm-syntax: *(intel|att|nasm)"#).unwrap(),
+                Regex::new(r#"(?m)^\s*\.(intel|att)_syntax\b"#).unwrap(),
+            ]
+        });
+
+        // NASM directives don't have the `.` prefix that GAS uses
+        static NASM_DETECTION: Lazy<[Regex; 4]> = Lazy::new(|| {
+            [
+                Regex::new(r#"(?mi)^\s*(?:section|segment|global|extern)\s+\.?\w"#).unwrap(),
+                Regex::new(r#"(?mi)^\s*%(?:define|xdefine|assign|macro|imacro|include)\b"#)
+                    .unwrap(),
+                Regex::new(r#"(?mi)^\s*\[?bits\s+(?:16|32|64)\b"#).unwrap(),
+                Regex::new(r#"(?mi)^\s*\w+:?\s+(?:d[bwdq]|res[bwdq])\s"#).unwrap(),
+            ]
+        });
+
+        // Output from `objdump -M intel` and compilers without the directive
+        // don't state the syntax, however in Intel syntax registers don't
+        // have a `%` prefix and memory operands can have a size e.g. `qword ptr`
+        static INTEL_DETECTION: Lazy<[Regex; 2]> = Lazy::new(|| {
+            [
+                Regex::new(r#"(?i)\bptr\s*\["#).unwrap(),
+                Regex::new(
+                    r#"(?m)\t[a-z][a-z0-9]*[ \t]+(?:[re](?:[abcd]x|[sd]i|[sb]p)|r(?:[89]|1[0-5])[dwb]?)\b"#,
+                )
+                .unwrap(),
+            ]
+        });
+
+        // A symbol can have the same name as a register, so registers without
+        // the prefix only mean Intel syntax when no register has the prefix
+        static ATT_REGISTER: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r#"(?i)%[a-z][a-z0-9]*\b"#).unwrap());
+
+        DIALECT_SELECTION
+            .iter()
+            .find_map(|regex| {
+                regex
+                    .captures(contents)
+                    .and_then(|captures| captures.get(1))
+                    .map(|dialect| match dialect.as_str() {
+                        "intel" => Dialect::Intel,
+                        "nasm" => Dialect::Nasm,
+                        _ => Dialect::Att,
+                    })
+            })
+            .or_else(|| {
+                NASM_DETECTION
+                    .iter()
+                    .any(|regex| regex.is_match(contents))
+                    .then_some(Dialect::Nasm)
+            })
+            .or_else(|| {
+                let [size, bare_register] = &*INTEL_DETECTION;
+                (size.is_match(contents)
+                    || (bare_register.is_match(contents) && !ATT_REGISTER.is_match(contents)))
+                .then_some(Dialect::Intel)
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dialect_directives() {
+        assert_eq!(
+            Dialect::from_contents("\t.intel_syntax noprefix\n\tmov\trax, 1"),
+            Dialect::Intel
+        );
+        assert_eq!(
+            Dialect::from_contents("\t.att_syntax\n\tmovq\t$1, %rax"),
+            Dialect::Att
+        );
+        assert_eq!(
+            Dialect::from_contents("# lsp-asm-syntax: intel\n"),
+            Dialect::Intel
+        );
+        assert_eq!(
+            Dialect::from_contents("; lsp-asm-syntax: nasm\n"),
+            Dialect::Nasm
+        );
+    }
+
+    #[test]
+    fn test_dialect_filename() {
+        assert_eq!(
+            Dialect::from_filename("/src/codec.asm"),
+            Some(Dialect::Nasm)
+        );
+        assert_eq!(Dialect::from_filename("codec.NASM"), Some(Dialect::Nasm));
+        assert_eq!(Dialect::from_filename("codec.S"), None);
+        assert_eq!(Dialect::from_filename("codec"), None);
+    }
+
+    #[test]
+    fn test_dialect_detection() {
+        assert_eq!(
+            Dialect::from_contents("\tmovq\t%rsp, %rbp\n\tmovl\t-4(%rbp), %eax"),
+            Dialect::Att
+        );
+        assert_eq!(
+            Dialect::from_contents("\tmov\teax, DWORD PTR [rbp-4]"),
+            Dialect::Intel
+        );
+        assert_eq!(
+            Dialect::from_contents("    1130:\t55                   \tpush   rbp"),
+            Dialect::Intel
+        );
+        assert_eq!(
+            Dialect::from_contents("section .text\nglobal main\nmain:\n\tmov rax, 1"),
+            Dialect::Nasm
+        );
+        assert_eq!(Dialect::from_contents("msg db \"hello\", 0"), Dialect::Nasm);
+        assert_eq!(
+            Dialect::from_contents("\tleaq\trdi(%rip), %rax\n\tmovq\t%rax, %rdi"),
+            Dialect::Att
+        );
+        assert_eq!(
+            Dialect::from_contents("\t.section .text\n\t.globl main"),
+            Dialect::Att
+        );
+    }
+}
@@ -76,7 +76,8 @@ impl ObjDumpOptions {
             }};
         }
 
-        let show_raw_insn = regex_detect!(r#"^([0-9a-fA-F]*:)? *\t"#);
+        // Relocations from `-r` are on their own line after several tabs
+        let show_raw_insn = regex_detect!(r#"^([0-9a-fA-F]*:)? *\t[^\t]"#);
         let show_leading_addr = regex_detect!(r#"^<.*>:"#);
 
         Self {
@@ -106,6 +107,20 @@ Disassembly of section .text:
         assert_eq!(ObjDumpOptions::from_contents(input), Default::default());
     }
 
+    #[test]
+    fn relocations() {
+        let input = "
+test.o:     file format elf64-x86-64
+
+
+Disassembly of section .text:
+
+0000000000000000 <main>:
+   0:\te8 00 00 00 00       \tcall   5 <main+0x5>
+\t\t\t1: R_X86_64_PLT32\tfoo-0x4";
+        assert_eq!(ObjDumpOptions::from_contents(input), Default::default());
+    }
+
     #[test]
     fn no_show_raw_insn() {
         let input = r#"
@@ -4,9 +4,11 @@ extern crate log;
 pub mod register;
 pub mod rwlock;
 pub use architecture::*;
+pub use dialect::*;
 pub use filetype::*;
 
 mod architecture;
+mod dialect;
 mod filetype;
 
 use serde::{Deserialize, Deserializer};
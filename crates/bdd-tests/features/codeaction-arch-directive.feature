@@ -11,7 +11,11 @@ Feature: codeaction insert lsp-asm-architecture directive
     Then I expect the following response
       | file | name                                           | start |  end | text                                 |
       | T1   | Insert lsp-asm-architecture: aarch64 directive |   1:0 | 1:0  | // lsp-asm-architecture: aarch64{\n} |
+      | T1   | Insert lsp-asm-architecture: arm directive     |   1:0 | 1:0  | @ lsp-asm-architecture: arm{\n}      |
       | T1   | Insert lsp-asm-architecture: x86-64 directive  |   1:0 | 1:0  | # lsp-asm-architecture: x86-64{\n}   |
+      | T1   | Insert lsp-asm-architecture: x86 directive     |   1:0 | 1:0  | # lsp-asm-architecture: x86{\n}      |
+      | T1   | Insert lsp-asm-architecture: riscv64 directive |   1:0 | 1:0  | # lsp-asm-architecture: riscv64{\n}  |
+      | T1   | Insert lsp-asm-architecture: ppc64le directive |   1:0 | 1:0  | # lsp-asm-architecture: ppc64le{\n}  |
 
   Scenario: lsp-asm-architecture comment already inserted
     Given an lsp initialized with the following parameters
@@ -95,3 +95,15 @@ Feature: Debug location code lens
     When I run "codelens" on the file "./features/test-files/debug-loc.s" at position "1:0"
     Then I expect the following response
       | range | content | command |
+
+  Scenario: Debug location code lens with a name relative to the assembly file
+    Given an lsp initialized with the following parameters
+      | key                   | value   |
+      | architecture          | aarch64 |
+      | codelens::loc_enabled | true    |
+    When I open the file "./features/test-files/lens.s"
+    When I run "codelens" on the file "./features/test-files/lens.s" at position "1:0"
+    Then I expect the following response
+      | range    | content | command     | location | file                           |
+      | 2:1-2:11 | Line 0  | lsp-asm.loc | 1:0-1:0  | ./features/test-files/lens.txt |
+      | 4:1-4:21 | Line 5  | lsp-asm.loc | 6:0-6:0  | ./features/test-files/lens.txt |
@@ -6,10 +6,12 @@ Feature: Testing document symbols
     When I open the file "./features/test-files/multiple-functions.s"
     When I run "document symbols" on the file "./features/test-files/multiple-functions.s" at position "1:0"
     Then I expect the following response
-      | id | name                 | kind     |     range | sel_range | p_id |
-      |  1 | process:             | function |  7:0-28:0 |  7:0-28:0 |      |
-      |  2 | .Lfunc_end0:         | function | 21:0-28:0 | 21:0-28:0 |    1 |
-      |  3 | some_other_function: | function | 28:0-53:0 | 28:0-53:0 |      |
-      |  4 | .Lfunc_end1:         | function | 46:0-53:0 | 46:0-53:0 |    3 |
-      |  5 | main:                | function | 53:0-80:0 | 53:0-80:0 |      |
-      |  6 | .Lfunc_end2:         | function | 71:0-80:0 | 71:0-80:0 |    5 |
+      | id | name                 | kind      | detail          |     range |  sel_range | p_id |
+      |  1 | .text                | namespace | section         |  2:1-76:1 |    2:1-2:6 |      |
+      |  2 | process:             | function  | global function |  7:0-28:0 |   7:0-28:0 |    1 |
+      |  3 | .Lfunc_end0:         | function  |                 | 21:0-28:0 |  21:0-28:0 |    2 |
+      |  4 | some_other_function: | function  | global function | 28:0-53:0 |  28:0-53:0 |    1 |
+      |  5 | .Lfunc_end1:         | function  |                 | 46:0-53:0 |  46:0-53:0 |    4 |
+      |  6 | main:                | function  | global function | 53:0-76:1 |  53:0-76:1 |    1 |
+      |  7 | .Lfunc_end2:         | function  |                 | 71:0-76:1 |  71:0-76:1 |    6 |
+      |  8 | .note.GNU-stack      | namespace | section         | 76:1-80:0 | 76:1-76:40 |      |
@@ -13,7 +13,9 @@ Feature: Testing equ directive
     When I run "document hover" on the file "t1" at position "3:16"
     Then I expect the following response
       """
-      `number` is defined as `12`
+      `number` is defined as `12`  
+      Decimal: 12  
+      Hex: 0xC
       """
     When I run "document highlight" on the file "t1" at position "3:16"
     Then I expect the following response
@@ -55,7 +57,9 @@ Feature: Testing equ directive
     When I run "document hover" on the file "t1" at position "3:16"
     Then I expect the following response
       """
-      `number` is defined as `12`
+      `number` is defined as `12`  
+      Decimal: 12  
+      Hex: 0xC
       """
     When I run "document highlight" on the file "t1" at position "3:16"
     Then I expect the following response
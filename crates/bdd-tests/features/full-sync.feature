@@ -18,14 +18,17 @@ ROOT@0..29
     REGISTER@4..6 "x1"
     COMMA@6..7 ","
     WHITESPACE@7..8 " "
-    BRACKETS@8..17
-      L_SQ@8..9 "["
-      REGISTER@9..11 "sp"
-      COMMA@11..12 ","
-      WHITESPACE@12..13 " "
-      IMMEDIATE@13..14 "#"
-      NUMBER@14..16 "80"
-      R_SQ@16..17 "]"
+    MEMORY_OPERAND@8..17
+      BRACKETS@8..17
+        L_SQ@8..9 "["
+        BASE@9..11
+          REGISTER@9..11 "sp"
+        COMMA@11..12 ","
+        WHITESPACE@12..13 " "
+        DISPLACEMENT@13..16
+          IMMEDIATE@13..14 "#"
+          NUMBER@14..16 "80"
+        R_SQ@16..17 "]"
   WHITESPACE@17..18 "\n"
   INSTRUCTION@18..29
     MNEMONIC@18..22 "fmov"
@@ -49,12 +52,15 @@ ROOT@0..17
     REGISTER@4..6 "x1"
     COMMA@6..7 ","
     WHITESPACE@7..8 " "
-    BRACKETS@8..17
-      L_SQ@8..9 "["
-      REGISTER@9..11 "sp"
-      COMMA@11..12 ","
-      WHITESPACE@12..13 " "
-      IMMEDIATE@13..14 "#"
-      NUMBER@14..16 "80"
-      R_SQ@16..17 "]"
+    MEMORY_OPERAND@8..17
+      BRACKETS@8..17
+        L_SQ@8..9 "["
+        BASE@9..11
+          REGISTER@9..11 "sp"
+        COMMA@11..12 ","
+        WHITESPACE@12..13 " "
+        DISPLACEMENT@13..16
+          IMMEDIATE@13..14 "#"
+          NUMBER@14..16 "80"
+        R_SQ@16..17 "]"
       """
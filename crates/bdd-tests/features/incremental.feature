@@ -18,14 +18,17 @@ ROOT@0..29
     REGISTER@4..6 "x1"
     COMMA@6..7 ","
     WHITESPACE@7..8 " "
-    BRACKETS@8..17
-      L_SQ@8..9 "["
-      REGISTER@9..11 "sp"
-      COMMA@11..12 ","
-      WHITESPACE@12..13 " "
-      IMMEDIATE@13..14 "#"
-      NUMBER@14..16 "80"
-      R_SQ@16..17 "]"
+    MEMORY_OPERAND@8..17
+      BRACKETS@8..17
+        L_SQ@8..9 "["
+        BASE@9..11
+          REGISTER@9..11 "sp"
+        COMMA@11..12 ","
+        WHITESPACE@12..13 " "
+        DISPLACEMENT@13..16
+          IMMEDIATE@13..14 "#"
+          NUMBER@14..16 "80"
+        R_SQ@16..17 "]"
   WHITESPACE@17..18 "\n"
   INSTRUCTION@18..29
     MNEMONIC@18..22 "fmov"
@@ -52,14 +55,17 @@ ROOT@0..62
     REGISTER@37..39 "x1"
     COMMA@39..40 ","
     WHITESPACE@40..41 " "
-    BRACKETS@41..50
-      L_SQ@41..42 "["
-      REGISTER@42..44 "sp"
-      COMMA@44..45 ","
-      WHITESPACE@45..46 " "
-      IMMEDIATE@46..47 "#"
-      NUMBER@47..49 "80"
-      R_SQ@49..50 "]"
+    MEMORY_OPERAND@41..50
+      BRACKETS@41..50
+        L_SQ@41..42 "["
+        BASE@42..44
+          REGISTER@42..44 "sp"
+        COMMA@44..45 ","
+        WHITESPACE@45..46 " "
+        DISPLACEMENT@46..49
+          IMMEDIATE@46..47 "#"
+          NUMBER@47..49 "80"
+        R_SQ@49..50 "]"
   WHITESPACE@50..51 "\n"
   INSTRUCTION@51..62
     MNEMONIC@51..55 "fmov"
@@ -89,14 +95,17 @@ ROOT@0..29
     REGISTER@4..6 "x1"
     COMMA@6..7 ","
     WHITESPACE@7..8 " "
-    BRACKETS@8..17
-      L_SQ@8..9 "["
-      REGISTER@9..11 "sp"
-      COMMA@11..12 ","
-      WHITESPACE@12..13 " "
-      IMMEDIATE@13..14 "#"
-      NUMBER@14..16 "80"
-      R_SQ@16..17 "]"
+    MEMORY_OPERAND@8..17
+      BRACKETS@8..17
+        L_SQ@8..9 "["
+        BASE@9..11
+          REGISTER@9..11 "sp"
+        COMMA@11..12 ","
+        WHITESPACE@12..13 " "
+        DISPLACEMENT@13..16
+          IMMEDIATE@13..14 "#"
+          NUMBER@14..16 "80"
+        R_SQ@16..17 "]"
   WHITESPACE@17..18 "\n"
   INSTRUCTION@18..29
     MNEMONIC@18..22 "fmov"
@@ -120,13 +129,16 @@ ROOT@0..18
     REGISTER@4..6 "x1"
     COMMA@6..7 ","
     WHITESPACE@7..8 " "
-    BRACKETS@8..17
-      L_SQ@8..9 "["
-      REGISTER@9..11 "sp"
-      COMMA@11..12 ","
-      WHITESPACE@12..13 " "
-      IMMEDIATE@13..14 "#"
-      NUMBER@14..16 "80"
-      R_SQ@16..17 "]"
+    MEMORY_OPERAND@8..17
+      BRACKETS@8..17
+        L_SQ@8..9 "["
+        BASE@9..11
+          REGISTER@9..11 "sp"
+        COMMA@11..12 ","
+        WHITESPACE@12..13 " "
+        DISPLACEMENT@13..16
+          IMMEDIATE@13..14 "#"
+          NUMBER@14..16 "80"
+        R_SQ@16..17 "]"
   WHITESPACE@17..18 "\n"
       """
@@ -12,7 +12,11 @@ Feature: Completion of internal directives
     Then I expect the following response
       | label   | details | kind |
       | aarch64 |         | text |
+      | arm     |         | text |
       | x86-64  |         | text |
+      | x86     |         | text |
+      | riscv64 |         | text |
+      | ppc64le |         | text |
       | UNKNOWN |         | text |
 
   Scenario: Complete lsp-asm-architecture without docs
@@ -27,5 +31,9 @@ Feature: Completion of internal directives
     Then I expect the following response
       | label   | details | kind |
       | aarch64 |         | text |
+      | arm     |         | text |
       | x86-64  |         | text |
+      | x86     |         | text |
+      | riscv64 |         | text |
+      | ppc64le |         | text |
       | UNKNOWN |         | text |
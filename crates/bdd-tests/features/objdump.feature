@@ -59,26 +59,26 @@ Hex: 0x0
     When I open the file "./features/test-files/objdump.s"
     When I run "semantic tokens" on the file "./features/test-files/objdump.s" at position "1:0-11:0"
     Then I expect the following response
-      | delta line | delta start | length | token type | modifiers |
-      |          1 |           0 |     35 | metadata   |         0 |
-      |          3 |           0 |     29 | metadata   |         0 |
-      |          2 |           0 |     16 | metadata   |         0 |
-      |          0 |          17 |      8 | label      |         0 |
-      |          1 |           2 |      6 | metadata   |         0 |
-      |          0 |           6 |      1 | metadata   |         0 |
-      |          0 |           2 |     21 | metadata   |         0 |
-      |          0 |          22 |      7 | opcode     |         0 |
-      |          1 |           2 |      6 | metadata   |         0 |
-      |          0 |           6 |      1 | metadata   |         0 |
-      |          0 |           2 |     21 | metadata   |         0 |
-      |          0 |          22 |      3 | opcode     |         0 |
-      |          0 |           7 |      4 | number     |         0 |
-      |          0 |           5 |      4 | register   |         0 |
-      |          1 |           2 |      6 | metadata   |         0 |
-      |          0 |           6 |      1 | metadata   |         0 |
-      |          0 |           2 |     21 | metadata   |         0 |
-      |          0 |          22 |      3 | opcode     |         0 |
-      |          0 |           7 |      6 | number     |         0 |
-      |          0 |           7 |      4 | register   |         0 |
-      |          0 |           6 |      4 | register   |         0 |
-      |          0 |          12 |     25 | comment    |         0 |
+      | delta line | delta start | length | token type  | modifiers |
+      |          1 |           0 |     35 | metadata    |         0 |
+      |          3 |           0 |     29 | metadata    |         0 |
+      |          2 |           0 |     16 | metadata    |         0 |
+      |          0 |          17 |      8 | label       |         0 |
+      |          1 |           2 |      6 | metadata    |         0 |
+      |          0 |           6 |      1 | metadata    |         0 |
+      |          0 |           2 |     21 | metadata    |         0 |
+      |          0 |          22 |      7 | opcode      |         0 |
+      |          1 |           2 |      6 | metadata    |         0 |
+      |          0 |           6 |      1 | metadata    |         0 |
+      |          0 |           2 |     21 | metadata    |         0 |
+      |          0 |          22 |      3 | opcode      |         0 |
+      |          0 |           7 |      4 | number      |         0 |
+      |          0 |           5 |      4 | gp-register |         0 |
+      |          1 |           2 |      6 | metadata    |         0 |
+      |          0 |           6 |      1 | metadata    |         0 |
+      |          0 |           2 |     21 | metadata    |         0 |
+      |          0 |          22 |      3 | opcode      |         0 |
+      |          0 |           7 |      6 | number      |         0 |
+      |          0 |           7 |      4 | register    |         0 |
+      |          0 |           6 |      4 | gp-register |         0 |
+      |          0 |          12 |     25 | comment     |         0 |
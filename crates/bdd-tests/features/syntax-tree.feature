@@ -14,7 +14,8 @@ Feature: View syntax tree
   INSTRUCTION@0..14
     MNEMONIC@0..4 "subq"
     WHITESPACE@4..5 " "
-    NUMBER@5..8 "$56"
+    IMMEDIATE@5..6 "$"
+    NUMBER@6..8 "56"
     COMMA@8..9 ","
     WHITESPACE@9..10 " "
     REGISTER@10..14 "%rsp"
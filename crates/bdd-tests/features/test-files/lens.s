@@ -0,0 +1,4 @@
+	.file	2 "lens.txt"
+	.loc	2 1 0
+	sub	sp, sp, #64
+	.loc	2 6 6 is_stmt 1
@@ -12,7 +12,6 @@ use lsp_types::{
     SignatureHelp, SignatureInformation, SymbolKind, TextDocumentEdit, TextEdit, Url,
     WorkspaceEdit,
 };
-use std::collections::HashMap;
 use std::path::Path;
 
 use crate::file::FileUrl;
@@ -60,42 +59,50 @@ pub(crate) fn make_lsp_doc_location(file: &FileUrl, table: &[Vec<String>]) -> Ve
 }
 
 pub(crate) fn make_doc_symbol(table: &[Vec<String>]) -> Vec<DocumentSymbol> {
-    let mut symbols: HashMap<u32, DocumentSymbol> = HashMap::new();
-
-    for row in table.iter().skip(1) {
-        let symbol = DocumentSymbol {
-            name: row.get(1).unwrap().to_string(),
-            detail: None,
-            kind: match row.get(2).unwrap().as_str() {
+    let rows = table
+        .iter()
+        .skip(1)
+        .map(|row| {
+            let kind = match row.get(2).unwrap().as_str() {
                 "function" => SymbolKind::FUNCTION,
+                "namespace" => SymbolKind::NAMESPACE,
                 kind => panic!("Unknown kind: {kind}"),
-            },
-            tags: None,
-            deprecated: None,
-            range: PositionString::from_string(row.get(3).unwrap().into()).into(),
-            selection_range: PositionString::from_string(row.get(4).unwrap().into()).into(),
-            children: None,
-        };
-        let pid = row
-            .get(5)
-            .unwrap()
-            .parse::<u32>()
-            .unwrap_or_else(|_| row.get(0).unwrap().parse().unwrap());
+            };
+            let symbol = DocumentSymbol {
+                name: row.get(1).unwrap().to_string(),
+                detail: Some(row.get(3).unwrap().to_string()).filter(|d| !d.is_empty()),
+                kind,
+                tags: None,
+                deprecated: None,
+                range: PositionString::from_string(row.get(4).unwrap().into()).into(),
+                selection_range: PositionString::from_string(row.get(5).unwrap().into()).into(),
+                children: (kind == SymbolKind::NAMESPACE).then(Vec::new),
+            };
+            let id = row.get(0).unwrap().parse::<u32>().unwrap();
+            let pid = row.get(6).unwrap().parse::<u32>().ok();
 
-        if let Some(parent) = symbols.get_mut(&pid) {
-            if let Some(c) = parent.children.as_mut() {
-                c.push(symbol);
-            } else {
-                parent.children = Some(vec![symbol]);
-            }
-        } else {
-            symbols.insert(pid, symbol);
-        }
+            (id, pid, symbol)
+        })
+        .collect_vec();
+
+    fn children(
+        rows: &[(u32, Option<u32>, DocumentSymbol)],
+        pid: Option<u32>,
+    ) -> Vec<DocumentSymbol> {
+        rows.iter()
+            .filter(|(_, parent, _)| *parent == pid)
+            .map(|(id, _, symbol)| {
+                let mut symbol = symbol.clone();
+                let children = children(rows, Some(*id));
+                if !children.is_empty() {
+                    symbol.children = Some(children);
+                }
+                symbol
+            })
+            .collect()
     }
 
-    let mut v = symbols.values().cloned().collect::<Vec<_>>();
-    v.sort_by(|a, b| a.range.start.partial_cmp(&b.range.start).unwrap());
-    v
+    children(&rows, None)
 }
 
 pub(crate) fn make_doc_highlight(table: &[Vec<String>]) -> Vec<DocumentHighlight> {
@@ -148,7 +155,13 @@ pub(crate) fn make_codelens(table: &[Vec<String>]) -> Option<Vec<CodeLens>> {
                 command: Some(Command {
                     title: row.get(1).unwrap().into(),
                     command: row.get(2).unwrap().into(),
-                    arguments: None,
+                    arguments: row.get(4).map(|file| {
+                        vec![serde_json::to_value(Location {
+                            uri: file_to_uri(file),
+                            range: PositionString::from_string(row.get(3).unwrap().into()).into(),
+                        })
+                        .unwrap()]
+                    }),
                 }),
                 data: None,
             })
@@ -30,7 +30,11 @@ type RunActionFn = fn(&ActionContext) -> Option<Vec<CodeAction>>;
 
 define_actions!(
     (Architecture::AArch64, lsp_asm_arch_directive),
+    (Architecture::AArch32, lsp_asm_arch_directive),
     (Architecture::X86_64, lsp_asm_arch_directive),
+    (Architecture::X86, lsp_asm_arch_directive),
+    (Architecture::RiscV64, lsp_asm_arch_directive),
+    (Architecture::PowerPC64, lsp_asm_arch_directive),
     (Architecture::Unknown, lsp_asm_arch_directive)
 );
 
@@ -1,7 +1,7 @@
 use base::register::Registers;
-use base::Architecture;
+use base::{Architecture, Dialect};
 use syntax::alias::Alias;
-use syntax::ast::{find_parent, SyntaxKind, SyntaxToken};
+use syntax::ast::{find_parent, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
 
 use crate::templates::find_correct_instruction_template;
 use crate::{DocumentationMap, OperandAccessType};
@@ -12,21 +12,13 @@ pub fn access_type(
     registers: &dyn Registers,
     alias: &Alias,
     arch: Architecture,
+    dialect: Dialect,
 ) -> Option<OperandAccessType> {
     let instruction = find_parent(token, SyntaxKind::INSTRUCTION)?;
 
-    let (index, _) = instruction
-        .descendants_with_tokens()
-        .filter(|c| {
-            matches!(
-                c.kind(),
-                SyntaxKind::REGISTER
-                    | SyntaxKind::NUMBER
-                    | SyntaxKind::FLOAT
-                    | SyntaxKind::TOKEN
-                    | SyntaxKind::REGISTER_ALIAS
-            )
-        })
+    let reversed =
+        dialect == Dialect::Att && matches!(arch, Architecture::X86_64 | Architecture::X86);
+    let (index, _) = operand_tokens(&instruction, reversed)
         .enumerate()
         .find(|(_, t)| t.as_token().map(|t| t == token).unwrap_or(false))?;
 
@@ -37,3 +29,40 @@ pub fn access_type(
 
     template.access_map.get(index).cloned()
 }
+
+/// Gets the tokens that can have an access type in the order that they appear
+/// in the access map. The access maps follow the destination first operand
+/// order, so for AT&T syntax the operands need to be `reversed`.
+fn operand_tokens(instruction: &SyntaxNode, reversed: bool) -> impl Iterator<Item = SyntaxElement> {
+    let mut operands = vec![vec![]];
+    for child in instruction.children_with_tokens() {
+        if child.kind() == SyntaxKind::COMMA {
+            operands.push(vec![]);
+            continue;
+        }
+
+        let tokens = match child {
+            SyntaxElement::Node(node) => node.descendants_with_tokens().collect(),
+            token => vec![token],
+        };
+
+        if let Some(operand) = operands.last_mut() {
+            operand.extend(tokens.into_iter().filter(|c| {
+                matches!(
+                    c.kind(),
+                    SyntaxKind::REGISTER
+                        | SyntaxKind::NUMBER
+                        | SyntaxKind::FLOAT
+                        | SyntaxKind::TOKEN
+                        | SyntaxKind::REGISTER_ALIAS
+                )
+            }));
+        }
+    }
+
+    if reversed {
+        operands.reverse();
+    }
+
+    operands.into_iter().flatten()
+}
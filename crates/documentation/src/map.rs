@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use syntax::ast::{find_kind_index, find_parent, SyntaxKind, SyntaxNode, SyntaxToken};
+use syntax::ast::{self, find_parent, AstNode, SyntaxKind, SyntaxNode, SyntaxToken};
 
 use crate::Instruction;
 
@@ -16,12 +16,11 @@ impl DocumentationMap {
 
     pub fn get_from_token(&self, token: &SyntaxToken) -> Option<&Vec<Instruction>> {
         let instruction = find_parent(token, SyntaxKind::INSTRUCTION)?;
-        let op = find_kind_index(&instruction, 0, SyntaxKind::MNEMONIC)?.into_token()?;
-        self.get(op.text())
+        self.get_from_instruction_node(&instruction)
     }
 
     pub fn get_from_instruction_node(&self, node: &SyntaxNode) -> Option<&Vec<Instruction>> {
-        let op = find_kind_index(node, 0, SyntaxKind::MNEMONIC)?.into_token()?;
+        let op = ast::Instruction::cast(node.clone())?.mnemonic()?;
         self.get(op.text())
     }
 
@@ -1,5 +1,5 @@
 use base::register::{RegisterSize, Registers};
-use base::Architecture;
+use base::{Architecture, Dialect};
 use itertools::Itertools;
 use parser::config::ParserConfig;
 use parser::ParsedData;
@@ -15,6 +15,10 @@ fn get_config(architecture: Architecture) -> ParserConfig {
         architecture,
         file_type: Default::default(),
         registers: Some(&crate::registers::DOCUMENTATION_REGISTERS),
+        // Templates are written without register prefixes, in the destination
+        // first operand order.
+        dialect: Dialect::Intel,
+        register_operands: None,
     }
 }
 
@@ -108,13 +112,18 @@ fn check_template(
                     | SyntaxKind::OBJDUMP_OFFSET
                     | SyntaxKind::COMMENT
                     | SyntaxKind::RELOCATION
-            ) && find_parent_elem(c, SyntaxKind::METADATA).is_none()
+            ) && !is_memory_part(c.kind())
+                && find_parent_elem(c, SyntaxKind::METADATA).is_none()
+                && find_parent_elem(c, SyntaxKind::RELOCATION).is_none()
         })
         .collect_vec();
 
     let filtered_parsed = parsed_template
         .descendants_with_tokens()
-        .filter(|c| !matches!(c.kind(), SyntaxKind::WHITESPACE | SyntaxKind::METADATA))
+        .filter(|c| {
+            !matches!(c.kind(), SyntaxKind::WHITESPACE | SyntaxKind::METADATA)
+                && !is_memory_part(c.kind())
+        })
         .collect_vec();
 
     (!exact || filtered.len() == filtered_parsed.len())
@@ -124,6 +133,21 @@ fn check_template(
             .all(|x| node_or_token_match(x, lookup, alias))
 }
 
+/// The nodes that split up a memory operand depend on the whole operand, so
+/// they can't be compared while the operand is being typed. Only the tokens
+/// within them are compared.
+fn is_memory_part(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::MEMORY_OPERAND
+            | SyntaxKind::BASE
+            | SyntaxKind::INDEX
+            | SyntaxKind::SCALE
+            | SyntaxKind::DISPLACEMENT
+            | SyntaxKind::WRITEBACK
+    )
+}
+
 fn node_or_token_match(
     elements: (&SyntaxElement, &SyntaxElement),
     lookup: &dyn Registers,
@@ -136,8 +160,9 @@ fn node_or_token_match(
             register_match(actual, template, lookup)
         }
         (a, t) if a.kind() == SyntaxKind::REGISTER_ALIAS && t.kind() == SyntaxKind::REGISTER => {
+            let actual = a.as_token().unwrap();
             alias
-                .get_register_for_alias(a.as_token().unwrap().text())
+                .get_register_for_alias(actual.text(), actual.text_range().start())
                 .map(|actual| {
                     let template = t.as_token().unwrap().text();
                     register_match(actual, template, lookup)
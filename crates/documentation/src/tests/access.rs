@@ -4,7 +4,7 @@ use crate::access::access_type;
 use crate::tests::util;
 use crate::{DocumentationMap, Instruction, InstructionTemplate, OperandAccessType};
 use arch::registers::registers_for_architecture;
-use base::Architecture;
+use base::{Architecture, Dialect};
 use syntax::ast::{find_kind_index, SyntaxKind};
 
 #[test]
@@ -41,11 +41,25 @@ fn test_access_types_simple() {
 
     assert_eq!(
         Some(OperandAccessType::Write),
-        access_type(&first, &map, registers, &alias, Architecture::AArch64)
+        access_type(
+            &first,
+            &map,
+            registers,
+            &alias,
+            Architecture::AArch64,
+            Dialect::Att
+        )
     );
     assert_eq!(
         Some(OperandAccessType::Read),
-        access_type(&second, &map, registers, &alias, Architecture::AArch64)
+        access_type(
+            &second,
+            &map,
+            registers,
+            &alias,
+            Architecture::AArch64,
+            Dialect::Att
+        )
     );
 }
 
@@ -88,10 +102,120 @@ addvl	test, sp, #-2";
 
     assert_eq!(
         Some(OperandAccessType::Write),
-        access_type(&first, &map, registers, &alias, Architecture::AArch64)
+        access_type(
+            &first,
+            &map,
+            registers,
+            &alias,
+            Architecture::AArch64,
+            Dialect::Att
+        )
     );
     assert_eq!(
         Some(OperandAccessType::Read),
-        access_type(&second, &map, registers, &alias, Architecture::AArch64)
+        access_type(
+            &second,
+            &map,
+            registers,
+            &alias,
+            Architecture::AArch64,
+            Dialect::Att
+        )
+    );
+}
+
+fn make_mov_map() -> DocumentationMap {
+    let mut map = HashMap::new();
+    map.insert(
+        "mov".into(),
+        vec![Instruction {
+            opcode: "mov".into(),
+            header: None,
+            architecture: None,
+            description: "".into(),
+            asm_template: vec![InstructionTemplate {
+                asm: vec!["MOV <gp_64>, <gp_64>".into()],
+                display_asm: "".into(),
+                items: vec![],
+                access_map: vec![OperandAccessType::Write, OperandAccessType::Read],
+            }],
+        }],
+    );
+    DocumentationMap::from(map)
+}
+
+#[test]
+fn test_access_types_att_reversed() {
+    let (parsed, alias) = util::parse_x86_asm("mov %rax, %rbx", Dialect::Att);
+    let map = make_mov_map();
+    let registers = registers_for_architecture(&Architecture::X86_64);
+    let first = find_kind_index(&parsed, 0, SyntaxKind::REGISTER)
+        .unwrap()
+        .into_token()
+        .unwrap();
+    let second = find_kind_index(&parsed, 1, SyntaxKind::REGISTER)
+        .unwrap()
+        .into_token()
+        .unwrap();
+
+    assert_eq!(
+        Some(OperandAccessType::Read),
+        access_type(
+            &first,
+            &map,
+            registers,
+            &alias,
+            Architecture::X86_64,
+            Dialect::Att
+        )
+    );
+    assert_eq!(
+        Some(OperandAccessType::Write),
+        access_type(
+            &second,
+            &map,
+            registers,
+            &alias,
+            Architecture::X86_64,
+            Dialect::Att
+        )
+    );
+}
+
+#[test]
+fn test_access_types_intel() {
+    let (parsed, alias) = util::parse_x86_asm("mov rax, rbx", Dialect::Intel);
+    let map = make_mov_map();
+    let registers = registers_for_architecture(&Architecture::X86_64);
+    let first = find_kind_index(&parsed, 0, SyntaxKind::REGISTER)
+        .unwrap()
+        .into_token()
+        .unwrap();
+    let second = find_kind_index(&parsed, 1, SyntaxKind::REGISTER)
+        .unwrap()
+        .into_token()
+        .unwrap();
+
+    assert_eq!(
+        Some(OperandAccessType::Write),
+        access_type(
+            &first,
+            &map,
+            registers,
+            &alias,
+            Architecture::X86_64,
+            Dialect::Intel
+        )
+    );
+    assert_eq!(
+        Some(OperandAccessType::Read),
+        access_type(
+            &second,
+            &map,
+            registers,
+            &alias,
+            Architecture::X86_64,
+            Dialect::Intel
+        )
     );
 }
@@ -1,5 +1,5 @@
-use arch::register_names::AARCH64_REGISTERS;
-use base::FileType;
+use arch::register_names::{AARCH64_REGISTERS, X86_64_REGISTERS};
+use base::{Dialect, FileType};
 use parser::config::ParserConfig;
 use parser::{ParsedData, ParsedInclude};
 use syntax::alias::Alias;
@@ -13,6 +13,26 @@ pub(super) fn parse_asm(data: &str, file_type: FileType) -> (SyntaxNode, Alias)
         architecture: base::Architecture::AArch64,
         file_type,
         registers: Some(&AARCH64_REGISTERS),
+        dialect: Default::default(),
+        register_operands: None,
+    };
+    let load_file = |_current_config: &ParserConfig,
+                     _current_file: &str,
+                     _filename: &str|
+     -> Option<ParsedInclude> { None };
+
+    let ParsedData { root, alias, .. } = parser::parse_asm(data, &config, None, load_file);
+    (SyntaxNode::new_root(root), alias)
+}
+
+pub(super) fn parse_x86_asm(data: &str, dialect: Dialect) -> (SyntaxNode, Alias) {
+    let config = ParserConfig {
+        comment_start: String::from(base::Architecture::X86_64.default_comment_start()),
+        architecture: base::Architecture::X86_64,
+        file_type: FileType::Assembly,
+        registers: Some(&X86_64_REGISTERS),
+        dialect,
+        register_operands: None,
     };
     let load_file = |_current_config: &ParserConfig,
                      _current_file: &str,
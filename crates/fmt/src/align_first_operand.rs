@@ -1,4 +1,4 @@
-use syntax::ast::{find_kind_index, SyntaxKind, SyntaxNode};
+use syntax::ast::{AstNode, Instruction, SyntaxKind, SyntaxNode};
 use syntax::edit::{create_token, perform_replacements, Position};
 
 use crate::FormatOptions;
@@ -10,27 +10,21 @@ pub(crate) fn perform_pass(root: SyntaxNode, options: &FormatOptions) -> SyntaxN
 
     let instructions = root
         .descendants()
-        .filter_map(|n| {
-            if matches!(n.kind(), SyntaxKind::INSTRUCTION) {
-                Some(n)
-            } else {
-                None
-            }
-        })
+        .filter_map(Instruction::cast)
         .collect::<Vec<_>>();
 
-    let max = instructions.iter().fold(0, |max, node| {
-        find_kind_index(node, 0, SyntaxKind::MNEMONIC)
-            .and_then(|t| t.as_token().map(|t| t.text().len()))
+    let max = instructions.iter().fold(0, |max, instruction| {
+        instruction
+            .mnemonic()
+            .map(|t| t.text().len())
             .unwrap_or(0)
             .max(max)
     });
 
     let replacements = instructions
         .iter()
-        .filter_map(|node| {
-            let token =
-                find_kind_index(node, 0, SyntaxKind::MNEMONIC).and_then(|t| t.into_token())?;
+        .filter_map(|instruction| {
+            let token = instruction.mnemonic()?;
             let num_spaces = max - token.text().len();
             let spaces = " ".repeat(num_spaces + 1);
             let maybe_ws = token.next_sibling_or_token()?.into_token()?;
@@ -1,4 +1,5 @@
-use syntax::ast::{SyntaxKind, SyntaxNode, SyntaxToken};
+use rowan::NodeOrToken;
+use syntax::ast::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
 use syntax::edit::{create_token, perform_replacements, Position};
 
 use crate::FormatOptions;
@@ -16,7 +17,7 @@ pub(crate) fn perform_pass(root: SyntaxNode, options: &FormatOptions) -> SyntaxN
                         | SyntaxKind::R_SQ
                         | SyntaxKind::L_PAREN
                         | SyntaxKind::R_PAREN
-                )
+                ) && t.parent().map(|p| p.kind()) != Some(SyntaxKind::ERROR)
             })
         })
         .filter_map(|child| match child.kind() {
@@ -49,50 +50,48 @@ pub(crate) fn perform_pass(root: SyntaxNode, options: &FormatOptions) -> SyntaxN
 
 fn insert_spaces(child: SyntaxToken) -> Option<(Position, SyntaxToken)> {
     let position = if is_opening(child.kind()) {
-        if let Some(token) = child.next_sibling_or_token()?.into_token() {
-            if matches!(token.kind(), SyntaxKind::WHITESPACE) {
-                if token.text() == " " {
-                    None
-                } else {
-                    Some(Position::Replace(token))
-                }
-            } else {
-                Some(Position::After(child))
-            }
-        } else {
-            None
-        }
-    } else if let Some(token) = child.prev_sibling_or_token()?.into_token() {
-        if matches!(token.kind(), SyntaxKind::WHITESPACE) {
-            if token.text() == " " {
-                None
-            } else {
-                Some(Position::Replace(token))
-            }
-        } else {
-            Some(Position::Before(child))
+        match inside(child.next_sibling_or_token()?)? {
+            Some(token) if token.text() == " " => None,
+            Some(token) => Some(Position::Replace(token)),
+            None => Some(Position::After(child)),
         }
     } else {
-        unreachable!("Closing bracket should have a previous token");
+        match child.prev_sibling_or_token().and_then(|s| inside(s).flatten()) {
+            Some(token) if token.text() == " " => None,
+            Some(token) => Some(Position::Replace(token)),
+            None => Some(Position::Before(child)),
+        }
     };
 
     position.map(|position| (position, create_token(SyntaxKind::WHITESPACE, " ")))
 }
 
 fn remove_spaces(child: SyntaxToken) -> Option<(Position, SyntaxToken)> {
-    if is_opening(child.kind()) {
-        if let Some(token) = child.next_sibling_or_token()?.into_token() {
-            if matches!(token.kind(), SyntaxKind::WHITESPACE) {
-                return Some((Position::Replace(token), create_token(SyntaxKind::ROOT, "")));
-            }
-        }
-    } else if let Some(token) = child.prev_sibling_or_token()?.into_token() {
-        if matches!(token.kind(), SyntaxKind::WHITESPACE) {
-            return Some((Position::Replace(token), create_token(SyntaxKind::ROOT, "")));
-        }
-    }
+    let sibling = if is_opening(child.kind()) {
+        child.next_sibling_or_token()?
+    } else {
+        child.prev_sibling_or_token()?
+    };
 
-    None
+    inside(sibling)
+        .flatten()
+        .map(|token| (Position::Replace(token), create_token(SyntaxKind::ROOT, "")))
+}
+
+/// Checks the element next to a bracket on the inside, this is `None` if the
+/// spacing can't be changed, otherwise it holds the whitespace if there is any.
+/// The parts of a memory operand are nodes but never start or end with
+/// whitespace.
+fn inside(element: SyntaxElement) -> Option<Option<SyntaxToken>> {
+    match element {
+        NodeOrToken::Token(token) if token.kind() == SyntaxKind::WHITESPACE => Some(Some(token)),
+        NodeOrToken::Token(_) => Some(None),
+        NodeOrToken::Node(node) => matches!(
+            node.kind(),
+            SyntaxKind::BASE | SyntaxKind::INDEX | SyntaxKind::SCALE | SyntaxKind::DISPLACEMENT
+        )
+        .then_some(None),
+    }
 }
 
 fn is_opening(kind: SyntaxKind) -> bool {
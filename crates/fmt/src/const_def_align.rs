@@ -1,4 +1,4 @@
-use syntax::ast::{find_kind_index, SyntaxKind, SyntaxNode, SyntaxToken};
+use syntax::ast::{AstNode, ConstDef, RegisterAlias, SyntaxKind, SyntaxNode, SyntaxToken};
 use syntax::edit::{create_token, perform_replacements, Position};
 
 use crate::FormatOptions;
@@ -14,19 +14,15 @@ pub(crate) fn perform_pass(root: SyntaxNode, options: &FormatOptions) -> SyntaxN
         .iter()
         .flat_map(|const_defs| {
             let max = const_defs.iter().fold(0, |max, node| {
-                if matches!(node.kind(), SyntaxKind::CONST_DEF) {
-                    find_kind_index(node, 0, SyntaxKind::NAME)
-                } else {
-                    find_kind_index(node, 0, SyntaxKind::REGISTER_ALIAS)
-                }
-                .and_then(|t| t.as_token().map(|t| t.text().len()))
-                .unwrap_or(0)
-                .max(max)
+                name(node)
+                    .map(|t| t.text().len())
+                    .unwrap_or(0)
+                    .max(max)
             });
 
             let max_mnemonic = const_defs.iter().fold(0, |max, node| {
-                find_kind_index(node, 0, SyntaxKind::MNEMONIC)
-                    .and_then(|t| t.as_token().map(|t| t.text().len()))
+                mnemonic(node)
+                    .map(|t| t.text().len())
                     .unwrap_or(0)
                     .max(max)
             });
@@ -96,16 +92,8 @@ fn handle_name(
     max_mnemonic: usize,
     node: &SyntaxNode,
 ) -> Option<(Position, SyntaxToken)> {
-    let token = if matches!(node.kind(), SyntaxKind::CONST_DEF) {
-        find_kind_index(node, 0, SyntaxKind::NAME)
-    } else {
-        find_kind_index(node, 0, SyntaxKind::REGISTER_ALIAS)
-    }
-    .and_then(|t| t.into_token())?;
-
-    let mnemonic_len = find_kind_index(node, 0, SyntaxKind::MNEMONIC)
-        .and_then(|t| t.as_token().map(|t| t.text().len()))
-        .unwrap_or(0);
+    let token = name(node)?;
+    let mnemonic_len = mnemonic(node).map(|t| t.text().len()).unwrap_or(0);
 
     let num_spaces = max - token.text().len();
     let spaces = " ".repeat(num_spaces + 1 + (max_mnemonic - mnemonic_len));
@@ -122,7 +110,7 @@ fn handle_name(
 }
 
 fn handle_expr(node: &SyntaxNode) -> Option<(Position, SyntaxToken)> {
-    let expr = find_kind_index(node, 0, SyntaxKind::EXPR).and_then(|t| t.into_node())?;
+    let expr = ConstDef::cast(node.clone())?.expr()?;
     let spaces = " ";
     let maybe_ws = expr.first_child_or_token().map(|e| e.into_token())??;
 
@@ -137,7 +125,7 @@ fn handle_expr(node: &SyntaxNode) -> Option<(Position, SyntaxToken)> {
 }
 
 fn handle_register(node: &SyntaxNode) -> Option<(Position, SyntaxToken)> {
-    let register = find_kind_index(node, 0, SyntaxKind::REGISTER).and_then(|t| t.into_token())?;
+    let register = RegisterAlias::cast(node.clone())?.register()?;
     let spaces = " ";
     let maybe_ws = register.prev_sibling_or_token()?.into_token()?;
 
@@ -151,6 +139,21 @@ fn handle_register(node: &SyntaxNode) -> Option<(Position, SyntaxToken)> {
     }
 }
 
+/// The name being defined, either the constant or the register alias
+fn name(node: &SyntaxNode) -> Option<SyntaxToken> {
+    match node.kind() {
+        SyntaxKind::CONST_DEF => ConstDef::cast(node.clone())?.name(),
+        _ => RegisterAlias::cast(node.clone())?.alias(),
+    }
+}
+
+fn mnemonic(node: &SyntaxNode) -> Option<SyntaxToken> {
+    match node.kind() {
+        SyntaxKind::CONST_DEF => ConstDef::cast(node.clone())?.mnemonic(),
+        _ => RegisterAlias::cast(node.clone())?.mnemonic(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,7 +13,10 @@ pub(crate) fn perform_pass(root: SyntaxNode, options: &FormatOptions) -> SyntaxN
                 matches!(t.kind(), SyntaxKind::MNEMONIC)
                     && matches!(
                         t.parent().map(|n| n.kind()),
-                        Some(SyntaxKind::INSTRUCTION) | Some(SyntaxKind::DIRECTIVE)
+                        Some(SyntaxKind::INSTRUCTION)
+                            | Some(SyntaxKind::DIRECTIVE)
+                            | Some(SyntaxKind::MACRO_DEF)
+                            | Some(SyntaxKind::MACRO_CALL)
                     )
             })
         })
@@ -24,7 +24,10 @@ pub(crate) fn perform_pass(root: SyntaxNode, options: &FormatOptions) -> SyntaxN
                     None
                 } else if matches!(
                     ws.next_sibling_or_token().map(|d| d.kind()),
-                    Some(SyntaxKind::INSTRUCTION) | Some(SyntaxKind::DIRECTIVE)
+                    Some(SyntaxKind::INSTRUCTION)
+                        | Some(SyntaxKind::DIRECTIVE)
+                        | Some(SyntaxKind::MACRO_DEF)
+                        | Some(SyntaxKind::MACRO_CALL)
                 ) {
                     Some((
                         Position::Replace(ws),
@@ -20,6 +20,8 @@ pub fn parse_asm(data: &str) -> (SyntaxNode, syntax::alias::Alias) {
         architecture: base::Architecture::AArch64,
         file_type: base::FileType::Assembly,
         registers: Some(&AARCH64_REGISTERS),
+        dialect: Default::default(),
+        register_operands: None,
     };
     let load_file = |_current_config: &ParserConfig,
                      _current_file: &str,
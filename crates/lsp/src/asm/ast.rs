@@ -1,6 +1,10 @@
+use std::borrow::Cow;
+use std::iter;
+
 use arch::registers::registers_for_architecture;
 use base::register::RegisterKind;
 use base::FileType;
+use itertools::Itertools;
 use parser::config::ParserConfig;
 use syntax::ast::{SyntaxKind, SyntaxNode, SyntaxToken};
 
@@ -92,7 +96,15 @@ impl<'st, 'c> AstToken<'st, 'c> for RegisterToken<'st, 'c> {
 }
 impl<'st, 'c> RegisterToken<'st, 'c> {
     pub(crate) fn register_kind(&self) -> RegisterKind {
-        registers_for_architecture(&self.config.architecture).get_kind(self.token.text())
+        registers_for_architecture(&self.config.architecture).get_kind(&self.name())
+    }
+
+    /// The name of the register, registers that are written as a plain number
+    /// are converted into the full name e.g. `r9`.
+    pub(crate) fn name(&self) -> Cow<'st, str> {
+        parser::bare_register_name(self.token, self.config)
+            .map(Cow::Owned)
+            .unwrap_or_else(|| Cow::Borrowed(self.token.text()))
     }
 }
 
@@ -145,3 +157,269 @@ impl<'s> AstNode<'s> for LocalLabelNode<'s> {
         self.syntax
     }
 }
+
+pub struct SectionNode<'s> {
+    syntax: &'s SyntaxNode,
+}
+
+impl<'s> AstNode<'s> for SectionNode<'s> {
+    fn cast(node: &'s SyntaxNode) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        matches!(node.kind(), SyntaxKind::SECTION).then(|| Self { syntax: node })
+    }
+
+    fn syntax(&self) -> &'s SyntaxNode {
+        self.syntax
+    }
+}
+
+impl<'s> SectionNode<'s> {
+    /// The name of the section, this is the operand of `.section` or
+    /// `.pushsection` otherwise it's the directive itself e.g. `.text`
+    pub(crate) fn name(&self) -> Option<String> {
+        let (mnemonic, operand) = section_directive(self.syntax)?;
+        match mnemonic.to_lowercase().as_str() {
+            ".pushsection" | ".section" | "section" | "segment" => operand,
+            _ => Some(mnemonic),
+        }
+    }
+}
+
+/// Gets the mnemonic and name operand of the directive that starts a section
+fn section_directive(section: &SyntaxNode) -> Option<(String, Option<String>)> {
+    let directive = section.first_child()?;
+    let mut tokens = directive
+        .children_with_tokens()
+        .filter_map(|c| c.into_token())
+        .skip_while(|t| t.kind() != SyntaxKind::MNEMONIC);
+
+    let mnemonic = tokens.next()?.text().to_string();
+    let operand = tokens
+        .find(|t| t.kind() != SyntaxKind::WHITESPACE)
+        .filter(|t| !matches!(t.kind(), SyntaxKind::COMMA | SyntaxKind::COMMENT))
+        .map(|t| t.text().trim_matches('"').to_string());
+
+    Some((mnemonic, operand))
+}
+
+/// Gets the name of the section that contains `token`
+pub(crate) fn section_for_token(token: &SyntaxToken) -> Option<String> {
+    let section = token
+        .parent_ancestors()
+        .find(|a| a.kind() == SyntaxKind::SECTION)?;
+
+    SectionNode::cast(&section)?.name()
+}
+
+pub struct MacroNode<'s> {
+    syntax: &'s SyntaxNode,
+}
+
+impl<'s> AstNode<'s> for MacroNode<'s> {
+    fn cast(node: &'s SyntaxNode) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        matches!(node.kind(), SyntaxKind::MACRO_DEF).then(|| Self { syntax: node })
+    }
+
+    fn syntax(&self) -> &'s SyntaxNode {
+        self.syntax
+    }
+}
+
+/// A parameter to a macro e.g. `reg`, `offset=0` or `args:vararg`
+#[derive(Debug, PartialEq)]
+pub(crate) struct MacroParameter {
+    pub name: String,
+    pub default: Option<String>,
+    pub vararg: bool,
+}
+
+impl<'s> MacroNode<'s> {
+    pub(crate) fn name(&self) -> Option<SyntaxToken> {
+        self.syntax
+            .children_with_tokens()
+            .filter_map(|c| c.into_token())
+            .find(|t| t.kind() == SyntaxKind::NAME)
+    }
+
+    /// NASM macros are defined with `%macro name count` and refer to their
+    /// parameters by position e.g. `%1`
+    fn is_nasm(&self) -> bool {
+        self.syntax
+            .descendants_with_tokens()
+            .find(|d| d.kind() == SyntaxKind::MNEMONIC)
+            .map(|m| m.to_string().starts_with('%'))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn parameters(&self) -> Vec<MacroParameter> {
+        let Some(name) = self.name() else {
+            return Vec::new();
+        };
+        if self.is_nasm() {
+            return Vec::new();
+        }
+
+        // The parameters are the remainder of the `.macro` line, these can be
+        // separated by either commas or whitespace.
+        iter::successors(name.next_token(), |t| t.next_token())
+            .take_while(|t| !(t.kind() == SyntaxKind::WHITESPACE && t.text().contains('\n')))
+            .filter(|t| t.kind() != SyntaxKind::COMMENT)
+            .map(|t| t.text().to_string())
+            .collect::<String>()
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                let (name, default) = match p.split_once('=') {
+                    Some((name, default)) => (name, Some(default.to_string())),
+                    None => (p, None),
+                };
+                let (name, qualifier) = name.split_once(':').unwrap_or((name, ""));
+
+                MacroParameter {
+                    name: name.to_string(),
+                    default,
+                    vararg: qualifier == "vararg",
+                }
+            })
+            .collect()
+    }
+
+    /// The lines between the `.macro` and `.endm` directives
+    pub(crate) fn body(&self) -> String {
+        let text = self.syntax.text().to_string();
+
+        text.lines()
+            .skip(1)
+            .take_while(|line| {
+                let line = line.trim_start();
+                !(line
+                    .get(0..5)
+                    .map(|d| d.eq_ignore_ascii_case(".endm"))
+                    .unwrap_or(false)
+                    || line
+                        .get(0..9)
+                        .map(|d| d.eq_ignore_ascii_case("%endmacro"))
+                        .unwrap_or(false))
+            })
+            .join("\n")
+    }
+
+    /// Expands the body of the macro with the given arguments, parameters
+    /// that aren't given an argument use their default value.
+    pub(crate) fn expand(&self, arguments: &[String]) -> String {
+        let parameters = self.parameters();
+        let value_for = |name: &str| {
+            let index = parameters.iter().position(|p| p.name == name)?;
+            let parameter = &parameters[index];
+
+            let value = if parameter.vararg {
+                arguments.iter().skip(index).join(", ")
+            } else {
+                arguments.get(index).cloned().unwrap_or_default()
+            };
+
+            if value.is_empty() {
+                Some(parameter.default.clone().unwrap_or_default())
+            } else {
+                Some(value)
+            }
+        };
+
+        let body = self.body();
+        if self.is_nasm() {
+            return expand_positional(&body, arguments);
+        }
+
+        let mut expanded = String::with_capacity(body.len());
+        let mut remaining = body.as_str();
+        while let Some(position) = remaining.find('\\') {
+            expanded.push_str(&remaining[..position]);
+            remaining = &remaining[position + 1..];
+
+            // `\()` is used to separate a parameter from following text
+            if let Some(rest) = remaining.strip_prefix("()") {
+                remaining = rest;
+                continue;
+            }
+
+            let end = remaining
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.' || c == '$'))
+                .unwrap_or(remaining.len());
+
+            match value_for(&remaining[..end]) {
+                Some(value) => expanded.push_str(&value),
+                None => {
+                    expanded.push('\\');
+                    expanded.push_str(&remaining[..end]);
+                }
+            }
+            remaining = &remaining[end..];
+        }
+        expanded.push_str(remaining);
+
+        expanded
+    }
+}
+
+/// Replaces the positional parameters of a NASM macro e.g. `%1` with the
+/// arguments, `%%label` is a label local to the macro and is left as is.
+fn expand_positional(body: &str, arguments: &[String]) -> String {
+    let mut expanded = String::with_capacity(body.len());
+    let mut remaining = body;
+    while let Some(position) = remaining.find('%') {
+        expanded.push_str(&remaining[..position]);
+        remaining = &remaining[position + 1..];
+
+        let end = remaining
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(remaining.len());
+        match remaining[..end].parse::<usize>() {
+            Ok(index) if index > 0 => {
+                expanded.push_str(arguments.get(index - 1).map_or("", |a| a.as_str()))
+            }
+            _ => {
+                expanded.push('%');
+                expanded.push_str(&remaining[..end]);
+            }
+        }
+        remaining = &remaining[end..];
+    }
+    expanded.push_str(remaining);
+
+    expanded
+}
+
+/// Gets the arguments that are passed to a macro, arguments are separated by
+/// commas, or by whitespace when no commas are used.
+pub(crate) fn macro_call_arguments(call: &SyntaxNode) -> Vec<String> {
+    let tokens = call
+        .children_with_tokens()
+        .skip_while(|c| c.kind() != SyntaxKind::MNEMONIC)
+        .skip(1)
+        .filter(|c| c.kind() != SyntaxKind::COMMENT)
+        .collect_vec();
+
+    if tokens.iter().any(|c| c.kind() == SyntaxKind::COMMA) {
+        tokens
+            .split(|c| c.kind() == SyntaxKind::COMMA)
+            .map(|arg| {
+                arg.iter()
+                    .map(|c| c.to_string())
+                    .join("")
+                    .trim()
+                    .to_string()
+            })
+            .collect()
+    } else {
+        tokens
+            .split(|c| c.kind() == SyntaxKind::WHITESPACE)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| arg.iter().map(|c| c.to_string()).join(""))
+            .collect()
+    }
+}
@@ -0,0 +1,336 @@
+//! Follows the call frame information given by the `.cfi_*` directives
+//! through each procedure, so that the unwind rules at each directive are
+//! known and any mistakes in them can be reported.
+
+use std::fmt::Display;
+
+use base::{Architecture, Dialect};
+use lsp_types::DiagnosticSeverity;
+use rowan::TextRange;
+use syntax::ast::{AstNode, Directive, SyntaxKind, SyntaxNode};
+
+use super::evaluate::Evaluator;
+use super::parser::Parser;
+
+/// How the canonical frame address is calculated
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Cfa {
+    Register {
+        register: String,
+        offset: i128,
+    },
+    /// Set with `.cfi_def_cfa_expression` or a value that couldn't be
+    /// evaluated
+    Unknown,
+}
+
+/// Where the value of a register from the calling frame can be found
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Rule {
+    /// Saved at CFA + offset
+    Offset(i128),
+    /// The value is CFA + offset
+    ValOffset(i128),
+    /// Saved in another register
+    Register(String),
+    Undefined,
+    SameValue,
+    Expression,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CfiState {
+    pub cfa: Cfa,
+    /// The rules for each register in the order they were first given
+    pub rules: Vec<(String, Rule)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CfiError {
+    /// The range of the directive
+    pub range: TextRange,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct CfiAnalysis {
+    /// The state after each `.cfi_*` directive within a procedure, keyed by
+    /// the range of the directive.
+    pub states: Vec<(TextRange, CfiState)>,
+    pub errors: Vec<CfiError>,
+}
+
+/// The directives that are allowed outside of a procedure
+const GLOBAL_DIRECTIVES: [&str; 2] = [".cfi_sections", ".cfi_startproc"];
+
+pub(crate) fn analyse(parser: &Parser) -> CfiAnalysis {
+    let evaluator = Evaluator::new(parser);
+    let mut analysis = CfiAnalysis::default();
+
+    let mut start: Option<SyntaxNode> = None;
+    let mut state = initial_state(parser, false);
+    let mut remembered: Vec<(SyntaxNode, CfiState)> = Vec::new();
+
+    let directives = parser
+        .tree()
+        .descendants()
+        .filter_map(Directive::cast)
+        .filter(|d| d.name().is_some_and(|n| n.starts_with(".cfi_")))
+        // Macros are only checked where they're used
+        .filter(|d| {
+            d.syntax()
+                .ancestors()
+                .all(|a| a.kind() != SyntaxKind::MACRO_DEF)
+        })
+        .filter(|d| {
+            !d.syntax()
+                .first_token()
+                .is_some_and(|t| parser.is_inactive(&t))
+        });
+
+    for directive in directives {
+        let node = directive.syntax().clone();
+        let name = directive.name().unwrap_or_default();
+        let operands = directive
+            .operands()
+            .iter()
+            .map(|o| o.text())
+            .collect::<Vec<_>>();
+        let value = |index: usize| {
+            evaluator.evaluate(operands.get(index)?, Some(node.text_range().start()))
+        };
+
+        let mut error = |severity: DiagnosticSeverity, message: String| {
+            analysis.errors.push(CfiError {
+                range: node.text_range(),
+                severity,
+                message,
+            })
+        };
+
+        if start.is_none() && !GLOBAL_DIRECTIVES.contains(&name.as_str()) {
+            error(
+                DiagnosticSeverity::ERROR,
+                format!("`{name}` used outside of a `.cfi_startproc` and `.cfi_endproc`"),
+            );
+            continue;
+        }
+
+        match name.as_str() {
+            ".cfi_startproc" => {
+                if start.is_some() {
+                    error(
+                        DiagnosticSeverity::ERROR,
+                        String::from("missing `.cfi_endproc` before `.cfi_startproc`"),
+                    );
+                }
+                start = Some(node.clone());
+                state = initial_state(parser, operands.first().is_some_and(|o| o == "simple"));
+                remembered.clear();
+            }
+            ".cfi_endproc" => {
+                for (remember, _) in remembered.drain(..) {
+                    analysis.errors.push(CfiError {
+                        range: remember.text_range(),
+                        severity: DiagnosticSeverity::WARNING,
+                        message: String::from(
+                            "`.cfi_remember_state` without a matching `.cfi_restore_state`",
+                        ),
+                    });
+                }
+                start = None;
+                continue;
+            }
+            ".cfi_def_cfa" => {
+                state.cfa = match (operands.first(), value(1)) {
+                    (Some(register), Some(offset)) => Cfa::Register {
+                        register: register.clone(),
+                        offset,
+                    },
+                    _ => Cfa::Unknown,
+                };
+            }
+            ".cfi_def_cfa_register" => {
+                state.cfa = match (&state.cfa, operands.first()) {
+                    (Cfa::Register { offset, .. }, Some(register)) => Cfa::Register {
+                        register: register.clone(),
+                        offset: *offset,
+                    },
+                    _ => Cfa::Unknown,
+                };
+            }
+            ".cfi_def_cfa_offset" | ".cfi_adjust_cfa_offset" => {
+                let adjust = name == ".cfi_adjust_cfa_offset";
+                state.cfa = match (&state.cfa, value(0)) {
+                    (Cfa::Register { register, offset }, Some(value)) => Cfa::Register {
+                        register: register.clone(),
+                        offset: if adjust { offset + value } else { value },
+                    },
+                    _ => Cfa::Unknown,
+                };
+            }
+            ".cfi_def_cfa_expression" => state.cfa = Cfa::Unknown,
+            ".cfi_offset" | ".cfi_rel_offset" | ".cfi_val_offset" => {
+                let (Some(register), Some(offset)) = (operands.first(), value(1)) else {
+                    continue;
+                };
+                let rule = match (name.as_str(), &state.cfa) {
+                    (".cfi_val_offset", _) => Rule::ValOffset(offset),
+                    // The offset is from the CFA register rather than the CFA
+                    (".cfi_rel_offset", Cfa::Register { offset: cfa, .. }) => {
+                        Rule::Offset(offset - cfa)
+                    }
+                    (".cfi_rel_offset", Cfa::Unknown) => Rule::Expression,
+                    _ => Rule::Offset(offset),
+                };
+                state.set(register, rule);
+            }
+            ".cfi_register" => {
+                if let (Some(register), Some(other)) = (operands.first(), operands.get(1)) {
+                    state.set(register, Rule::Register(other.clone()));
+                }
+            }
+            ".cfi_undefined" => {
+                if let Some(register) = operands.first() {
+                    state.set(register, Rule::Undefined);
+                }
+            }
+            ".cfi_same_value" => {
+                if let Some(register) = operands.first() {
+                    state.set(register, Rule::SameValue);
+                }
+            }
+            ".cfi_expression" | ".cfi_val_expression" => {
+                if let Some(register) = operands.first() {
+                    state.set(register, Rule::Expression);
+                }
+            }
+            ".cfi_restore" => {
+                let initial = initial_state(parser, false);
+                for register in &operands {
+                    if state.rule(register).is_none() {
+                        error(
+                            DiagnosticSeverity::WARNING,
+                            format!("`.cfi_restore` of `{register}` which hasn't been saved"),
+                        );
+                    }
+                    state.rules.retain(|(r, _)| !same_register(r, register));
+                    if let Some(rule) = initial.rule(register) {
+                        state.set(register, rule.clone());
+                    }
+                }
+            }
+            ".cfi_remember_state" => remembered.push((node.clone(), state.clone())),
+            ".cfi_restore_state" => match remembered.pop() {
+                Some((_, remembered)) => state = remembered,
+                None => error(
+                    DiagnosticSeverity::ERROR,
+                    String::from("`.cfi_restore_state` without a `.cfi_remember_state`"),
+                ),
+            },
+            _ => {}
+        }
+
+        analysis.states.push((node.text_range(), state.clone()));
+    }
+
+    if let Some(start) = start {
+        analysis.errors.push(CfiError {
+            range: start.text_range(),
+            severity: DiagnosticSeverity::ERROR,
+            message: String::from("`.cfi_startproc` is missing a `.cfi_endproc`"),
+        });
+    }
+
+    analysis
+}
+
+/// The rules at the start of a function, a `simple` procedure starts without
+/// any rules.
+fn initial_state(parser: &Parser, simple: bool) -> CfiState {
+    if simple {
+        return CfiState {
+            cfa: Cfa::Unknown,
+            rules: Vec::new(),
+        };
+    }
+
+    let prefix = match parser.architecture() {
+        Architecture::X86 | Architecture::X86_64 if parser.dialect() == Dialect::Att => "%",
+        _ => "",
+    };
+    let register = |name: &str| format!("{prefix}{name}");
+
+    // The call instruction on x86 pushes the return address to the stack
+    let (cfa, offset, rules) = match parser.architecture() {
+        Architecture::X86_64 => ("rsp", 8, vec![(register("rip"), Rule::Offset(-8))]),
+        Architecture::X86 => ("esp", 4, vec![(register("eip"), Rule::Offset(-4))]),
+        Architecture::PowerPC64 => ("r1", 0, Vec::new()),
+        _ => ("sp", 0, Vec::new()),
+    };
+
+    CfiState {
+        cfa: Cfa::Register {
+            register: register(cfa),
+            offset,
+        },
+        rules,
+    }
+}
+
+/// Registers can be written with or without a `%` and in any case
+fn same_register(lhs: &str, rhs: &str) -> bool {
+    lhs.trim_start_matches('%')
+        .eq_ignore_ascii_case(rhs.trim_start_matches('%'))
+}
+
+impl CfiState {
+    pub(crate) fn rule(&self, register: &str) -> Option<&Rule> {
+        self.rules
+            .iter()
+            .find(|(r, _)| same_register(r, register))
+            .map(|(_, rule)| rule)
+    }
+
+    fn set(&mut self, register: &str, rule: Rule) {
+        match self
+            .rules
+            .iter_mut()
+            .find(|(r, _)| same_register(r, register))
+        {
+            Some((_, existing)) => *existing = rule,
+            None => self.rules.push((register.to_string(), rule)),
+        }
+    }
+}
+
+fn signed(base: &str, offset: i128) -> String {
+    match offset {
+        0 => base.to_string(),
+        offset if offset < 0 => format!("{base} - {}", -offset),
+        offset => format!("{base} + {offset}"),
+    }
+}
+
+impl Display for Cfa {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cfa::Register { register, offset } => write!(f, "{}", signed(register, *offset)),
+            Cfa::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rule::Offset(offset) => write!(f, "[{}]", signed("CFA", *offset)),
+            Rule::ValOffset(offset) => write!(f, "{}", signed("CFA", *offset)),
+            Rule::Register(register) => write!(f, "{register}"),
+            Rule::Undefined => write!(f, "undefined"),
+            Rule::SameValue => write!(f, "same value"),
+            Rule::Expression => write!(f, "expression"),
+        }
+    }
+}
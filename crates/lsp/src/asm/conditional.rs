@@ -0,0 +1,110 @@
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use rowan::TextRange;
+use syntax::alias::Alias;
+use syntax::ast::{find_parent, SyntaxKind, SyntaxNode};
+
+/// Finds the ranges of the file that are in a branch of a conditional that
+/// will not be assembled. Only conditions that can be evaluated are
+/// considered, anything depending on unknown symbols is treated as active.
+pub(crate) fn inactive_ranges(root: &SyntaxNode, alias: &Alias) -> Vec<TextRange> {
+    let mut inactive = Vec::new();
+
+    // Conditionals inside a macro depend on the arguments to the macro
+    for conditional in root
+        .descendants()
+        .filter(|d| d.kind() == SyntaxKind::CONDITIONAL)
+        .filter(|d| d.ancestors().all(|a| a.kind() != SyntaxKind::MACRO_DEF))
+    {
+        let mut taken = false;
+        for branch in conditional
+            .children()
+            .filter(|c| c.kind() == SyntaxKind::CONDITIONAL_BRANCH)
+        {
+            let Some(directive) = branch.first_child() else {
+                break;
+            };
+            let body = TextRange::new(directive.text_range().end(), branch.text_range().end());
+
+            if taken {
+                inactive.push(body);
+                continue;
+            }
+
+            match evaluate_condition(root, &directive, alias) {
+                Some(true) => taken = true,
+                Some(false) => inactive.push(body),
+                None => break,
+            }
+        }
+    }
+
+    inactive
+}
+
+fn evaluate_condition(root: &SyntaxNode, directive: &SyntaxNode, alias: &Alias) -> Option<bool> {
+    let mnemonic = directive
+        .children_with_tokens()
+        .find(|c| c.kind() == SyntaxKind::MNEMONIC)?
+        .to_string()
+        .to_lowercase();
+
+    let operand = directive
+        .children_with_tokens()
+        .skip_while(|c| c.kind() != SyntaxKind::MNEMONIC)
+        .skip(1)
+        .filter(|c| c.kind() != SyntaxKind::COMMENT)
+        .map(|c| c.to_string())
+        .join("");
+    let operand = operand.trim();
+
+    let offset = directive.text_range().start();
+    let value = || alias.evaluate(operand, offset, &|_| None);
+    match mnemonic.as_str() {
+        ".else" | "#else" | "%else" => Some(true),
+        ".if" | ".elseif" | ".ifne" => value().map(|v| v != 0),
+        "#if" | "#elif" | "%if" | "%elif" => alias
+            .evaluate(&replace_defined(root, operand, alias), offset, &|_| None)
+            .map(|v| v != 0),
+        ".ifeq" => value().map(|v| v == 0),
+        ".ifgt" => value().map(|v| v > 0),
+        ".ifge" => value().map(|v| v >= 0),
+        ".iflt" => value().map(|v| v < 0),
+        ".ifle" => value().map(|v| v <= 0),
+        ".ifdef" | "#ifdef" | "%ifdef" => Some(is_defined(root, operand, alias)),
+        ".ifndef" | ".ifnotdef" | "#ifndef" | "%ifndef" => Some(!is_defined(root, operand, alias)),
+        ".ifb" => Some(operand.is_empty()),
+        ".ifnb" => Some(!operand.is_empty()),
+        _ => None,
+    }
+}
+
+/// Replaces the preprocessor `defined(NAME)` and `defined NAME` operators with
+/// their value so that the rest of the expression can be evaluated.
+fn replace_defined(root: &SyntaxNode, expr: &str, alias: &Alias) -> String {
+    static DEFINED: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"\bdefined\s*(?:\(\s*(\w+)\s*\)|(\w+))").unwrap());
+
+    DEFINED
+        .replace_all(expr, |captures: &Captures| {
+            let name = captures.get(1).or_else(|| captures.get(2)).unwrap();
+            if is_defined(root, name.as_str(), alias) {
+                "1"
+            } else {
+                "0"
+            }
+        })
+        .to_string()
+}
+
+/// A symbol is defined if it is a constant, alias, or label
+fn is_defined(root: &SyntaxNode, name: &str, alias: &Alias) -> bool {
+    alias.get_kind(name).is_some()
+        || root
+            .descendants_with_tokens()
+            .filter_map(|d| d.into_token())
+            .filter(|t| t.kind() == SyntaxKind::LABEL)
+            .filter(|t| find_parent(t, SyntaxKind::MACRO_DEF).is_none())
+            .any(|t| t.text().trim_end_matches(':') == name)
+}
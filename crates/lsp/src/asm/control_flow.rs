@@ -0,0 +1,216 @@
+//! Finds the jumps and calls within a file along with the instruction or label
+//! that they go to, so that clients can draw arrows between them in the same
+//! way as `objdump --visualize-jumps`.
+
+use std::collections::HashMap;
+
+use base::{Architecture, FileType};
+use parser::parse_number;
+use rowan::TextRange;
+use syntax::ast::{find_parent, AstNode, Instruction, SyntaxKind, SyntaxToken};
+use syntax::utils::token_is_local_label;
+
+use super::ast::LabelToken;
+use super::definition::labels;
+use super::objdump_util;
+use super::parser::Parser;
+use crate::handler::ext::ControlFlowKind;
+
+pub(super) struct Edge {
+    pub source: TextRange,
+    pub target: TextRange,
+    pub kind: ControlFlowKind,
+    pub conditional: bool,
+}
+
+const ARM_CONDITIONS: [&str; 16] = [
+    "eq", "ne", "cs", "hs", "cc", "lo", "mi", "pl", "vs", "vc", "hi", "ls", "ge", "lt", "gt", "le",
+];
+
+const RISCV_BRANCHES: [&str; 18] = [
+    "beq", "bne", "blt", "bge", "bltu", "bgeu", "beqz", "bnez", "blez", "bgez", "bltz", "bgtz",
+    "bgt", "ble", "bgtu", "bleu", "c.beqz", "c.bnez",
+];
+
+const POWERPC_BRANCHES: [&str; 13] = [
+    "bc", "beq", "bne", "blt", "bgt", "ble", "bge", "bnl", "bng", "bso", "bns", "bdnz", "bdz",
+];
+
+pub(super) fn edges(parser: &Parser) -> Vec<Edge> {
+    // The labels by name in the order that they're defined
+    let mut names: HashMap<String, Vec<SyntaxToken>> = HashMap::new();
+    for label in labels(parser, &parser.tree()) {
+        if let Some(name) = parser
+            .token::<LabelToken>(&label)
+            .map(|l| l.name().to_string())
+        {
+            names.entry(name).or_default().push(label);
+        }
+    }
+
+    parser
+        .tree()
+        .descendants()
+        .filter_map(Instruction::cast)
+        // Macros are only followed where they're used
+        .filter(|i| {
+            i.syntax()
+                .ancestors()
+                .all(|a| a.kind() != SyntaxKind::MACRO_DEF)
+        })
+        .filter_map(|instruction| {
+            let mnemonic = instruction.mnemonic()?;
+            if parser.is_inactive(&mnemonic) {
+                return None;
+            }
+
+            let (kind, conditional) = classify(
+                parser.architecture(),
+                &mnemonic.text().to_lowercase(),
+                &instruction,
+            )?;
+            let operand = instruction.operands().last()?.text_range();
+            let target = instruction
+                .syntax()
+                .descendants_with_tokens()
+                .filter_map(|e| e.into_token())
+                .filter(|t| operand.contains_range(t.text_range()))
+                // The address can look like a name in an objdump e.g. `a <foo>`
+                .filter(|t| {
+                    !matches!(parser.file_type(), FileType::ObjDump(_))
+                        || find_parent(t, SyntaxKind::METADATA).is_some()
+                })
+                .find(|t| t.kind() == SyntaxKind::TOKEN)?;
+
+            Some(Edge {
+                source: instruction_range(&instruction),
+                target: find_target(parser, &names, &target)?,
+                kind,
+                conditional,
+            })
+        })
+        .collect()
+}
+
+/// The instruction that `<label+0x10>` refers to in an objdump, otherwise
+/// the definition of the label.
+fn find_target(
+    parser: &Parser,
+    names: &HashMap<String, Vec<SyntaxToken>>,
+    token: &SyntaxToken,
+) -> Option<TextRange> {
+    let scope = if token_is_local_label(token) {
+        find_parent(token, SyntaxKind::LABEL)?.text_range()
+    } else {
+        parser.tree().text_range()
+    };
+    let label = names
+        .get(token.text())?
+        .iter()
+        .find(|label| scope.contains_range(label.text_range()))?;
+
+    let offset = token
+        .next_token()
+        .filter(|t| t.text() == "+")
+        .and_then(|t| parse_number(t.next_token()?.text()).ok());
+
+    match (parser.file_type(), offset) {
+        (FileType::ObjDump(_), Some(offset)) => {
+            let instruction =
+                objdump_util::find_instruction_at_relative_offset(&label.parent()?, offset)?;
+            Some(instruction_range(&Instruction::cast(instruction)?))
+        }
+        _ => Some(label.text_range()),
+    }
+}
+
+/// The instruction without any relocations that are listed after it
+fn instruction_range(instruction: &Instruction) -> TextRange {
+    let node = instruction.syntax();
+    let end = instruction
+        .operands()
+        .last()
+        .map(|o| o.text_range().end())
+        .or_else(|| instruction.mnemonic().map(|m| m.text_range().end()))
+        .unwrap_or_else(|| node.text_range().end());
+
+    TextRange::new(node.text_range().start(), end)
+}
+
+/// Whether the instruction is a call or a jump to a label, along with whether
+/// it is conditional.
+fn classify(
+    architecture: &Architecture,
+    mnemonic: &str,
+    instruction: &Instruction,
+) -> Option<(ControlFlowKind, bool)> {
+    use ControlFlowKind::{Call, Jump};
+
+    match architecture {
+        Architecture::AArch64 => match mnemonic {
+            "bl" => Some((Call, false)),
+            "b" => Some((Jump, false)),
+            "cbz" | "cbnz" | "tbz" | "tbnz" => Some((Jump, true)),
+            _ if mnemonic.starts_with("b.") || mnemonic.starts_with("bc.") => Some((Jump, true)),
+            _ => None,
+        },
+        Architecture::AArch32 => {
+            // The width qualifier in thumb code e.g. `b.w`
+            let mnemonic = mnemonic.split('.').next().unwrap_or_default();
+            let is_condition = |c: &str| c == "al" || ARM_CONDITIONS.contains(&c);
+
+            match mnemonic {
+                "bl" | "blx" => Some((Call, false)),
+                "b" => Some((Jump, false)),
+                "cbz" | "cbnz" => Some((Jump, true)),
+                _ => {
+                    if let Some(condition) = mnemonic.strip_prefix('b').filter(|c| is_condition(c))
+                    {
+                        Some((Jump, condition != "al"))
+                    } else {
+                        let condition = mnemonic
+                            .strip_prefix("blx")
+                            .or_else(|| mnemonic.strip_prefix("bl"))
+                            .filter(|c| is_condition(c))?;
+                        Some((Call, condition != "al"))
+                    }
+                }
+            }
+        }
+        Architecture::RiscV64 => match mnemonic {
+            "call" | "c.jal" => Some((Call, false)),
+            // `jal zero, label` doesn't save the return address
+            "jal" => {
+                let operands = instruction.operands();
+                let jump =
+                    operands.len() > 1 && matches!(operands[0].text().as_str(), "zero" | "x0");
+                Some((if jump { Jump } else { Call }, false))
+            }
+            "j" | "c.j" | "tail" => Some((Jump, false)),
+            _ if RISCV_BRANCHES.contains(&mnemonic) => Some((Jump, true)),
+            _ => None,
+        },
+        Architecture::PowerPC64 => {
+            // Branch prediction hints e.g. `beq+`
+            let mnemonic = mnemonic.trim_end_matches(['+', '-']);
+
+            match mnemonic {
+                "bl" | "bla" => Some((Call, false)),
+                "b" | "ba" => Some((Jump, false)),
+                _ if POWERPC_BRANCHES.contains(&mnemonic) => Some((Jump, true)),
+                _ => None,
+            }
+        }
+        Architecture::X86 | Architecture::X86_64 | Architecture::Unknown => {
+            if mnemonic.starts_with("call") {
+                Some((Call, false))
+            } else if matches!(mnemonic, "jmp" | "jmpq" | "jmpl" | "jmpw") {
+                Some((Jump, false))
+            } else if mnemonic.starts_with('j') || mnemonic.starts_with("loop") {
+                Some((Jump, true))
+            } else {
+                None
+            }
+        }
+    }
+}
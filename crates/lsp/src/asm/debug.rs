@@ -1,65 +1,164 @@
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use lsp_types::Url;
+use md5::{Digest, Md5};
 use once_cell::sync::OnceCell;
+use rowan::{TextRange, TextSize};
 
 use crate::types::{DocumentLocation, DocumentPosition, DocumentRange, LineNumber};
-use syntax::ast::{self, SyntaxKind, SyntaxNode};
+use syntax::ast::{AstNode, Directive, SyntaxElement, SyntaxKind, SyntaxNode};
 
 #[derive(Debug, Clone, PartialEq)]
 pub(super) struct DebugMap {
-    map: HashMap<u32, FileInfo>,
+    units: Vec<CompileUnit>,
+    /// The ids of the files named by `objdump -l`, there aren't any `.file`
+    /// directives in objdump files so these are numbered in the first unit.
+    paths: HashMap<String, u32>,
+}
+
+/// The files of a compile unit, DWARF 5 starts each unit with a `.file 0`
+/// that gives the compilation directory and the numbering starts again.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct CompileUnit {
+    start: TextSize,
+    directory: Option<PathBuf>,
+    /// The source file given to `.file` without a number
+    source: Option<String>,
+    files: HashMap<u32, FileInfo>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 struct FileInfo {
     name: String,
+    /// The checksum from a DWARF 5 `.file` directive
+    md5: Option<String>,
+    range: TextRange,
     contents: OnceCell<Vec<String>>,
 }
 
+/// A file number in a `.loc` directive along with the unit that it's from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct FileId {
+    unit: usize,
+    id: u32,
+}
+
 impl DebugMap {
-    pub(super) fn new(tree: &SyntaxNode) -> DebugMap {
-        let map = tree
+    /// Relative file names are resolved from `directory`, which is the
+    /// directory that the assembly file is in. Without one they are left
+    /// relative to the working directory.
+    pub(super) fn new(tree: &SyntaxNode, directory: Option<&Path>) -> DebugMap {
+        let mut units = vec![CompileUnit::default()];
+
+        let directives = tree
             .descendants()
-            .filter(|d| {
-                matches!(d.kind(), SyntaxKind::DIRECTIVE)
-                    && ast::find_kind_index(d, 0, SyntaxKind::MNEMONIC)
-                        .and_then(|n| n.as_token().map(|t| t.text() == ".file"))
-                        .unwrap_or(false)
-            })
-            .filter_map(|n| {
-                let id = ast::find_kind_index(&n, 0, SyntaxKind::NUMBER)?;
-                let file = ast::find_kind_index(&n, 0, SyntaxKind::STRING)?;
-                let name = file.as_token()?.text().trim_matches('"').to_string();
-
-                let mut path = PathBuf::from(&name);
-                if path.is_dir() {
-                    let file = ast::find_kind_index(&n, 1, SyntaxKind::STRING)?;
-                    let name = file.as_token()?.text().trim_matches('"').to_string();
-                    path.push(name);
+            .filter_map(Directive::cast)
+            .filter(|d| d.name().as_deref() == Some(".file"));
+
+        for directive in directives {
+            let arguments = directive.arguments().collect::<Vec<_>>();
+            let Some(id) = arguments.first().and_then(number) else {
+                if let Some(source) = arguments.first().and_then(string) {
+                    units.last_mut().unwrap().source = Some(source);
                 }
+                continue;
+            };
+            let strings = arguments[1..].iter().map_while(string).collect::<Vec<_>>();
+            let (file_directory, name) = match strings.as_slice() {
+                [name] => (None, name),
+                [file_directory, name] => (Some(file_directory), name),
+                _ => continue,
+            };
+
+            let md5 = arguments
+                .iter()
+                .position(|a| a.as_token().is_some_and(|t| t.text() == "md5"))
+                .and_then(|i| arguments.get(i + 1))
+                .and_then(checksum);
 
-                let name = path.to_str()?.to_string();
-                let file = FileInfo {
+            let range = directive.syntax().text_range();
+            if id == 0 && units.last().is_some_and(|u| u.files.contains_key(&0)) {
+                units.push(CompileUnit {
+                    start: range.start(),
+                    ..Default::default()
+                });
+            }
+            let unit = units.last_mut().unwrap();
+
+            // Relative paths are from the compilation directory or the
+            // assembly file, pushing an absolute path replaces what's there
+            // already
+            let mut path = directory.map(PathBuf::from).unwrap_or_default();
+            if let Some(compilation) = unit.directory.as_ref().filter(|_| id != 0) {
+                path.push(compilation);
+            }
+            if let Some(file_directory) = file_directory {
+                path.push(file_directory);
+            }
+            if id == 0 {
+                unit.directory = Some(path.clone());
+            }
+            path.push(name);
+
+            let Some(name) = path.to_str().map(String::from) else {
+                continue;
+            };
+            unit.files.insert(
+                id,
+                FileInfo {
                     name,
+                    md5,
+                    range,
                     contents: Default::default(),
-                };
-                Some((id.as_token()?.text().parse::<u32>().ok()?, file))
-            })
-            .collect();
+                },
+            );
+        }
+
+        let mut paths = HashMap::new();
+        for node in tree
+            .descendants()
+            .filter(|n| n.kind() == SyntaxKind::SOURCE_LOCATION)
+        {
+            let Some((name, _)) = source_location(&node) else {
+                continue;
+            };
+            if paths.contains_key(&name) {
+                continue;
+            }
 
-        DebugMap { map }
+            let path = directory
+                .map(|d| d.join(&name))
+                .unwrap_or_else(|| name.clone().into());
+            let Some(path) = path.to_str().map(String::from) else {
+                continue;
+            };
+
+            let id = paths.len() as u32;
+            units[0].files.insert(
+                id,
+                FileInfo {
+                    name: path,
+                    md5: None,
+                    range: node.text_range(),
+                    contents: Default::default(),
+                },
+            );
+            paths.insert(name, id);
+        }
+
+        DebugMap { units, paths }
     }
 
     pub fn get_file_location(&self, node: &SyntaxNode) -> Option<DocumentLocation> {
         let (file_id, line) = self.get_location(node)?;
-        let line = line - 1;
+        // Line 0 is code that doesn't come from any line of the source
+        let line = line.checked_sub(1)?;
         let file = self.get_filename(file_id)?;
         Some(DocumentLocation {
-            uri: Url::parse(format!("file://{file}").as_str()).ok()?,
+            uri: file_uri(file)?,
             range: DocumentRange {
                 start: DocumentPosition { line, column: 0 },
                 end: DocumentPosition { line, column: 0 },
@@ -67,14 +166,45 @@ impl DebugMap {
         })
     }
 
+    /// The instructions generated for each `.loc`, this is everything up
+    /// until the next `.loc` in the order that they're written.
+    pub fn source_ranges(&self, tree: &SyntaxNode) -> Vec<(DocumentLocation, TextRange)> {
+        let mut ranges = Vec::new();
+        let mut current: Option<(DocumentLocation, Option<TextRange>)> = None;
+
+        let nodes = tree
+            .descendants()
+            .filter(|n| n.ancestors().all(|a| a.kind() != SyntaxKind::MACRO_DEF));
+        for node in nodes {
+            match node.kind() {
+                SyntaxKind::DIRECTIVE | SyntaxKind::SOURCE_LOCATION
+                    if self.get_location(&node).is_some() =>
+                {
+                    ranges.extend(current.take().and_then(|(l, r)| Some((l, r?))));
+                    current = self.get_file_location(&node).map(|l| (l, None));
+                }
+                SyntaxKind::INSTRUCTION => {
+                    if let Some((_, range)) = current.as_mut() {
+                        let instruction = node.text_range();
+                        *range = Some(range.map_or(instruction, |r| r.cover(instruction)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        ranges.extend(current.and_then(|(l, r)| Some((l, r?))));
+
+        ranges
+    }
+
     pub fn has_debug_map(&self) -> bool {
-        !self.map.is_empty()
+        self.units.iter().any(|u| !u.files.is_empty())
     }
 
-    pub fn get_contents(&self, location: (u32, LineNumber)) -> Option<&String> {
+    pub fn get_contents(&self, location: (FileId, LineNumber)) -> Option<&String> {
         let (file, line) = location;
 
-        self.map.get(&file).and_then(|f| {
+        self.get_file(file).and_then(|f| {
             f.contents
                 .get_or_init(|| {
                     Self::load_file(&f.name)
@@ -85,25 +215,73 @@ impl DebugMap {
         })
     }
 
-    pub fn get_location(&self, node: &SyntaxNode) -> Option<(u32, LineNumber)> {
-        if matches!(node.kind(), SyntaxKind::DIRECTIVE) {
-            ast::find_kind_index(node, 0, SyntaxKind::MNEMONIC)
-                .filter(|t| t.as_token().map(|t| t.text() == ".loc").unwrap_or(false))
-                .and_then(|_| {
-                    let file_id = ast::find_kind_index(node, 0, SyntaxKind::NUMBER)?;
-                    let file_id = file_id.as_token()?.text().parse::<u32>().ok()?;
-                    let line = ast::find_kind_index(node, 1, SyntaxKind::NUMBER)?;
-                    let line = line.as_token()?.text().parse::<LineNumber>().ok()?;
+    pub fn get_location(&self, node: &SyntaxNode) -> Option<(FileId, LineNumber)> {
+        if node.kind() == SyntaxKind::SOURCE_LOCATION {
+            let (name, line) = source_location(node)?;
+            let id = *self.paths.get(&name)?;
+            return Some((FileId { unit: 0, id }, line));
+        }
+
+        let directive = Directive::cast(node.clone())?;
+        if directive.name().as_deref() != Some(".loc") {
+            return None;
+        }
+
+        let mut arguments = directive.arguments();
+        let id = number(&arguments.next()?)?;
+        let line = number(&arguments.next()?)?;
+
+        // The unit that the `.loc` is in, or the first one if it's before
+        // any `.file 0`
+        let offset = node.text_range().start();
+        let unit = self
+            .units
+            .iter()
+            .rposition(|u| u.start <= offset)
+            .unwrap_or_default();
 
-                    Some((file_id, line))
+        Some((FileId { unit, id }, line))
+    }
+
+    /// The files whose contents don't match the MD5 given in their `.file`
+    /// directive, files that can't be read are skipped.
+    pub fn checksum_mismatches(&self) -> impl Iterator<Item = (TextRange, &str)> {
+        self.units
+            .iter()
+            .flat_map(|u| u.files.values())
+            .filter(|f| {
+                f.md5.as_ref().is_some_and(|md5| {
+                    std::fs::read(&f.name)
+                        .is_ok_and(|data| format!("{:x}", Md5::digest(data)) != *md5)
                 })
-        } else {
-            None
+            })
+            .map(|f| (f.range, f.name.as_str()))
+    }
+
+    /// Checks if the file is the source file that the unit was compiled
+    /// from rather than a header, this is always true if the source file
+    /// isn't known.
+    pub fn is_source_file(&self, file: FileId) -> bool {
+        let Some(unit) = self.units.get(file.unit) else {
+            return true;
+        };
+        let Some(name) = unit.files.get(&file.id).map(|f| &f.name) else {
+            return true;
+        };
+
+        match (unit.files.get(&0), &unit.source) {
+            (Some(source), _) if file.id != 0 => source.name == *name,
+            (None, Some(source)) => PathBuf::from(name).ends_with(source),
+            _ => true,
         }
     }
 
-    fn get_filename(&self, id: u32) -> Option<&String> {
-        self.map.get(&id).map(|f| &f.name)
+    fn get_file(&self, file: FileId) -> Option<&FileInfo> {
+        self.units.get(file.unit)?.files.get(&file.id)
+    }
+
+    fn get_filename(&self, file: FileId) -> Option<&String> {
+        self.get_file(file).map(|f| &f.name)
     }
 
     fn load_file(filename: &str) -> Option<String> {
@@ -115,13 +293,71 @@ impl DebugMap {
     }
 }
 
+/// Names that are still relative after resolving them are kept relative to
+/// the working directory, as there is no directory to resolve them from.
+fn file_uri(name: &str) -> Option<Url> {
+    if Path::new(name).is_absolute() {
+        Url::from_file_path(name).ok()
+    } else {
+        Url::parse(format!("file://{name}").as_str()).ok()
+    }
+}
+
+fn number(element: &SyntaxElement) -> Option<u32> {
+    let token = element.as_token()?;
+    (token.kind() == SyntaxKind::NUMBER)
+        .then(|| token.text().parse().ok())
+        .flatten()
+}
+
+/// The file and line of a `file:line` marker from `objdump -l`
+fn source_location(node: &SyntaxNode) -> Option<(String, LineNumber)> {
+    let mut tokens = node.children_with_tokens();
+    let file = tokens.find_map(|t| string(&t))?;
+    let line = tokens.find_map(|t| number(&t))?;
+
+    Some((file, line))
+}
+
+/// The hex digits of an MD5 checksum e.g. `0x0123...`, leading zeros can be
+/// left out.
+fn checksum(element: &SyntaxElement) -> Option<String> {
+    let token = element.as_token()?;
+    let digits = token
+        .text()
+        .strip_prefix("0x")
+        .or_else(|| token.text().strip_prefix("0X"))?;
+
+    (digits.len() <= 32 && digits.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| format!("{:0>32}", digits.to_ascii_lowercase()))
+}
+
+fn string(element: &SyntaxElement) -> Option<String> {
+    let token = element.as_token()?;
+    (token.kind() == SyntaxKind::STRING).then(|| token.text().trim_matches('"').to_string())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::asm::parser::Parser;
 
+    use itertools::Itertools;
+
     use pretty_assertions::assert_eq;
 
+    fn files(map: &DebugMap) -> Vec<(u32, &str)> {
+        map.units
+            .iter()
+            .flat_map(|u| {
+                u.files
+                    .iter()
+                    .map(|(id, f)| (*id, f.name.as_str()))
+                    .sorted()
+            })
+            .collect()
+    }
+
     #[test]
     fn debug_map() {
         let data = r#"Lfunc_begin0:
@@ -129,26 +365,18 @@ mod test {
 	.loc	2 2132 0
 	.cfi_startproc"#;
         let tree = Parser::in_memory(data, &Default::default());
-        let map = DebugMap::new(&tree.tree());
+        let map = DebugMap::new(&tree.tree(), None);
 
-        let values = map.map.iter().clone().collect::<Vec<_>>();
-        assert_eq!(
-            values,
-            vec![(
-                &2,
-                &FileInfo {
-                    name: String::from("filename"),
-                    contents: Default::default(),
-                }
-            )]
-        );
+        assert_eq!(files(&map), vec![(2, "filename")]);
 
         assert_eq!(
             map.get_file_location(
-                ast::find_kind_index(&tree.tree(), 1, SyntaxKind::DIRECTIVE)
-                    .unwrap()
-                    .as_node()
+                tree.tree()
+                    .descendants()
+                    .filter_map(Directive::cast)
+                    .nth(1)
                     .unwrap()
+                    .syntax()
             ),
             Some(DocumentLocation {
                 uri: Url::parse("file://filename").unwrap(),
@@ -173,29 +401,61 @@ mod test {
 	.loc	1 2132 0
 	.cfi_startproc"#;
         let tree = Parser::in_memory(data, &Default::default());
-        let map = DebugMap::new(&tree.tree());
+        let map = DebugMap::new(&tree.tree(), None);
+
+        assert_eq!(files(&map), vec![(1, "./main.c")]);
 
-        let values = map.map.iter().clone().collect::<Vec<_>>();
         assert_eq!(
-            values,
-            vec![(
-                &1,
-                &FileInfo {
-                    name: String::from("./main.c"),
-                    contents: Default::default(),
-                }
-            )]
+            map.get_file_location(
+                tree.tree()
+                    .descendants()
+                    .filter_map(Directive::cast)
+                    .nth(1)
+                    .unwrap()
+                    .syntax()
+            ),
+            Some(DocumentLocation {
+                uri: Url::parse("file://./main.c").unwrap(),
+                range: DocumentRange {
+                    start: DocumentPosition {
+                        line: 2131,
+                        column: 0
+                    },
+                    end: DocumentPosition {
+                        line: 2131,
+                        column: 0
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn debug_map_relative_to_file() {
+        let data = r#"Lfunc_begin0:
+	.file	1 "main.c"
+	.file	2 "/usr/include/stdio.h"
+	.loc	1 2132 0
+	.cfi_startproc"#;
+        let tree = Parser::in_memory(data, &Default::default());
+        let map = DebugMap::new(&tree.tree(), Some(Path::new("/home/user")));
+
+        assert_eq!(
+            files(&map),
+            vec![(1, "/home/user/main.c"), (2, "/usr/include/stdio.h")]
         );
 
         assert_eq!(
             map.get_file_location(
-                ast::find_kind_index(&tree.tree(), 1, SyntaxKind::DIRECTIVE)
-                    .unwrap()
-                    .as_node()
+                tree.tree()
+                    .descendants()
+                    .filter_map(Directive::cast)
+                    .nth(2)
                     .unwrap()
+                    .syntax()
             ),
             Some(DocumentLocation {
-                uri: Url::parse("file://./main.c").unwrap(),
+                uri: Url::parse("file:///home/user/main.c").unwrap(),
                 range: DocumentRange {
                     start: DocumentPosition {
                         line: 2131,
@@ -209,4 +469,77 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn debug_map_dwarf5() {
+        let data = r#"	.file	"main.c"
+	.file	0 "/home/user/project" "main.c" md5 0xb5f1a9a6cb9e5d03bce2b4ec8fd3c0ef
+	.file	1 "include" "util.h" md5 0x01
+	.file	2 "/usr/include" "stdio.h"
+	.loc	1 10 0
+	.file	0 "/home/user/other" "other.c"
+	.file	1 "other.h"
+	.loc	1 20 0"#;
+        let tree = Parser::in_memory(data, &Default::default());
+        let map = DebugMap::new(&tree.tree(), None);
+
+        assert_eq!(
+            files(&map),
+            vec![
+                (0, "/home/user/project/main.c"),
+                (1, "/home/user/project/include/util.h"),
+                (2, "/usr/include/stdio.h"),
+                (0, "/home/user/other/other.c"),
+                (1, "/home/user/other/other.h"),
+            ]
+        );
+        assert_eq!(
+            map.units[0].files[&1].md5.as_deref(),
+            Some("00000000000000000000000000000001")
+        );
+
+        let locations = tree
+            .tree()
+            .descendants()
+            .filter_map(Directive::cast)
+            .filter(|d| d.name().as_deref() == Some(".loc"))
+            .filter_map(|d| map.get_file_location(d.syntax()))
+            .map(|l| (l.uri.to_string(), l.range.start.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            locations,
+            vec![
+                (String::from("file:///home/user/project/include/util.h"), 9),
+                (String::from("file:///home/user/other/other.h"), 19),
+            ]
+        );
+    }
+
+    #[test]
+    fn debug_map_checksum() {
+        let path = std::env::temp_dir().join("lsp-asm-debug-map-checksum.c");
+        std::fs::write(&path, "abc").unwrap();
+        let path = path.to_str().unwrap();
+
+        let data = format!(
+            r#"	.file	0 "/" "{path}" md5 0x900150983cd24fb0d6963f7d28e17f72
+	.file	1 "{path}" md5 0x900150983cd24fb0d6963f7d28e17f73
+	.file	2 "/missing/file.c" md5 0x900150983cd24fb0d6963f7d28e17f73"#
+        );
+        let tree = Parser::in_memory(&data, &Default::default());
+        let map = DebugMap::new(&tree.tree(), None);
+
+        assert_eq!(
+            map.checksum_mismatches()
+                .map(|(range, name)| (
+                    tree.position()
+                        .get_position_for_size(&range.start())
+                        .unwrap()
+                        .line,
+                    name
+                ))
+                .collect::<Vec<_>>(),
+            vec![(1, path)]
+        );
+    }
 }
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use itertools::Itertools;
 use lsp_types::{Location, Url};
 use parser::parse_number;
+use rowan::TextSize;
 use syntax::ast::{find_kind_index, find_parent, SyntaxKind, SyntaxNode, SyntaxToken};
 use syntax::utils::token_is_local_label;
 
@@ -10,7 +11,7 @@ use crate::file_util;
 use crate::handler::context::Context;
 use crate::handler::error::{lsp_error_map, ErrorCode};
 
-use super::ast::LabelToken;
+use super::ast::{AstNode, LabelToken, MacroNode};
 use super::objdump_util;
 use super::parser::Parser;
 
@@ -25,15 +26,7 @@ where
 {
     let text = token.text();
     let handle_node = |node: SyntaxNode, parser: &Parser| {
-        node.descendants_with_tokens()
-            .filter_map(|d| d.into_token())
-            .filter(|token| token.kind() == SyntaxKind::LABEL)
-            .filter(move |label| {
-                parser
-                    .token::<LabelToken>(label)
-                    .map(|name| name.name() == text)
-                    .unwrap_or(false)
-            })
+        find_labels(parser, &node, text)
             .filter_map(|t| map(parser, &t))
             .collect_vec()
     };
@@ -49,6 +42,31 @@ where
     })
 }
 
+/// The labels within `node` that are called `name`
+pub(super) fn find_labels<'a>(
+    parser: &'a Parser,
+    node: &SyntaxNode,
+    name: &'a str,
+) -> impl Iterator<Item = SyntaxToken> + 'a {
+    labels(parser, node).filter(move |label| {
+        parser
+            .token::<LabelToken>(label)
+            .map(|label| label.name() == name)
+            .unwrap_or(false)
+    })
+}
+
+/// The labels within `node` that aren't in an inactive conditional branch
+pub(super) fn labels<'a>(
+    parser: &'a Parser,
+    node: &SyntaxNode,
+) -> impl Iterator<Item = SyntaxToken> + 'a {
+    node.descendants_with_tokens()
+        .filter_map(|d| d.into_token())
+        .filter(|token| token.kind() == SyntaxKind::LABEL)
+        .filter(|label| !parser.is_inactive(label))
+}
+
 pub(super) fn goto_definition_label(
     context: Arc<Context>,
     parser: &Parser,
@@ -115,14 +133,19 @@ pub(crate) fn goto_definition_const(
     parser: &Parser,
 ) -> Result<Vec<Location>, lsp_server::ResponseError> {
     let name = token.text().trim_start_matches('#');
+    let current = in_effect(parser, name, token);
     let handle_node = |parser: &Parser| {
         parser
             .tree()
             .descendants()
             .filter(|d| matches!(d.kind(), SyntaxKind::CONST_DEF))
+            .filter(|d| {
+                current.is_none() || current == Some((parser.uri(), d.text_range().start()))
+            })
             .filter_map(|d| find_kind_index(&d, 0, SyntaxKind::NAME))
             .filter_map(|t| t.into_token())
             .filter(|t| t.text() == name)
+            .filter(|t| !parser.is_inactive(t))
             .filter_map(|token| {
                 Some(lsp_types::Location::new(
                     parser.uri().clone(),
@@ -144,14 +167,66 @@ pub(crate) fn goto_definition_reg_alias(
     parser: &Parser,
 ) -> Result<Vec<Location>, lsp_server::ResponseError> {
     let name = token.text();
+    let current = in_effect(parser, name, token);
     let handle_node = |parser: &Parser| {
         parser
             .tree()
             .descendants()
             .filter(|d| matches!(d.kind(), SyntaxKind::ALIAS))
+            .filter(|d| {
+                current.is_none() || current == Some((parser.uri(), d.text_range().start()))
+            })
             .filter_map(|d| find_kind_index(&d, 0, SyntaxKind::REGISTER_ALIAS))
             .filter_map(|t| t.into_token())
             .filter(|t| t.text() == name)
+            .filter(|t| !parser.is_inactive(t))
+            .filter_map(|token| {
+                Some(lsp_types::Location::new(
+                    parser.uri().clone(),
+                    parser.position().range_for_token(&token)?.into(),
+                ))
+            })
+            .collect_vec()
+            .into_iter()
+    };
+    let def = context.related_parsers(true, parser.uri().clone(), handle_node);
+
+    Ok(def)
+}
+
+/// Gets the location of the definition of `name` that is in effect at
+/// `token`, when it's defined in this file. Names can be defined more than
+/// once with `.set` or after an `.unreq`.
+fn in_effect<'p>(
+    parser: &'p Parser,
+    name: &str,
+    token: &SyntaxToken,
+) -> Option<(&'p Url, TextSize)> {
+    let offset = parser
+        .alias()
+        .definition_offset(name, token.text_range().start())?;
+
+    parser
+        .tree()
+        .descendants()
+        .filter(|d| matches!(d.kind(), SyntaxKind::CONST_DEF | SyntaxKind::ALIAS))
+        .any(|d| d.text_range().start() == offset)
+        .then_some((parser.uri(), offset))
+}
+
+pub(crate) fn goto_definition_macro(
+    context: Arc<Context>,
+    token: &SyntaxToken,
+    parser: &Parser,
+) -> Result<Vec<Location>, lsp_server::ResponseError> {
+    let name = token.text();
+    let handle_node = |parser: &Parser| {
+        parser
+            .tree()
+            .descendants()
+            .filter_map(|d| MacroNode::cast(&d)?.name())
+            .filter(|t| t.text().eq_ignore_ascii_case(name))
+            .filter(|t| !parser.is_inactive(t))
             .filter_map(|token| {
                 Some(lsp_types::Location::new(
                     parser.uri().clone(),
@@ -170,25 +245,14 @@ fn handle_definition_objdump(
     parser: &Parser,
     token: &SyntaxToken,
 ) -> Result<Vec<Location>, lsp_server::ResponseError> {
-    let text = token.text();
-
-    let mut labels = parser
-        .tree()
-        .descendants_with_tokens()
-        .filter_map(|d| d.into_token())
-        .filter(|token| token.kind() == SyntaxKind::LABEL)
-        .filter(move |label| {
-            parser
-                .token::<LabelToken>(label)
-                .map(|name| name.name() == text)
-                .unwrap_or(false)
-        })
-        .collect_vec();
+    let mut labels = find_labels(parser, &parser.tree(), token.text()).collect_vec();
 
     let mut offsets = labels
         .iter()
         .filter_map(|label| {
-            let offset = parse_number(token.next_token()?.next_token()?.text()).ok()?;
+            // e.g. `<main+0x6>`, relocations can also subtract an addend
+            let operator = token.next_token().filter(|t| t.text() == "+")?;
+            let offset = parse_number(operator.next_token()?.text()).ok()?;
             let instruction =
                 objdump_util::find_instruction_at_relative_offset(&label.parent()?, offset);
             find_kind_index(&instruction?, 0, SyntaxKind::MNEMONIC)?.into_token()
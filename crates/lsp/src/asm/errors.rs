@@ -0,0 +1,106 @@
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+use syntax::ast::{SyntaxKind, SyntaxNode};
+
+use super::parser::Parser;
+use crate::types::DocumentRange;
+
+/// Creates a diagnostic for each part of the file that couldn't be parsed,
+/// these don't need an assembler so are available as soon as the file has
+/// been parsed.
+pub(crate) fn syntax_errors(parser: &Parser) -> Vec<Diagnostic> {
+    parser
+        .tree()
+        .descendants()
+        .filter(|d| !d.first_token().is_some_and(|t| parser.is_inactive(&t)))
+        .filter_map(|node| {
+            let (range, message) = match node.kind() {
+                SyntaxKind::ERROR => (parser.position().range_for_node(&node)?, message(&node)),
+                SyntaxKind::BRACKETS if is_unclosed(&node) => {
+                    let open = node.first_token()?;
+                    let message = format!("unclosed `{}`", open.text());
+                    (parser.position().range_for_token(&open)?, message)
+                }
+                _ => return None,
+            };
+
+            Some(Diagnostic {
+                range: range.into(),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some(String::from("lsp-asm")),
+                message,
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Brackets that aren't closed contain the rest of the line without a
+/// closing bracket.
+fn is_unclosed(brackets: &SyntaxNode) -> bool {
+    !brackets.children_with_tokens().any(|c| {
+        matches!(
+            c.kind(),
+            SyntaxKind::R_PAREN | SyntaxKind::R_SQ | SyntaxKind::R_CURLY | SyntaxKind::R_ANGLE
+        )
+    })
+}
+
+/// Creates a diagnostic for each `.cfi_*` directive that leaves the call frame
+/// information in an inconsistent state.
+pub(crate) fn cfi_errors(parser: &Parser) -> Vec<Diagnostic> {
+    let position = parser.position();
+
+    parser
+        .cfi()
+        .errors
+        .iter()
+        .filter_map(|error| {
+            Some(Diagnostic {
+                range: DocumentRange {
+                    start: position.get_position_for_size(&error.range.start())?,
+                    end: position.get_position_for_size(&error.range.end())?,
+                }
+                .into(),
+                severity: Some(error.severity),
+                source: Some(String::from("lsp-asm")),
+                message: error.message.clone(),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Warns about source files that have changed since the assembly was
+/// generated, the `.loc` code lens would show the wrong lines for these.
+pub(crate) fn debug_errors(parser: &Parser) -> Vec<Diagnostic> {
+    let position = parser.position();
+
+    parser
+        .debug_map()
+        .checksum_mismatches()
+        .filter_map(|(range, name)| {
+            Some(Diagnostic {
+                range: DocumentRange {
+                    start: position.get_position_for_size(&range.start())?,
+                    end: position.get_position_for_size(&range.end())?,
+                }
+                .into(),
+                severity: Some(DiagnosticSeverity::WARNING),
+                source: Some(String::from("lsp-asm")),
+                message: format!("`{name}` doesn't match the MD5 checksum, it may have changed since this was generated"),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+fn message(error: &SyntaxNode) -> String {
+    let Some(token) = error.first_token() else {
+        return String::from("syntax error");
+    };
+
+    match token.kind() {
+        SyntaxKind::STRING => String::from("unterminated string"),
+        _ => format!("unexpected `{}`", token.text().trim()),
+    }
+}
@@ -0,0 +1,302 @@
+//! Evaluates constant expressions within the assembly, this resolves any
+//! constants, labels and the `.` location counter that the expression uses.
+//!
+//! Locations are offsets from the start of the section as the address of the
+//! section isn't known until link time, labels can only be used within the
+//! section that they are defined in.
+
+use base::{Architecture, Dialect};
+use itertools::Itertools;
+use rowan::TextSize;
+use syntax::ast::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+use super::parser::Parser;
+
+/// Limit on how deep constants can refer to other constants, this prevents
+/// a constant that refers to itself from recursing forever.
+const MAX_DEPTH: usize = 32;
+
+pub(crate) struct Evaluator<'p> {
+    parser: &'p Parser,
+    root: SyntaxNode,
+}
+
+impl<'p> Evaluator<'p> {
+    pub(crate) fn new(parser: &'p Parser) -> Self {
+        Self {
+            parser,
+            root: parser.tree(),
+        }
+    }
+
+    /// Evaluates `expr` as if it were written at `offset` within the file
+    pub(crate) fn evaluate(&self, expr: &str, offset: Option<TextSize>) -> Option<i128> {
+        self.evaluate_depth(expr, offset, 0)
+    }
+
+    /// Gets the value of the constant `name` as it is defined at `offset`
+    pub(crate) fn constant(&self, name: &str, offset: Option<TextSize>) -> Option<i128> {
+        self.constant_depth(name, offset, 0)
+    }
+
+    fn evaluate_depth(&self, expr: &str, offset: Option<TextSize>, depth: usize) -> Option<i128> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        syntax::expr::evaluate(expr, &|name| match name {
+            "." | "$" => self.location(offset?),
+            "$$" => offset.map(|_| 0),
+            _ => self
+                .constant_depth(name, offset, depth + 1)
+                .or_else(|| self.label(name, offset?)),
+        })
+    }
+
+    fn constant_depth(&self, name: &str, offset: Option<TextSize>, depth: usize) -> Option<i128> {
+        // Definitions within this file are preferred as the location of the
+        // definition is needed for any use of `.`
+        match self.definition(name, offset) {
+            Some(expr) => self.evaluate_depth(
+                &expression_text(&expr),
+                Some(expr.text_range().start()),
+                depth,
+            ),
+            None => self.evaluate_depth(
+                self.parser
+                    .alias()
+                    .get_constant_for_token(name, offset.unwrap_or_default())?,
+                None,
+                depth,
+            ),
+        }
+    }
+
+    /// Gets the expression of the definition of `name` that is in effect at
+    /// `offset`, a constant can be used before it's defined so the first
+    /// definition is used for anything before it.
+    fn definition(&self, name: &str, offset: Option<TextSize>) -> Option<SyntaxNode> {
+        let definitions = self
+            .root
+            .descendants()
+            .filter(|d| d.kind() == SyntaxKind::CONST_DEF)
+            .filter(|d| self.is_active(d))
+            .filter(|d| {
+                d.children_with_tokens()
+                    .any(|c| c.kind() == SyntaxKind::NAME && c.to_string() == name)
+            })
+            .filter_map(|d| d.children().find(|c| c.kind() == SyntaxKind::EXPR))
+            .collect_vec();
+
+        // A redefinition that refers to itself e.g. `.set SIZE, SIZE + 4`
+        // uses the previous value
+        offset
+            .and_then(|offset| {
+                definitions.iter().rev().find(|expr| {
+                    expr.parent()
+                        .is_some_and(|d| d.text_range().start() <= offset)
+                        && !expr.text_range().contains_inclusive(offset)
+                })
+            })
+            .or_else(|| definitions.first())
+            .cloned()
+    }
+
+    /// Checks that the node isn't in an inactive conditional branch
+    fn is_active(&self, node: &SyntaxNode) -> bool {
+        !node
+            .first_token()
+            .is_some_and(|t| self.parser.is_inactive(&t))
+    }
+
+    /// Gets the location of the label `name` if it's in the same section as
+    /// `offset`
+    fn label(&self, name: &str, offset: TextSize) -> Option<i128> {
+        let label = self
+            .root
+            .descendants_with_tokens()
+            .filter_map(|d| d.into_token())
+            .filter(|t| t.kind() == SyntaxKind::LABEL)
+            .filter(|t| {
+                t.parent_ancestors()
+                    .all(|a| a.kind() != SyntaxKind::MACRO_DEF)
+            })
+            .find(|t| t.text().trim_end_matches(':') == name)?;
+
+        let current = self.root.token_at_offset(offset).right_biased()?;
+        (section(&label) == section(&current)).then_some(())?;
+
+        self.location(label.text_range().start())
+    }
+
+    /// Gets the location within the section of `offset`, this is the size of
+    /// everything in the section that comes before it.
+    fn location(&self, offset: TextSize) -> Option<i128> {
+        let token = self.root.token_at_offset(offset).right_biased()?;
+
+        section(&token)
+            .unwrap_or_else(|| self.root.clone())
+            .descendants()
+            .filter(|d| {
+                matches!(
+                    d.kind(),
+                    SyntaxKind::INSTRUCTION | SyntaxKind::DIRECTIVE | SyntaxKind::MACRO_CALL
+                )
+            })
+            .take_while(|d| d.text_range().end() <= offset)
+            .filter(|d| d.ancestors().all(|a| a.kind() != SyntaxKind::MACRO_DEF))
+            .filter(|d| self.is_active(d))
+            .try_fold(0, |location, statement| {
+                Some(location + self.size(&statement, location)?)
+            })
+    }
+
+    /// Gets the number of bytes that the statement adds to the section, None
+    /// is returned if this can't be determined.
+    fn size(&self, statement: &SyntaxNode, location: i128) -> Option<i128> {
+        match statement.kind() {
+            // Only the architectures with a fixed instruction size are known,
+            // x86 is variable and RISC-V and Thumb have compressed encodings.
+            SyntaxKind::INSTRUCTION => match self.parser.architecture() {
+                Architecture::AArch64 | Architecture::PowerPC64 => Some(4),
+                _ => None,
+            },
+            SyntaxKind::DIRECTIVE => self.directive_size(statement, location),
+            _ => None,
+        }
+    }
+
+    fn directive_size(&self, directive: &SyntaxNode, location: i128) -> Option<i128> {
+        let mnemonic = directive
+            .children_with_tokens()
+            .find(|c| c.kind() == SyntaxKind::MNEMONIC)?
+            .to_string()
+            .to_lowercase();
+        let operands = operands(directive);
+        let count = operands.len() as i128;
+        let value = |index: usize| {
+            self.evaluate(
+                operands.get(index)?.as_str(),
+                Some(directive.text_range().start()),
+            )
+        };
+        let align = |alignment: i128| {
+            (alignment > 0).then(|| (alignment - location % alignment) % alignment)
+        };
+        let x86 = matches!(
+            self.parser.architecture(),
+            Architecture::X86 | Architecture::X86_64
+        );
+
+        match mnemonic.as_str() {
+            ".byte" | ".1byte" => Some(count),
+            ".short" | ".hword" | ".2byte" | ".value" => Some(2 * count),
+            ".word" if x86 => Some(2 * count),
+            ".word" | ".long" | ".int" | ".4byte" | ".float" | ".single" | ".inst" => {
+                Some(4 * count)
+            }
+            ".quad" | ".8byte" | ".xword" | ".dword" | ".double" => Some(8 * count),
+            ".octa" => Some(16 * count),
+            ".ascii" => operands.iter().map(|s| string_size(s)).sum(),
+            ".asciz" | ".string" => operands.iter().map(|s| Some(string_size(s)? + 1)).sum(),
+            ".zero" | ".skip" | ".space" => value(0),
+            ".fill" => Some(value(0)? * operands.get(1).map_or(Some(1), |_| value(1))?),
+            ".balign" => align(value(0)?),
+            ".p2align" => align(1 << value(0)?),
+            ".align" if x86 => align(value(0)?),
+            ".align" => align(1 << value(0)?),
+            ".org" | ".incbin" | ".sleb128" | ".uleb128" | ".rept" | ".irp" | ".irpc" => None,
+            "db" if self.parser.dialect() == Dialect::Nasm => {
+                operands.iter().map(|o| string_size(o).or(Some(1))).sum()
+            }
+            "dw" => Some(2 * count),
+            "dd" => Some(4 * count),
+            "dq" => Some(8 * count),
+            "resb" => value(0),
+            "resw" => Some(2 * value(0)?),
+            "resd" => Some(4 * value(0)?),
+            "resq" => Some(8 * value(0)?),
+            "times" | "incbin" | "align" | "alignb" => None,
+            // Everything else e.g. `.globl` or `.cfi_startproc` doesn't emit
+            // any data.
+            _ => Some(0),
+        }
+    }
+}
+
+/// Gets the section that contains `token`
+fn section(token: &SyntaxToken) -> Option<SyntaxNode> {
+    token
+        .parent_ancestors()
+        .find(|a| a.kind() == SyntaxKind::SECTION)
+}
+
+/// Gets the text of the expression without any comments
+pub(crate) fn expression_text(node: &SyntaxNode) -> String {
+    node.descendants_with_tokens()
+        .filter_map(|d| d.into_token())
+        .filter(|t| t.kind() != SyntaxKind::COMMENT)
+        .map(|t| t.text().to_string())
+        .join("")
+}
+
+/// Gets the text of each of the comma separated operands of a directive
+fn operands(directive: &SyntaxNode) -> Vec<String> {
+    let text = directive
+        .children_with_tokens()
+        .skip_while(|c| c.kind() != SyntaxKind::MNEMONIC)
+        .skip(1)
+        .filter(|c| c.kind() != SyntaxKind::COMMENT)
+        .map(|c| match c.kind() {
+            // Commas within brackets aren't direct children of the directive
+            SyntaxKind::COMMA => String::from("\0"),
+            _ => c.to_string(),
+        })
+        .join("");
+
+    text.split('\0')
+        .map(|operand| operand.trim().to_string())
+        .filter(|operand| !operand.is_empty())
+        .collect()
+}
+
+/// Gets the number of bytes in a string literal, accounting for any escapes
+fn string_size(text: &str) -> Option<i128> {
+    let inner = text.strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut size = 0;
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            size += c.len_utf8() as i128;
+            continue;
+        }
+
+        match chars.next()? {
+            // Octal escapes have up to three digits
+            '0'..='7' => {
+                for _ in 0..2 {
+                    chars.next_if(|c| c.is_digit(8));
+                }
+            }
+            'x' => while chars.next_if(|c| c.is_ascii_hexdigit()).is_some() {},
+            _ => {}
+        }
+        size += 1;
+    }
+
+    Some(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_size() {
+        assert_eq!(string_size(r#""hello""#), Some(5));
+        assert_eq!(string_size(r#""a\n\"b\"""#), Some(5));
+        assert_eq!(string_size(r#""\0\101\x41""#), Some(3));
+        assert_eq!(string_size("label"), None);
+    }
+}
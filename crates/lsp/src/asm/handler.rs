@@ -1,18 +1,24 @@
 #![allow(deprecated)]
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use super::ast::{AstNode, LabelNode, LocalLabelNode, RegisterToken};
+use super::ast::{AstNode, LabelNode, LocalLabelNode, MacroNode, RegisterToken, SectionNode};
 use super::llvm_mca::run_mca;
 use super::parser::{split_parsed_include, Parser, PositionInfo};
-use super::{definition, references};
+use super::symbols::{SymbolTable, SymbolType};
+use super::{control_flow, definition, errors, listing, references};
 use crate::asm::{hovers, inlay_hints, signature};
 use crate::completion;
 use crate::handler::context::Context;
 use crate::handler::error::{lsp_error_map, ErrorCode};
+use crate::handler::ext::{
+    ControlFlowEdge, FilteredListingResult, ListingFilters, SourceLineMapping,
+};
 use crate::handler::semantic::semantic_delta_transform;
 use crate::handler::types::DocumentChange;
-use crate::types::{DocumentPosition, DocumentRange};
+use crate::types::{DocumentPosition, DocumentRange, LineNumber};
 
 use arch::registers::registers_for_architecture;
 use base::register::RegisterKind;
@@ -23,7 +29,7 @@ use fmt::FormatOptions;
 use itertools::*;
 use lsp_server::ResponseError;
 use lsp_types::{
-    AnnotatedTextEdit, CodeActionOrCommand, CodeLens, Command, CompletionList,
+    AnnotatedTextEdit, CodeActionOrCommand, CodeLens, Command, CompletionList, Diagnostic,
     DocumentHighlightKind, DocumentSymbol, DocumentSymbolResponse, HoverContents, InlayHint,
     Location, MarkupContent, OneOf, OptionalVersionedTextDocumentIdentifier, Range, SemanticToken,
     SemanticTokens, SemanticTokensResult, SignatureHelp, SymbolKind, TextDocumentEdit, TextEdit,
@@ -31,8 +37,10 @@ use lsp_types::{
 };
 use parser::ParsedInclude;
 use rowan::TextRange;
-use syntax::ast::{self, find_kind_index, find_parent, SyntaxKind, SyntaxToken};
-use syntax::utils::token_is_local_label;
+use syntax::ast::{
+    self, find_kind_index, find_parent, AstNode as _, SyntaxKind, SyntaxNode, SyntaxToken,
+};
+use syntax::utils::{token_is_local_label, token_is_macro_name};
 
 pub struct AssemblyLanguageServerProtocol {
     parser: Parser,
@@ -67,13 +75,8 @@ impl AssemblyLanguageServerProtocol {
             return Err(lsp_error_map(ErrorCode::InvalidVersion(self.uri.clone())));
         }
 
-        let mut contents = self.parser.reconstruct_file();
-        for change in changes {
-            self.apply_change(&mut contents, change);
-        }
         self.version = version;
-        let (parser, includes) =
-            Parser::from(self.uri.clone(), contents.as_str(), context.config());
+        let (parser, includes) = self.parser.update(changes, context.config());
         self.parser = parser;
 
         Ok(handle_includes(includes, context, &self.uri))
@@ -105,7 +108,13 @@ impl AssemblyLanguageServerProtocol {
         };
 
         let res = match token.kind() {
+            _ if token.parent().map(|p| p.kind()) == Some(SyntaxKind::SOURCE_LOCATION) => {
+                definition::goto_definition_loc(&self.parser, &token)?
+            }
             SyntaxKind::TOKEN => definition::goto_definition_label(context, &self.parser, &token)?,
+            SyntaxKind::MNEMONIC if token_is_macro_name(&token) => {
+                definition::goto_definition_macro(context, &token, &self.parser)?
+            }
             SyntaxKind::MNEMONIC if token.text() == ".loc" => {
                 definition::goto_definition_loc(&self.parser, &token)?
             }
@@ -190,7 +199,9 @@ impl AssemblyLanguageServerProtocol {
             .ok_or_else(|| lsp_error_map(ErrorCode::TokenNotFound))?;
 
         let hover = match token.kind() {
-            SyntaxKind::TOKEN => hovers::get_token_hover(context, &self.parser, token),
+            SyntaxKind::TOKEN => hovers::get_token_hover(context, &self.parser, token.clone())
+                .filter(|hover| !hover.is_empty())
+                .or_else(|| hovers::get_expression_hover(&self.parser, &token)),
             SyntaxKind::NUMBER => hovers::get_numeric_hover(
                 &self
                     .parser
@@ -198,45 +209,73 @@ impl AssemblyLanguageServerProtocol {
                     .ok_or_else(|| lsp_error_map(ErrorCode::CastFailed))?,
             ),
             SyntaxKind::LABEL => hovers::get_label_hover(
+                &self.parser,
                 &self
                     .parser
                     .token(&token)
                     .ok_or_else(|| lsp_error_map(ErrorCode::CastFailed))?,
             ),
-            SyntaxKind::MNEMONIC => {
+            SyntaxKind::MNEMONIC if token_is_macro_name(&token) => {
+                hovers::get_macro_hover(context, &self.parser, &token)
+            }
+            SyntaxKind::MNEMONIC => hovers::get_cfi_hover(&self.parser, &token).or_else(|| {
                 hovers::get_hover_mnemonic(&token, self.parser.architecture(), self.parser.alias())
+            }),
+            SyntaxKind::RELOCATION
+                if token.parent().map(|p| p.kind()) == Some(SyntaxKind::RELOCATION) =>
+            {
+                hovers::get_relocation_hover(&token)
             }
             SyntaxKind::REGISTER_ALIAS => hovers::get_alias_hover(&token, self.parser.alias()),
-            SyntaxKind::CONSTANT => hovers::get_constant_hover(&token, self.parser.alias()),
+            SyntaxKind::CONSTANT => hovers::get_constant_hover(&self.parser, &token),
+            SyntaxKind::NAME if token.parent().map(|p| p.kind()) == Some(SyntaxKind::CONST_DEF) => {
+                hovers::get_constant_hover(&self.parser, &token)
+            }
             SyntaxKind::L_PAREN
             | SyntaxKind::R_PAREN
             | SyntaxKind::L_SQ
             | SyntaxKind::R_SQ
-            | SyntaxKind::L_CURLY
+            | SyntaxKind::OPERATOR
+            | SyntaxKind::IMMEDIATE => hovers::get_expression_hover(&self.parser, &token),
+            SyntaxKind::L_CURLY
             | SyntaxKind::R_CURLY
             | SyntaxKind::L_ANGLE
             | SyntaxKind::R_ANGLE
             | SyntaxKind::REGISTER
             | SyntaxKind::WHITESPACE
             | SyntaxKind::COMMA
-            | SyntaxKind::OPERATOR
             | SyntaxKind::STRING
             | SyntaxKind::LOCAL_LABEL
             | SyntaxKind::COMMENT
-            | SyntaxKind::IMMEDIATE
+            | SyntaxKind::KEYWORD
             | SyntaxKind::FLOAT
             | SyntaxKind::ALIAS
             | SyntaxKind::RELOCATION
             | SyntaxKind::INSTRUCTION
             | SyntaxKind::DIRECTIVE
             | SyntaxKind::BRACKETS
+            | SyntaxKind::MEMORY_OPERAND
+            | SyntaxKind::BASE
+            | SyntaxKind::INDEX
+            | SyntaxKind::SCALE
+            | SyntaxKind::DISPLACEMENT
+            | SyntaxKind::WRITEBACK
             | SyntaxKind::METADATA
             | SyntaxKind::OBJDUMP_OFFSET
+            | SyntaxKind::SOURCE_LINE
+            | SyntaxKind::SOURCE_LOCATION
             | SyntaxKind::CONST_DEF
             | SyntaxKind::EXPR
             | SyntaxKind::NAME
+            | SyntaxKind::MACRO_DEF
+            | SyntaxKind::MACRO_CALL
+            | SyntaxKind::CONDITIONAL
+            | SyntaxKind::CONDITIONAL_BRANCH
+            | SyntaxKind::SECTION
+            | SyntaxKind::ERROR
             | SyntaxKind::ROOT => None,
-        };
+        }
+        .or_else(|| hovers::get_memory_hover(&token));
 
         debug!("hover: {:#?}", hover);
 
@@ -349,35 +388,44 @@ impl AssemblyLanguageServerProtocol {
             OperandAccessType::Write => DocumentHighlightKind::WRITE,
         };
 
-        let locations = if let Some(docs) = docs {
-            references
-                .filter_map(|token| {
-                    let kind = access_type(
-                        &token,
-                        &docs,
-                        registers,
-                        self.parser.alias(),
-                        *self.parser.architecture(),
-                    )
+        let locations = references
+            .filter_map(|token| {
+                let kind = docs
+                    .as_ref()
+                    .and_then(|docs| {
+                        access_type(
+                            &token,
+                            docs,
+                            registers,
+                            self.parser.alias(),
+                            *self.parser.architecture(),
+                            self.parser.dialect(),
+                        )
+                    })
                     .map(to_proto_kind)
-                    .or(Some(DocumentHighlightKind::TEXT));
+                    .unwrap_or(DocumentHighlightKind::TEXT);
 
-                    Some(lsp_types::DocumentHighlight {
-                        range: position_cache.range_for_token(&token)?.into(),
-                        kind,
-                    })
-                })
-                .collect()
-        } else {
-            references
-                .filter_map(|token| {
-                    Some(lsp_types::DocumentHighlight {
-                        range: position_cache.range_for_token(&token)?.into(),
-                        kind: Some(DocumentHighlightKind::TEXT),
-                    })
-                })
-                .collect()
-        };
+                // The base register of a writeback is read to get the address
+                // and then written with the updated address, a highlight is
+                // given for each as only one kind can be given to a highlight
+                let kinds = if is_writeback_base(&token) {
+                    vec![DocumentHighlightKind::READ, DocumentHighlightKind::WRITE]
+                } else {
+                    vec![kind]
+                };
+
+                let range: Range = position_cache.range_for_token(&token)?.into();
+                Some(
+                    kinds
+                        .into_iter()
+                        .map(move |kind| lsp_types::DocumentHighlight {
+                            range,
+                            kind: Some(kind),
+                        }),
+                )
+            })
+            .flatten()
+            .collect();
 
         Ok(locations)
     }
@@ -417,16 +465,24 @@ impl AssemblyLanguageServerProtocol {
                     }
                     SyntaxKind::MNEMONIC => match token.parent()?.kind() {
                         SyntaxKind::INSTRUCTION => Some(crate::handler::semantic::OPCODE_INDEX),
-                        SyntaxKind::DIRECTIVE | SyntaxKind::ALIAS | SyntaxKind::CONST_DEF => {
-                            Some(crate::handler::semantic::DIRECTIVE_INDEX)
-                        }
-                        _ => unreachable!("Invalid parent kind"),
+                        SyntaxKind::DIRECTIVE
+                        | SyntaxKind::ALIAS
+                        | SyntaxKind::CONST_DEF
+                        | SyntaxKind::MACRO_DEF
+                        | SyntaxKind::MACRO_CALL => Some(crate::handler::semantic::DIRECTIVE_INDEX),
+                        _ => None,
                     },
                     SyntaxKind::COMMENT => Some(crate::handler::semantic::COMMENT_INDEX),
-                    SyntaxKind::NUMBER | SyntaxKind::FLOAT => {
-                        Some(crate::handler::semantic::NUMERIC_INDEX)
+                    SyntaxKind::NUMBER | SyntaxKind::FLOAT => token
+                        .prev_token()
+                        .and_then(|prev| immediate_number(&prev))
+                        .is_none()
+                        .then_some(crate::handler::semantic::NUMERIC_INDEX),
+                    SyntaxKind::IMMEDIATE => {
+                        immediate_number(&token).map(|_| crate::handler::semantic::NUMERIC_INDEX)
                     }
                     SyntaxKind::STRING => Some(crate::handler::semantic::STRING_INDEX),
+                    SyntaxKind::KEYWORD => Some(crate::handler::semantic::OPCODE_INDEX),
                     SyntaxKind::REGISTER => self
                         .parser
                         .token::<RegisterToken>(&token)
@@ -443,7 +499,10 @@ impl AssemblyLanguageServerProtocol {
                         })
                         .or(Some(crate::handler::semantic::REGISTER_INDEX)),
                     SyntaxKind::REGISTER_ALIAS => {
-                        let register = self.parser.alias().get_register_for_alias(token.text())?;
+                        let register = self
+                            .parser
+                            .alias()
+                            .get_register_for_alias(token.text(), token.text_range().start())?;
                         let registers = registers_for_architecture(self.parser.architecture());
 
                         let kind = registers.get_kind(register);
@@ -465,7 +524,22 @@ impl AssemblyLanguageServerProtocol {
                     {
                         Some(crate::handler::semantic::CONSTANT_INDEX)
                     }
-                    SyntaxKind::NAME | SyntaxKind::CONST_DEF | SyntaxKind::EXPR => None,
+                    SyntaxKind::NAME
+                        if syntax::ast::find_parent(&token, SyntaxKind::MACRO_DEF).is_some() =>
+                    {
+                        Some(crate::handler::semantic::DIRECTIVE_INDEX)
+                    }
+                    SyntaxKind::NAME
+                    | SyntaxKind::CONST_DEF
+                    | SyntaxKind::EXPR
+                    | SyntaxKind::MACRO_DEF
+                    | SyntaxKind::MACRO_CALL
+                    | SyntaxKind::CONDITIONAL
+                    | SyntaxKind::CONDITIONAL_BRANCH
+                    | SyntaxKind::SECTION
+                    | SyntaxKind::SOURCE_LINE
+                    | SyntaxKind::SOURCE_LOCATION
+                    | SyntaxKind::ERROR => None,
                     SyntaxKind::L_PAREN
                     | SyntaxKind::R_PAREN
                     | SyntaxKind::L_SQ
@@ -479,19 +553,31 @@ impl AssemblyLanguageServerProtocol {
                     | SyntaxKind::COMMA
                     | SyntaxKind::OPERATOR
                     | SyntaxKind::ALIAS
-                    | SyntaxKind::IMMEDIATE
                     | SyntaxKind::INSTRUCTION
                     | SyntaxKind::DIRECTIVE
                     | SyntaxKind::BRACKETS
+                    | SyntaxKind::MEMORY_OPERAND
+                    | SyntaxKind::BASE
+                    | SyntaxKind::INDEX
+                    | SyntaxKind::SCALE
+                    | SyntaxKind::DISPLACEMENT
+                    | SyntaxKind::WRITEBACK
                     | SyntaxKind::ROOT => None,
                 } {
                     let pos = position.get_position(&token)?;
+                    let range = immediate_number(&token).map_or(token.text_range(), |number| {
+                        token.text_range().cover(number.text_range())
+                    });
                     Some(SemanticToken {
                         delta_line: pos.line,
                         delta_start: pos.column,
-                        length: token.text_range().len().into(),
+                        length: range.len().into(),
                         token_type: index,
-                        token_modifiers_bitset: 0,
+                        token_modifiers_bitset: if self.parser.is_inactive(&token) {
+                            crate::handler::semantic::INACTIVE_MODIFIER
+                        } else {
+                            0
+                        },
                     })
                 } else {
                     None
@@ -511,13 +597,18 @@ impl AssemblyLanguageServerProtocol {
         _context: Arc<Context>,
     ) -> Result<lsp_types::DocumentSymbolResponse, lsp_server::ResponseError> {
         let position = self.parser.position();
+        let symbols = self.parser.symbols();
         Ok(DocumentSymbolResponse::Nested(
             self.parser
                 .tree()
                 .descendants()
-                .filter_map(|n| {
-                    LabelNode::cast(&n).and_then(|label| label.to_document_symbol(position))
+                // Anything within a section is a child of the section
+                .filter(|n| {
+                    n.ancestors()
+                        .skip(1)
+                        .all(|a| a.kind() != SyntaxKind::SECTION)
                 })
+                .filter_map(|n| symbol_for_node(&n, position, symbols))
                 .collect::<Vec<_>>(),
         ))
     }
@@ -538,11 +629,12 @@ impl AssemblyLanguageServerProtocol {
             self.parser
                 .tree()
                 .descendants()
-                .filter(|d| matches!(d.kind(), SyntaxKind::DIRECTIVE))
-                .filter(|d| {
-                    ast::find_kind_index(d, 0, SyntaxKind::MNEMONIC)
+                .filter(|d| match d.kind() {
+                    SyntaxKind::DIRECTIVE => ast::find_kind_index(d, 0, SyntaxKind::MNEMONIC)
                         .and_then(|t| t.as_token().map(|t| t.text() == ".loc"))
-                        .unwrap_or(false)
+                        .unwrap_or(false),
+                    SyntaxKind::SOURCE_LOCATION => true,
+                    _ => false,
                 })
                 .filter_map(|n| {
                     let location = map.get_location(&n)?;
@@ -583,6 +675,13 @@ impl AssemblyLanguageServerProtocol {
             )));
         }
 
+        if matches!(self.parser().file_type(), base::FileType::Assembly) {
+            return Ok(Some(inlay_hints::cfi_inlay_hints(
+                parser,
+                range.unwrap_or_else(|| parser.text_range()),
+            )));
+        }
+
         Ok(None)
     }
 
@@ -621,6 +720,95 @@ impl AssemblyLanguageServerProtocol {
         Ok(signatures)
     }
 
+    pub fn syntax_diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = errors::syntax_errors(&self.parser);
+        diagnostics.extend(errors::cfi_errors(&self.parser));
+        diagnostics
+    }
+
+    /// The source files are read to check their MD5, so this is only done
+    /// when the file is opened or saved rather than on each change.
+    pub fn checksum_diagnostics(&self) -> Vec<Diagnostic> {
+        errors::debug_errors(&self.parser)
+    }
+
+    /// Groups the assembly by the source line that it was generated from,
+    /// each line is in the order that it first appears.
+    pub fn source_mapping(&self) -> Vec<SourceLineMapping> {
+        let position = self.parser.position();
+        let mut lines: HashMap<(Url, LineNumber), usize> = HashMap::new();
+        let mut mapping: Vec<SourceLineMapping> = Vec::new();
+
+        let ranges = self.parser.debug_map().source_ranges(&self.parser.tree());
+        for (location, range) in ranges {
+            let Some(range) = position
+                .get_position_for_size(&range.start())
+                .zip(position.get_position_for_size(&range.end()))
+            else {
+                continue;
+            };
+            let range = DocumentRange {
+                start: range.0,
+                end: range.1,
+            }
+            .into();
+
+            let line = location.range.start.line;
+            match lines.entry((location.uri.clone(), line)) {
+                Entry::Occupied(entry) => mapping[*entry.get()].ranges.push(range),
+                Entry::Vacant(entry) => {
+                    entry.insert(mapping.len());
+                    mapping.push(SourceLineMapping {
+                        uri: location.uri,
+                        line,
+                        ranges: vec![range],
+                    });
+                }
+            }
+        }
+
+        mapping
+    }
+
+    /// The assembly generated for a zero based `line` of the source file `uri`
+    pub fn assembly_for_source(&self, uri: &Url, line: LineNumber) -> Vec<Range> {
+        self.source_mapping()
+            .into_iter()
+            .filter(|m| m.uri == *uri && m.line == line)
+            .flat_map(|m| m.ranges)
+            .collect()
+    }
+
+    pub fn filtered_listing(&self, filters: &ListingFilters) -> FilteredListingResult {
+        let (text, lines) = listing::filter(&self.parser, filters);
+        FilteredListingResult { text, lines }
+    }
+
+    pub fn control_flow(&self) -> Vec<ControlFlowEdge> {
+        let position = self.parser.position();
+        let range = |range: TextRange| {
+            Some(
+                DocumentRange {
+                    start: position.get_position_for_size(&range.start())?,
+                    end: position.get_position_for_size(&range.end())?,
+                }
+                .into(),
+            )
+        };
+
+        control_flow::edges(&self.parser)
+            .into_iter()
+            .filter_map(|edge| {
+                Some(ControlFlowEdge {
+                    source: range(edge.source)?,
+                    target: range(edge.target)?,
+                    kind: edge.kind,
+                    conditional: edge.conditional,
+                })
+            })
+            .collect()
+    }
+
     pub fn syntax_tree(&self) -> Result<String, ResponseError> {
         Ok(format!("{:#?}", self.parser.tree()))
     }
@@ -764,32 +952,6 @@ impl AssemblyLanguageServerProtocol {
             change_annotations: None,
         }))
     }
-
-    fn apply_change(&self, contents: &mut String, change: DocumentChange) {
-        if let Some(range) = &change.range {
-            let range = {
-                let start_position = self
-                    .parser
-                    .position()
-                    .offset_for_line(range.start.line)
-                    .unwrap();
-                let start_offset = (start_position + range.start.column) as usize;
-
-                let end_position = self
-                    .parser
-                    .position()
-                    .offset_for_line(range.end.line)
-                    .unwrap();
-                let end_offset = (end_position + range.end.column) as usize;
-
-                start_offset..end_offset
-            };
-
-            contents.replace_range(range, &change.text);
-        } else {
-            *contents = change.text;
-        }
-    }
 }
 
 fn handle_includes(
@@ -828,15 +990,97 @@ fn get_format_options(workspace_root: &str) -> FormatOptions {
     }
 }
 
+/// The number after an AT&T `$` prefix, the prefix is highlighted as part
+/// of the number
+fn immediate_number(token: &SyntaxToken) -> Option<SyntaxToken> {
+    if token.kind() != SyntaxKind::IMMEDIATE || token.text() != "$" {
+        return None;
+    }
+
+    token
+        .next_token()
+        .filter(|next| matches!(next.kind(), SyntaxKind::NUMBER | SyntaxKind::FLOAT))
+}
+
+fn is_writeback_base(token: &SyntaxToken) -> bool {
+    token
+        .parent_ancestors()
+        .find_map(ast::MemoryOperand::cast)
+        .is_some_and(|memory| memory.is_writeback() && memory.base().as_ref() == Some(token))
+}
+
+fn symbol_for_node(
+    node: &SyntaxNode,
+    position: &PositionInfo,
+    symbols: &SymbolTable,
+) -> Option<DocumentSymbol> {
+    SectionNode::cast(node)
+        .and_then(|section| section.to_document_symbol(position, symbols))
+        .or_else(|| {
+            LabelNode::cast(node).and_then(|label| label.to_document_symbol(position, symbols))
+        })
+        .or_else(|| MacroNode::cast(node).and_then(|m| m.to_document_symbol(position)))
+}
+
+impl<'s> SectionNode<'s> {
+    fn to_document_symbol(
+        &self,
+        position: &PositionInfo,
+        symbols: &SymbolTable,
+    ) -> Option<DocumentSymbol> {
+        let node = self.syntax();
+        let directive = node.first_child()?;
+
+        Some(DocumentSymbol {
+            name: self.name()?,
+            detail: Some(String::from("section")),
+            kind: SymbolKind::NAMESPACE,
+            tags: None,
+            deprecated: None,
+            range: position.range_for_node(node)?.into(),
+            selection_range: position.range_for_node(&directive)?.into(),
+            children: Some(
+                node.descendants()
+                    .filter(|d| {
+                        matches!(
+                            d.kind(),
+                            SyntaxKind::MACRO_DEF | SyntaxKind::LABEL | SyntaxKind::SECTION
+                        )
+                    })
+                    // A pushed section has its own children
+                    .filter(|d| {
+                        d.ancestors()
+                            .skip(1)
+                            .find(|a| a.kind() == SyntaxKind::SECTION)
+                            .as_ref()
+                            == Some(node)
+                    })
+                    .filter_map(|d| symbol_for_node(&d, position, symbols))
+                    .collect(),
+            ),
+        })
+    }
+}
+
 impl<'s> LabelNode<'s> {
-    fn to_document_symbol(&self, position: &PositionInfo) -> Option<DocumentSymbol> {
+    fn to_document_symbol(
+        &self,
+        position: &PositionInfo,
+        symbols: &SymbolTable,
+    ) -> Option<DocumentSymbol> {
         let token = find_kind_index(self.syntax(), 1, SyntaxKind::LABEL)?.into_token()?;
         let node = self.syntax();
+        let symbol = symbols.get(token.text().trim_end_matches(':'));
 
         Some(DocumentSymbol {
             name: token.text().to_string(),
-            detail: None,
-            kind: SymbolKind::FUNCTION,
+            detail: symbol.map(|symbol| symbol.to_string()),
+            kind: match symbol.and_then(|symbol| symbol.kind) {
+                Some(SymbolType::Object | SymbolType::TlsObject | SymbolType::Common) => {
+                    SymbolKind::VARIABLE
+                }
+                _ => SymbolKind::FUNCTION,
+            },
             tags: None,
             deprecated: None,
             range: position.range_for_node(node).unwrap().into(),
@@ -867,6 +1111,24 @@ impl<'s> LocalLabelNode<'s> {
     }
 }
 
+impl<'s> MacroNode<'s> {
+    fn to_document_symbol(&self, position: &PositionInfo) -> Option<DocumentSymbol> {
+        let token = self.name()?;
+        let node = self.syntax();
+
+        Some(DocumentSymbol {
+            name: token.text().to_string(),
+            detail: Some(String::from("macro")),
+            kind: SymbolKind::FUNCTION,
+            tags: None,
+            deprecated: None,
+            range: position.range_for_node(node)?.into(),
+            selection_range: position.range_for_token(&token)?.into(),
+            children: None,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use base::Architecture;
@@ -876,6 +1138,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use crate::config::LSPConfig;
+    use crate::handler::ext::ControlFlowKind;
     use crate::types::DocumentRange;
 
     use super::*;
@@ -1067,6 +1330,118 @@ entry:
         assert_eq!(expected, response);
     }
 
+    const REBOUND_SOURCE: &str = r#"first:
+    width .req x0
+    mov width, #1
+    .unreq width
+second:
+    width .req x1
+    mov width, #2
+    .set SIZE, 16
+    mov x2, SIZE
+    .set SIZE, SIZE + 4
+    mov x2, SIZE
+// lsp-asm-architecture: AArch64"#;
+
+    #[test]
+    fn test_hover_rebound_names() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            REBOUND_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let hover = |line, column| {
+            get_response!(ctx, hover, DocumentPosition { line, column }).map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        };
+
+        assert_eq!(
+            Some(String::from("`width` is an alias to register `x0`")),
+            hover(2, 10)
+        );
+        assert_eq!(
+            Some(String::from("`width` is an alias to register `x1`")),
+            hover(6, 10)
+        );
+        assert_eq!(
+            Some(String::from(
+                "`SIZE` is defined as `16`  \nDecimal: 16  \nHex: 0x10"
+            )),
+            hover(8, 14)
+        );
+        assert_eq!(
+            Some(String::from(
+                "`SIZE` is defined as `SIZE + 4`  \nDecimal: 20  \nHex: 0x14"
+            )),
+            hover(10, 14)
+        );
+    }
+
+    #[test]
+    fn test_find_references_rebound_alias() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            REBOUND_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let references = |line, column| {
+            get_response!(
+                ctx,
+                find_references,
+                DocumentPosition { line, column },
+                false
+            )
+            .into_iter()
+            .map(|l| l.range.start.line)
+            .collect_vec()
+        };
+
+        assert_eq!(vec![2], references(2, 10));
+        assert_eq!(vec![6], references(6, 10));
+        assert_eq!(vec![10], references(10, 14));
+    }
+
+    #[test]
+    fn test_goto_definition_rebound() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            REBOUND_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let definition = |line, column| match get_response!(
+            ctx,
+            goto_definition,
+            DocumentPosition { line, column }
+        ) {
+            GotoDefinitionResponse::Array(locations) => {
+                locations.into_iter().map(|l| l.range.start).collect_vec()
+            }
+            _ => Vec::new(),
+        };
+
+        assert_eq!(vec![Position::new(1, 4)], definition(2, 10));
+        assert_eq!(vec![Position::new(5, 4)], definition(6, 10));
+        assert_eq!(vec![Position::new(7, 9)], definition(8, 14));
+        assert_eq!(vec![Position::new(9, 9)], definition(10, 14));
+    }
+
     #[test]
     fn test_find_references_numeric() {
         let ctx: Arc<Context> = Default::default();
@@ -1134,6 +1509,107 @@ end:
         assert_eq!(expected, response);
     }
 
+    #[test]
+    fn test_find_references_x86_64_sub_register() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            r#"    movl %r8d, %eax
+    addq %r8, %rbx
+    movb %sil, %r8b
+# lsp-asm-architecture: x86-64"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let expected = vec![
+            Location {
+                uri: Url::parse("file://temp").unwrap(),
+                range: Range {
+                    start: Position::new(0, 9),
+                    end: Position::new(0, 13),
+                },
+            },
+            Location {
+                uri: Url::parse("file://temp").unwrap(),
+                range: Range {
+                    start: Position::new(1, 9),
+                    end: Position::new(1, 12),
+                },
+            },
+            Location {
+                uri: Url::parse("file://temp").unwrap(),
+                range: Range {
+                    start: Position::new(2, 15),
+                    end: Position::new(2, 19),
+                },
+            },
+        ];
+
+        let response = get_response!(
+            ctx,
+            find_references,
+            DocumentPosition {
+                line: 1,
+                column: 10
+            },
+            false
+        );
+
+        assert_eq!(expected, response);
+    }
+
+    #[test]
+    fn test_find_references_powerpc64_bare_register() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            r#"    lfd 0,8(9)
+    addi 0,9,1
+    fadd 1,0,0
+# lsp-asm-architecture: ppc64le"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let expected = vec![
+            Location {
+                uri: Url::parse("file://temp").unwrap(),
+                range: Range {
+                    start: Position::new(0, 8),
+                    end: Position::new(0, 9),
+                },
+            },
+            Location {
+                uri: Url::parse("file://temp").unwrap(),
+                range: Range {
+                    start: Position::new(2, 11),
+                    end: Position::new(2, 12),
+                },
+            },
+            Location {
+                uri: Url::parse("file://temp").unwrap(),
+                range: Range {
+                    start: Position::new(2, 13),
+                    end: Position::new(2, 14),
+                },
+            },
+        ];
+
+        let response = get_response!(
+            ctx,
+            find_references,
+            DocumentPosition { line: 0, column: 8 },
+            false
+        );
+
+        assert_eq!(expected, response);
+    }
+
     #[test]
     fn test_document_symbols() {
         let ctx: Arc<Context> = Default::default();
@@ -1248,29 +1724,579 @@ end:
         assert_eq!(expected, response);
     }
 
+    const MACRO_SOURCE: &str = r#".macro push_pair a, b, offset=16
+    stp \a, \b, [sp, -\offset]!
+.endm
+entry:
+    push_pair x19, x20
+    push_pair x21, x22, 32
+// lsp-asm-architecture: AArch64"#;
+
     #[test]
-    fn test_document_highlight_label() {
+    fn test_goto_definition_macro() {
         let ctx: Arc<Context> = Default::default();
 
         let actor = AssemblyLanguageServerProtocol::new(
             ctx.clone(),
-            r#"entry:
-    b entry
-// lsp-asm-architecture: AArch64"#,
+            MACRO_SOURCE,
             Url::parse("file://temp").unwrap(),
             0,
         );
         setup_actor(ctx.clone(), actor);
 
-        let expected = vec![
-            DocumentHighlight {
-                range: Range {
-                    start: Position::new(0, 0),
-                    end: Position::new(0, 6),
-                },
-                kind: Some(DocumentHighlightKind::TEXT),
-            },
-            DocumentHighlight {
+        let expected = GotoDefinitionResponse::Array(vec![Location {
+            uri: Url::parse("file://temp").unwrap(),
+            range: Range {
+                start: Position::new(0, 7),
+                end: Position::new(0, 16),
+            },
+        }]);
+
+        let response = get_response!(
+            ctx,
+            goto_definition,
+            DocumentPosition { line: 4, column: 6 }
+        );
+
+        assert_eq!(expected, response);
+    }
+
+    #[test]
+    fn test_find_references_macro() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            MACRO_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let expected = vec![
+            Location {
+                uri: Url::parse("file://temp").unwrap(),
+                range: Range {
+                    start: Position::new(4, 4),
+                    end: Position::new(4, 13),
+                },
+            },
+            Location {
+                uri: Url::parse("file://temp").unwrap(),
+                range: Range {
+                    start: Position::new(5, 4),
+                    end: Position::new(5, 13),
+                },
+            },
+        ];
+
+        let response = get_response!(
+            ctx,
+            find_references,
+            DocumentPosition { line: 0, column: 8 },
+            false
+        );
+
+        assert_eq!(expected, response);
+    }
+
+    #[test]
+    fn test_hover_macro_expansion() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            MACRO_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let expected = Some(lsp_types::Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: String::from(
+                    "# Macro Expansion  \n```asm\n    stp x19, x20, [sp, -16]!\n```",
+                ),
+            }),
+            range: None,
+        });
+
+        let response = get_response!(ctx, hover, DocumentPosition { line: 4, column: 6 });
+        assert_eq!(expected, response);
+
+        let response = get_response!(ctx, hover, DocumentPosition { line: 5, column: 6 });
+        assert_eq!(
+            Some(String::from(
+                "# Macro Expansion  \n```asm\n    stp x21, x22, [sp, -32]!\n```"
+            )),
+            response.map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_hover_nasm_macro_expansion() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            r#"%macro save 2
+    push %1
+    push %2
+%endmacro
+    save rbx, rbp"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let response = get_response!(ctx, hover, DocumentPosition { line: 4, column: 6 });
+        assert_eq!(
+            Some(String::from(
+                "# Macro Expansion  \n```asm\n    push rbx\n    push rbp\n```"
+            )),
+            response.map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_document_symbols_macro() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            MACRO_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let response = get_response!(ctx, document_symbols,);
+        let DocumentSymbolResponse::Nested(symbols) = response else {
+            panic!("Expected nested symbols");
+        };
+
+        assert_eq!(
+            vec![("push_pair", Some("macro")), ("entry:", None)],
+            symbols
+                .iter()
+                .map(|s| (s.name.as_str(), s.detail.as_deref()))
+                .collect_vec()
+        );
+        assert_eq!(
+            Range {
+                start: Position::new(0, 7),
+                end: Position::new(0, 16),
+            },
+            symbols[0].selection_range
+        );
+    }
+
+    const SECTION_SOURCE: &str = r#"    .text
+main:
+    adrp x0, message
+.L2:
+    ret
+    .pushsection .rodata
+message:
+    .string "hi"
+    .popsection
+helper:
+    ret
+// lsp-asm-architecture: AArch64"#;
+
+    #[test]
+    fn test_document_symbols_sections() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            SECTION_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let response = get_response!(ctx, document_symbols,);
+        let DocumentSymbolResponse::Nested(symbols) = response else {
+            panic!("Expected nested symbols");
+        };
+
+        let names = |symbols: &[DocumentSymbol]| {
+            symbols
+                .iter()
+                .map(|s| {
+                    (
+                        s.name.clone(),
+                        s.children
+                            .iter()
+                            .flatten()
+                            .map(|c| c.name.clone())
+                            .collect_vec(),
+                    )
+                })
+                .collect_vec()
+        };
+
+        // The `.text` section carries on after the pushed section
+        assert_eq!(
+            vec![(
+                String::from(".text"),
+                vec![
+                    String::from("main:"),
+                    String::from(".rodata"),
+                    String::from("helper:")
+                ]
+            )],
+            names(&symbols)
+        );
+        assert_eq!(
+            vec![
+                (String::from("main:"), vec![String::from(".L2:")]),
+                (String::from(".rodata"), vec![String::from("message:")]),
+                (String::from("helper:"), vec![]),
+            ],
+            names(symbols[0].children.as_ref().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_hover_label_section() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            SECTION_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let hover = |line, column| {
+            get_response!(ctx, hover, DocumentPosition { line, column }).map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        };
+
+        assert_eq!(Some(String::from("**Section**: `.text`")), hover(1, 1));
+        assert_eq!(Some(String::from("**Section**: `.rodata`")), hover(2, 14));
+        assert_eq!(Some(String::from("**Section**: `.text`")), hover(9, 1));
+    }
+
+    const SYMBOL_SOURCE: &str = r#"    .text
+    .globl main
+    .hidden main
+    .type main, @function
+main:
+    nop
+    ret
+.Lfunc_end0:
+    .size main, .Lfunc_end0-main
+    .data
+    .weak counter
+    .globl counter
+    .type counter, %object
+counter:
+    .quad 0
+    .size counter, 8
+// lsp-asm-architecture: AArch64"#;
+
+    #[test]
+    fn test_document_symbols_linkage() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            SYMBOL_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let response = get_response!(ctx, document_symbols,);
+        let DocumentSymbolResponse::Nested(symbols) = response else {
+            panic!("Expected nested symbols");
+        };
+
+        assert_eq!(
+            vec![
+                (
+                    String::from("main:"),
+                    SymbolKind::FUNCTION,
+                    Some(String::from("global hidden function"))
+                ),
+                (
+                    String::from("counter:"),
+                    SymbolKind::VARIABLE,
+                    Some(String::from("weak object"))
+                ),
+            ],
+            symbols
+                .iter()
+                .flat_map(|s| s.children.iter().flatten())
+                .map(|s| (s.name.clone(), s.kind, s.detail.clone()))
+                .collect_vec()
+        );
+    }
+
+    #[test]
+    fn test_hover_label_symbol() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            SYMBOL_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let hover = |line, column| {
+            get_response!(ctx, hover, DocumentPosition { line, column }).map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        };
+
+        assert_eq!(
+            Some(String::from(
+                "**Section**: `.text`  \n**Binding**: `global`  \n**Visibility**: `hidden`  \n**Type**: `function`  \n**Size**: `8` bytes"
+            )),
+            hover(4, 1)
+        );
+        assert_eq!(
+            Some(String::from(
+                "**Section**: `.data`  \n**Binding**: `weak`  \n**Visibility**: `default`  \n**Type**: `object`  \n**Size**: `8` bytes"
+            )),
+            hover(13, 1)
+        );
+    }
+
+    #[test]
+    fn test_goto_definition_globl() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            SYMBOL_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let expected = GotoDefinitionResponse::Array(vec![Location {
+            uri: Url::parse("file://temp").unwrap(),
+            range: Range {
+                start: Position::new(4, 0),
+                end: Position::new(4, 5),
+            },
+        }]);
+
+        let response = get_response!(
+            ctx,
+            goto_definition,
+            DocumentPosition {
+                line: 1,
+                column: 11
+            }
+        );
+
+        assert_eq!(expected, response);
+    }
+
+    const EXPRESSION_SOURCE: &str = r#"    .equ HEADER_SIZE, 16
+    .equ ENTRY_OFFSET, HEADER_SIZE + 8
+    .equ ENTRY_END, (ENTRY_OFFSET + 4) << 1
+    .section .rodata
+message:
+    .ascii "hello"
+    .byte 0
+    .set MESSAGE_SIZE, . - message
+    .text
+    ldr x0, [x1, #(ENTRY_OFFSET * 2)]
+    mov x0, MESSAGE_SIZE
+    .equ BACKWARDS, -10
+// lsp-asm-architecture: AArch64"#;
+
+    #[test]
+    fn test_hover_constant_value() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            EXPRESSION_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let hover = |line, column| {
+            get_response!(ctx, hover, DocumentPosition { line, column }).map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        };
+
+        assert_eq!(
+            Some(String::from(
+                "`ENTRY_END` is defined as `(ENTRY_OFFSET + 4) << 1`  \nDecimal: 56  \nHex: 0x38"
+            )),
+            hover(2, 10)
+        );
+        assert_eq!(
+            Some(String::from(
+                "`MESSAGE_SIZE` is defined as `. - message`  \nDecimal: 6  \nHex: 0x6"
+            )),
+            hover(10, 12)
+        );
+        assert_eq!(
+            Some(String::from(
+                "`BACKWARDS` is defined as `-10`  \nDecimal: -10  \nHex: -0xA"
+            )),
+            hover(11, 10)
+        );
+    }
+
+    #[test]
+    fn test_hover_expression_value() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            EXPRESSION_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let hover = |line, column| {
+            get_response!(ctx, hover, DocumentPosition { line, column }).map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        };
+
+        let expected = Some(String::from("# Expression  \nDecimal: 48  \nHex: 0x30"));
+        assert_eq!(expected, hover(9, 18));
+        assert_eq!(expected, hover(9, 32));
+        assert_eq!(
+            Some(String::from(
+                "# Memory operand  \nAddress: `x1 + (ENTRY_OFFSET * 2)`"
+            )),
+            hover(9, 12)
+        );
+    }
+
+    #[test]
+    fn test_hover_memory_operand() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            r#"    ldr x2, [x0, x1, lsl #3]!
+    ldr x2, [x0], #16
+    ldr w2, [x0, w1, sxtw #2]
+    ldr x2, [x0, #-8]
+// lsp-asm-architecture: AArch64"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let hover = |line, column| {
+            get_response!(ctx, hover, DocumentPosition { line, column }).map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        };
+
+        assert_eq!(
+            Some(String::from(
+                "# Memory operand  \nAddress: `x0 + (x1 << 3)`  \n`x0` is updated with the address before the access"
+            )),
+            hover(0, 14)
+        );
+        assert_eq!(
+            Some(String::from(
+                "# Memory operand  \nAddress: `x0`  \n`x0` is incremented by `16` after the access"
+            )),
+            hover(1, 14)
+        );
+        assert_eq!(
+            Some(String::from(
+                "# Memory operand  \nAddress: `x0 + (sxtw(w1) << 2)`"
+            )),
+            hover(2, 18)
+        );
+        assert_eq!(
+            Some(String::from("# Memory operand  \nAddress: `x0 - 8`")),
+            hover(3, 13)
+        );
+    }
+
+    #[test]
+    fn test_hover_memory_operand_x86() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            r#"    movq 8(%rax,%rbx,4), %rcx
+    movq -16(%rbp), %rcx
+// lsp-asm-architecture: x86-64"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let hover = |line, column| {
+            get_response!(ctx, hover, DocumentPosition { line, column }).map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        };
+
+        assert_eq!(
+            Some(String::from(
+                "# Memory operand  \nAddress: `%rax + %rbx * 4 + 8`"
+            )),
+            hover(0, 12)
+        );
+        assert_eq!(
+            Some(String::from("# Memory operand  \nAddress: `%rbp - 16`")),
+            hover(1, 14)
+        );
+    }
+
+    #[test]
+    fn test_document_highlight_label() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            r#"entry:
+    b entry
+// lsp-asm-architecture: AArch64"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let expected = vec![
+            DocumentHighlight {
+                range: Range {
+                    start: Position::new(0, 0),
+                    end: Position::new(0, 6),
+                },
+                kind: Some(DocumentHighlightKind::TEXT),
+            },
+            DocumentHighlight {
                 range: Range {
                     start: Position::new(1, 6),
                     end: Position::new(1, 11),
@@ -1282,12 +2308,164 @@ end:
         let response = get_response!(
             ctx,
             document_highlight,
-            DocumentPosition { line: 1, column: 8 }
+            DocumentPosition { line: 1, column: 8 }
+        );
+
+        assert_eq!(expected, response);
+    }
+
+    #[test]
+    fn test_document_highlight_writeback() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            r#"    ldr x1, [x0], #16
+    ldr x1, [x0]
+    ld1 {v0.16b}, [x0], x1
+    ldr x1, [x0, #8]!
+// lsp-asm-architecture: AArch64"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let response = get_response!(
+            ctx,
+            document_highlight,
+            DocumentPosition {
+                line: 0,
+                column: 14
+            }
+        );
+
+        let kinds = |line, column| {
+            let range = Range {
+                start: Position::new(line, column),
+                end: Position::new(line, column + 2),
+            };
+            response
+                .iter()
+                .filter(|h| h.range == range)
+                .map(|h| h.kind)
+                .collect_vec()
+        };
+        let read_write = vec![
+            Some(DocumentHighlightKind::READ),
+            Some(DocumentHighlightKind::WRITE),
+        ];
+        assert_eq!(read_write, kinds(0, 13));
+        assert_eq!(read_write, kinds(2, 19));
+        assert_eq!(read_write, kinds(3, 13));
+
+        let read = Range {
+            start: Position::new(1, 13),
+            end: Position::new(1, 15),
+        };
+        assert!(response
+            .iter()
+            .filter(|h| h.range == read)
+            .all(|h| h.kind != Some(DocumentHighlightKind::WRITE)));
+    }
+
+    const CONDITIONAL_SOURCE: &str = r#".equ VARIANT, 2
+.ifeq VARIANT - 2
+target:
+    nop
+.else
+target:
+    ret
+.endif
+    b target
+// lsp-asm-architecture: AArch64"#;
+
+    #[test]
+    fn test_goto_definition_conditional() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            CONDITIONAL_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let expected = GotoDefinitionResponse::Array(vec![Location {
+            uri: Url::parse("file://temp").unwrap(),
+            range: Range {
+                start: Position::new(2, 0),
+                end: Position::new(2, 7),
+            },
+        }]);
+
+        let response = get_response!(
+            ctx,
+            goto_definition,
+            DocumentPosition { line: 8, column: 7 }
         );
 
         assert_eq!(expected, response);
     }
 
+    #[test]
+    fn test_semantic_inactive_conditional() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            CONDITIONAL_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        assert_eq!(inactive_lines(ctx), vec![5, 6]);
+    }
+
+    #[test]
+    fn test_semantic_inactive_preprocessor() {
+        let ctx: Arc<Context> = Default::default();
+
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            r#"#define FAST 1
+#if defined(FAST) && !defined(DEBUG)
+    nop
+#elif 0
+    ret
+#endif
+#ifdef DEBUG
+    ret
+#endif
+// lsp-asm-architecture: AArch64"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        assert_eq!(inactive_lines(ctx), vec![4, 7]);
+    }
+
+    /// Gets the lines of any semantic tokens that are marked as inactive
+    fn inactive_lines(ctx: Arc<Context>) -> Vec<u32> {
+        let response: SemanticTokensResult = get_response!(ctx, get_semantic_tokens, None);
+        let SemanticTokensResult::Tokens(tokens) = response else {
+            panic!("Expected semantic tokens");
+        };
+
+        let mut line = 0;
+        tokens
+            .data
+            .iter()
+            .filter_map(|t| {
+                line += t.delta_line;
+                (t.token_modifiers_bitset == crate::handler::semantic::INACTIVE_MODIFIER)
+                    .then_some(line)
+            })
+            .collect_vec()
+    }
+
     #[test]
     fn test_document_semantic() {
         let ctx: Arc<Context> = Default::default();
@@ -1441,4 +2619,518 @@ end:
             )
             .is_ok());
     }
+
+    #[test]
+    fn test_syntax_diagnostics() {
+        let ctx: Arc<Context> = Default::default();
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx,
+            r#"// lsp-asm-architecture: AArch64
+    ldr x0, [sp, #8
+    add x0, x0, #1]
+    .ascii "unterminated
+    mov x1, x2
+"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+
+        let diagnostic = |start: (u32, u32), end: (u32, u32), message: &str| Diagnostic {
+            range: Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1)),
+            severity: Some(lsp_types::DiagnosticSeverity::ERROR),
+            source: Some(String::from("lsp-asm")),
+            message: String::from(message),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            actor.syntax_diagnostics(),
+            vec![
+                diagnostic((1, 12), (1, 13), "unclosed `[`"),
+                diagnostic((2, 18), (2, 19), "unexpected `]`"),
+                diagnostic((3, 11), (3, 24), "unterminated string"),
+            ]
+        );
+    }
+
+    const CFI_SOURCE: &str = r#"// lsp-asm-architecture: AArch64
+foo:
+    .cfi_startproc
+    stp x29, x30, [sp, #-16]!
+    .cfi_def_cfa_offset 16
+    .cfi_offset w30, -8
+    .cfi_offset w29, -16
+    mov x29, sp
+    .cfi_def_cfa_register x29
+    .cfi_remember_state
+    .cfi_restore w19
+    ldp x29, x30, [sp], #16
+    .cfi_def_cfa sp, 0
+    .cfi_restore w30
+    .cfi_restore_state
+    .cfi_restore_state
+    ret
+    .cfi_endproc
+    .cfi_offset w29, -16
+bar:
+    .cfi_startproc
+    ret
+"#;
+
+    #[test]
+    fn test_hover_cfi() {
+        let ctx: Arc<Context> = Default::default();
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            CFI_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let hover = |line, column| {
+            get_response!(ctx, hover, DocumentPosition { line, column }).map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        };
+
+        assert_eq!(
+            Some(String::from(
+                "# Call frame information  \n**CFA**: `x29 + 16`  \n* `w30`: `[CFA - 8]`  \n* `w29`: `[CFA - 16]`"
+            )),
+            hover(8, 8)
+        );
+        assert_eq!(
+            Some(String::from(
+                "# Call frame information  \n**CFA**: `sp`  \n* `w29`: `[CFA - 16]`"
+            )),
+            hover(13, 8)
+        );
+        assert_eq!(
+            Some(String::from(
+                "# Call frame information  \n**CFA**: `x29 + 16`  \n* `w30`: `[CFA - 8]`  \n* `w29`: `[CFA - 16]`"
+            )),
+            hover(14, 8)
+        );
+    }
+
+    #[test]
+    fn test_inlay_hint_cfi() {
+        let ctx: Arc<Context> = Default::default();
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx,
+            CFI_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+
+        let hints = actor
+            .inlay_hint(Default::default(), None)
+            .unwrap()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|hint| {
+                let lsp_types::InlayHintLabel::String(label) = hint.label else {
+                    panic!("unexpected label");
+                };
+                (hint.position.line, hint.position.character, label)
+            })
+            .collect_vec();
+
+        assert_eq!(
+            hints,
+            vec![
+                (2, 18, String::from("CFA = sp")),
+                (4, 26, String::from("CFA = sp + 16")),
+                (8, 29, String::from("CFA = x29 + 16")),
+                (12, 22, String::from("CFA = sp")),
+                (14, 22, String::from("CFA = x29 + 16")),
+                (20, 18, String::from("CFA = sp")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cfi_diagnostics() {
+        let ctx: Arc<Context> = Default::default();
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx,
+            CFI_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+
+        let diagnostics = actor
+            .syntax_diagnostics()
+            .into_iter()
+            .map(|d| (d.range.start.line, d.severity.unwrap(), d.message))
+            .collect_vec();
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    10,
+                    lsp_types::DiagnosticSeverity::WARNING,
+                    String::from("`.cfi_restore` of `w19` which hasn't been saved")
+                ),
+                (
+                    15,
+                    lsp_types::DiagnosticSeverity::ERROR,
+                    String::from("`.cfi_restore_state` without a `.cfi_remember_state`")
+                ),
+                (
+                    18,
+                    lsp_types::DiagnosticSeverity::ERROR,
+                    String::from(
+                        "`.cfi_offset` used outside of a `.cfi_startproc` and `.cfi_endproc`"
+                    )
+                ),
+                (
+                    20,
+                    lsp_types::DiagnosticSeverity::ERROR,
+                    String::from("`.cfi_startproc` is missing a `.cfi_endproc`")
+                ),
+            ]
+        );
+    }
+
+    const LOC_SOURCE: &str = r#"// lsp-asm-architecture: AArch64
+	.file	0 "/src" "main.c"
+	.file	1 "util.h"
+square:
+	.loc	0 3 0
+	mul	w0, w0, w0
+	.loc	1 8 4
+	add	w0, w0, #1
+	sub	w0, w0, #2
+	.loc	0 0 0
+	nop
+	.loc	0 3 12
+	ret
+"#;
+
+    #[test]
+    fn test_source_mapping() {
+        let ctx: Arc<Context> = Default::default();
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx,
+            LOC_SOURCE,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+
+        let range = |start: (u32, u32), end: (u32, u32)| {
+            Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+        };
+
+        assert_eq!(
+            actor.source_mapping(),
+            vec![
+                SourceLineMapping {
+                    uri: Url::parse("file:///src/main.c").unwrap(),
+                    line: 2,
+                    ranges: vec![range((5, 1), (5, 15)), range((12, 1), (12, 4))],
+                },
+                SourceLineMapping {
+                    uri: Url::parse("file:///src/util.h").unwrap(),
+                    line: 7,
+                    ranges: vec![range((7, 1), (8, 15))],
+                },
+            ]
+        );
+
+        assert_eq!(
+            actor.assembly_for_source(&Url::parse("file:///src/util.h").unwrap(), 7),
+            vec![range((7, 1), (8, 15))]
+        );
+        assert!(actor
+            .assembly_for_source(&Url::parse("file:///src/main.c").unwrap(), 7)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_filtered_listing() {
+        let ctx: Arc<Context> = Default::default();
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx,
+            r#"	.text
+	.file	"main.c"
+	.file	0 "/src" "main.c"
+	.file	1 "/usr/include" "inline.h"
+	.globl	square // -- Begin function square
+	.type	square,@function
+square:
+	.loc	0 3 0
+.Ltmp0:
+	mul	w0, w0, w0 // square it
+	cbz	w0, .LBB0_2
+.LBB0_1:
+	adrp	x1, .L.str
+.LBB0_2:
+	ret
+.Lfunc_end0:
+	.size	square, .Lfunc_end0-square
+helper:
+	.loc	1 8 0
+	ret
+	.section	.rodata
+.L.str:
+	.asciz	"hi"
+	.section	.debug_abbrev,"",@progbits
+	.byte	1
+// lsp-asm-architecture: AArch64
+"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+
+        assert_eq!(
+            actor.filtered_listing(&Default::default()),
+            FilteredListingResult {
+                text: String::from(
+                    "square:\n\tmul\tw0, w0, w0\n\tcbz\tw0, .LBB0_2\n\tadrp\tx1, .L.str\n.LBB0_2:\n\tret\n.L.str:\n\t.asciz\t\"hi\"\n"
+                ),
+                lines: vec![6, 9, 10, 12, 13, 14, 21, 22],
+            }
+        );
+
+        let filters = ListingFilters {
+            directives: false,
+            comments: false,
+            library_functions: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            actor.filtered_listing(&filters).lines,
+            vec![0, 1, 2, 3, 4, 5, 6, 7, 9, 10, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22]
+        );
+    }
+
+    #[test]
+    fn test_objdump_source_location() {
+        let path = std::env::temp_dir().join("lsp-asm-objdump-source.c");
+        std::fs::write(
+            &path,
+            "#include <stdio.h>\n\nint main() {\n    return 0;\n}\n",
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+
+        let ctx: Arc<Context> = Default::default();
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            &format!(
+                r#"
+a.out:     file format elf64-x86-64
+
+
+Disassembly of section .text:
+
+0000000000001139 <main>:
+main():
+{path}:3
+int main() {{
+    1139:	55                   	push   %rbp
+{path}:4 (discriminator 1)
+    return 0;
+    113a:	b8 00 00 00 00       	mov    $0x0,%eax
+"#
+            ),
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+
+        let titles = actor
+            .code_lens(ctx.clone())
+            .unwrap()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|lens| (lens.range.start.line, lens.command.unwrap().title))
+            .collect_vec();
+        assert_eq!(
+            titles,
+            vec![
+                (8, String::from("int main() {")),
+                (11, String::from("    return 0;"))
+            ]
+        );
+
+        setup_actor(ctx.clone(), actor);
+        let expected = GotoDefinitionResponse::Array(vec![Location {
+            uri: Url::parse(&format!("file://{path}")).unwrap(),
+            range: Range {
+                start: Position::new(3, 0),
+                end: Position::new(3, 0),
+            },
+        }]);
+        let response = get_response!(
+            ctx,
+            goto_definition,
+            DocumentPosition {
+                line: 11,
+                column: 2
+            }
+        );
+        assert_eq!(expected, response);
+    }
+
+    #[test]
+    fn test_objdump_relocations() {
+        let ctx: Arc<Context> = Default::default();
+        let actor = AssemblyLanguageServerProtocol::new(
+            ctx.clone(),
+            r#"
+test.o:     file format elf64-x86-64
+
+
+Disassembly of section .text:
+
+0000000000000000 <foo>:
+   0:	c3                   	ret
+
+0000000000000001 <main>:
+   1:	e8 00 00 00 00       	call   6 <main+0x5>
+			2: R_X86_64_PLT32	foo-0x4
+   6:	c3                   	ret
+			7: R_AARCH64_UNKNOWN	bar
+"#,
+            Url::parse("file://temp").unwrap(),
+            0,
+        );
+        setup_actor(ctx.clone(), actor);
+
+        let hover = |line, column| {
+            get_response!(ctx, hover, DocumentPosition { line, column }).map(|h| match h.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => String::new(),
+            })
+        };
+
+        assert_eq!(
+            Some(String::from(
+                "# x86-64 relocation  \n`R_X86_64_PLT32`: The 32-bit offset from here to the symbol's PLT entry, or to the symbol when it is local  \n**Target**: `foo-0x4`"
+            )),
+            hover(11, 8)
+        );
+        assert_eq!(
+            Some(String::from(
+                "# AArch64 relocation  \n`R_AARCH64_UNKNOWN`  \n**Target**: `bar`"
+            )),
+            hover(13, 8)
+        );
+
+        let expected = GotoDefinitionResponse::Array(vec![Location {
+            uri: Url::parse("file://temp").unwrap(),
+            range: Range {
+                start: Position::new(6, 17),
+                end: Position::new(6, 23),
+            },
+        }]);
+        let response = get_response!(
+            ctx,
+            goto_definition,
+            DocumentPosition {
+                line: 11,
+                column: 22
+            }
+        );
+        assert_eq!(expected, response);
+    }
+
+    #[test]
+    fn test_control_flow() {
+        let edges = |source| {
+            let ctx: Arc<Context> = Default::default();
+            let actor = AssemblyLanguageServerProtocol::new(
+                ctx,
+                source,
+                Url::parse("file://temp").unwrap(),
+                0,
+            );
+            actor
+                .control_flow()
+                .into_iter()
+                .map(|edge| (edge.source, edge.target, edge.kind, edge.conditional))
+                .collect_vec()
+        };
+        let range = |line, start, end| Range {
+            start: Position::new(line, start),
+            end: Position::new(line, end),
+        };
+
+        assert_eq!(
+            edges(
+                r#"
+main:
+.L2:
+    cmpl $9, %eax
+    jle .L3
+    call foo
+    jmp .L2
+.L3:
+    ret
+foo:
+    ret
+"#
+            ),
+            vec![
+                (range(4, 4, 11), range(7, 0, 4), ControlFlowKind::Jump, true),
+                (
+                    range(5, 4, 12),
+                    range(9, 0, 4),
+                    ControlFlowKind::Call,
+                    false
+                ),
+                (
+                    range(6, 4, 11),
+                    range(2, 0, 4),
+                    ControlFlowKind::Jump,
+                    false
+                ),
+            ]
+        );
+
+        assert_eq!(
+            edges(
+                r#"
+test.o:     file format elf64-x86-64
+
+
+Disassembly of section .text:
+
+0000000000000000 <main>:
+   0:	74 02                	je     4 <main+0x4>
+   2:	eb fc                	jmp    0 <main>
+   4:	e8 01 00 00 00       	call   a <foo>
+			5: R_X86_64_PLT32	foo-0x4
+   9:	c3                   	ret
+
+000000000000000a <foo>:
+   a:	c3                   	ret
+"#
+            ),
+            vec![
+                (
+                    range(7, 3, 47),
+                    range(9, 3, 42),
+                    ControlFlowKind::Jump,
+                    true
+                ),
+                (
+                    range(8, 3, 43),
+                    range(6, 17, 24),
+                    ControlFlowKind::Jump,
+                    false
+                ),
+                (
+                    range(9, 3, 42),
+                    range(13, 17, 23),
+                    ControlFlowKind::Call,
+                    false
+                ),
+            ]
+        );
+    }
 }
@@ -1,8 +1,12 @@
 use crate::handler::context::Context;
 
-use super::ast::{LabelToken, NumericToken};
+use super::ast::{
+    macro_call_arguments, section_for_token, AstNode, AstToken, LabelToken, MacroNode, NumericToken,
+};
 use super::definition::get_definition_token;
+use super::evaluate::{expression_text, Evaluator};
 use super::parser::Parser;
+use super::relocations;
 use arch::registers::registers_for_architecture;
 use base::Architecture;
 use itertools::Itertools;
@@ -10,18 +14,162 @@ use rowan::NodeOrToken;
 use std::iter;
 use std::sync::Arc;
 use syntax::alias::Alias;
-use syntax::ast::{self, SyntaxKind, SyntaxToken};
+use syntax::ast::{self, AstNode as _, SyntaxKind, SyntaxToken};
+
+/// Negative values are shown with a sign rather than as two's complement,
+/// as the width of the operand isn't known.
+fn hex(value: i128) -> String {
+    if value < 0 {
+        format!("-{:#X}", value.unsigned_abs())
+    } else {
+        format!("{value:#X}")
+    }
+}
 
 pub fn get_numeric_hover(value: &NumericToken) -> Option<Vec<String>> {
     let value = value.value();
     Some(vec![
         "# Number".to_string(),
         format!("Decimal: {value}"),
-        format!("Hex: {value:#X}"),
+        format!("Hex: {}", hex(value)),
     ])
 }
 
-pub fn get_label_hover(label: &LabelToken) -> Option<Vec<String>> {
+/// Shows the value of an expression within brackets e.g. `[x0, #(SIZE * 2)]`,
+/// the innermost brackets that can be evaluated are used.
+pub fn get_expression_hover(parser: &Parser, token: &SyntaxToken) -> Option<Vec<String>> {
+    let evaluator = Evaluator::new(parser);
+    let value = token
+        .parent_ancestors()
+        .take_while(|a| a.kind() == SyntaxKind::BRACKETS)
+        .find_map(|brackets| {
+            evaluator.evaluate(
+                &expression_text(&brackets),
+                Some(brackets.text_range().start()),
+            )
+        })?;
+
+    Some(vec![
+        "# Expression".to_string(),
+        format!("Decimal: {value}"),
+        format!("Hex: {}", hex(value)),
+    ])
+}
+
+/// Explains how the address of a memory operand is calculated e.g.
+/// `x0 + (x1 << 3)` for `[x0, x1, lsl #3]`
+pub fn get_memory_hover(token: &SyntaxToken) -> Option<Vec<String>> {
+    let memory = token
+        .parent_ancestors()
+        .find_map(ast::MemoryOperand::cast)?;
+    let base = memory.base();
+
+    let mut address = base.iter().map(|b| b.text().to_string()).collect_vec();
+    if let Some(index) = memory.index() {
+        let index = index.text();
+        address.push(match (memory.scale(), memory.extend()) {
+            (Some(scale), _) => format!("{index} * {scale}"),
+            (None, Some(extend)) => {
+                let (op, amount) = extend
+                    .split_once(char::is_whitespace)
+                    .map(|(op, amount)| (op, Some(amount.trim().trim_start_matches('#'))))
+                    .unwrap_or((extend.as_str(), None));
+                match (op.to_lowercase().as_str(), amount) {
+                    ("lsl", Some(amount)) => format!("({index} << {amount})"),
+                    (op, Some(amount)) => format!("({op}({index}) << {amount})"),
+                    (op, None) => format!("{op}({index})"),
+                }
+            }
+            (None, None) => index.to_string(),
+        });
+    }
+
+    let mut address = address.join(" + ");
+    if let Some(displacement) = memory.displacement() {
+        let displacement = displacement.trim().trim_start_matches('#');
+        address = match displacement.strip_prefix('-') {
+            Some(displacement) if !address.is_empty() => {
+                format!("{address} - {}", displacement.trim())
+            }
+            _ if !address.is_empty() => format!("{address} + {displacement}"),
+            _ => displacement.to_string(),
+        };
+    }
+
+    let mut hover = vec![
+        "# Memory operand".to_string(),
+        format!("Address: `{address}`"),
+    ];
+
+    if let Some(base) = base {
+        let base = base.text();
+        if memory.is_pre_index() {
+            hover.push(format!(
+                "`{base}` is updated with the address before the access"
+            ));
+        } else if let Some(offset) = memory.post_index() {
+            let offset = offset.text();
+            hover.push(format!(
+                "`{base}` is incremented by `{}` after the access",
+                offset.trim_start_matches('#')
+            ));
+        }
+    }
+
+    Some(hover)
+}
+
+/// Shows the unwind rules in effect after a `.cfi_*` directive
+pub fn get_cfi_hover(parser: &Parser, token: &SyntaxToken) -> Option<Vec<String>> {
+    let directive = token.parent()?;
+    let (_, state) = parser
+        .cfi()
+        .states
+        .iter()
+        .find(|(range, _)| *range == directive.text_range())?;
+
+    let mut hover = vec![
+        "# Call frame information".to_string(),
+        format!("**CFA**: `{}`", state.cfa),
+    ];
+    hover.extend(
+        state
+            .rules
+            .iter()
+            .map(|(register, rule)| format!("* `{register}`: `{rule}`")),
+    );
+
+    Some(hover)
+}
+
+/// Explains a relocation listed by `objdump -r`
+pub fn get_relocation_hover(token: &SyntaxToken) -> Option<Vec<String>> {
+    let relocation = token.parent()?;
+    let name = token.text();
+    let target = relocation
+        .children_with_tokens()
+        .skip_while(|c| c.as_token() != Some(token))
+        .skip(1)
+        .filter(|c| c.kind() != SyntaxKind::WHITESPACE)
+        .map(|c| c.to_string())
+        .join("");
+
+    let mut hover = vec![match relocations::architecture(name) {
+        Some(architecture) => format!("# {architecture} relocation"),
+        None => "# Relocation".to_string(),
+    }];
+    hover.push(match relocations::description(name) {
+        Some(description) => format!("`{name}`: {description}"),
+        None => format!("`{name}`"),
+    });
+    if !target.is_empty() {
+        hover.push(format!("**Target**: `{target}`"));
+    }
+
+    Some(hover)
+}
+
+pub fn get_label_hover(parser: &Parser, label: &LabelToken) -> Option<Vec<String>> {
     let mut symbols = Vec::new();
 
     if let Some((sym, lang)) = label.demangle() {
@@ -29,6 +177,24 @@ pub fn get_label_hover(label: &LabelToken) -> Option<Vec<String>> {
         symbols.push(format!("**{lang}**: `{sym}`"));
     }
 
+    if let Some(section) = section_for_token(label.syntax()) {
+        symbols.push(format!("**Section**: `{section}`"));
+    }
+
+    if let Some(symbol) = parser.symbols().get(label.name()) {
+        symbols.push(format!("**Binding**: `{}`", symbol.binding));
+        symbols.push(format!("**Visibility**: `{}`", symbol.visibility));
+        if let Some(kind) = symbol.kind {
+            symbols.push(format!("**Type**: `{kind}`"));
+        }
+        if let Some((size, offset)) = &symbol.size {
+            symbols.push(match Evaluator::new(parser).evaluate(size, Some(*offset)) {
+                Some(value) => format!("**Size**: `{value}` bytes"),
+                None => format!("**Size**: `{size}`"),
+            });
+        }
+    }
+
     Some(symbols)
 }
 
@@ -68,20 +234,59 @@ pub fn get_hover_mnemonic(
 }
 
 pub fn get_alias_hover(token: &SyntaxToken, alias: &Alias) -> Option<Vec<String>> {
-    let register = alias.get_register_for_alias(token.text())?;
+    let register = alias.get_register_for_alias(token.text(), token.text_range().start())?;
     Some(vec![format!(
         "`{}` is an alias to register `{register}`",
         token.text(),
     )])
 }
 
-pub fn get_constant_hover(token: &SyntaxToken, alias: &Alias) -> Option<Vec<String>> {
-    let value = alias.get_constant_for_token(token.text())?;
-    Some(vec![format!(
+pub fn get_constant_hover(parser: &Parser, token: &SyntaxToken) -> Option<Vec<String>> {
+    let offset = token.text_range().start();
+    let definition = parser
+        .alias()
+        .get_constant_for_token(token.text(), offset)?;
+    let mut hover = vec![format!(
         "`{}` is defined as `{}`",
         token.text(),
-        value.trim()
-    )])
+        definition.trim()
+    )];
+
+    if let Some(value) = Evaluator::new(parser).constant(token.text(), Some(offset)) {
+        hover.push(format!("Decimal: {value}"));
+        hover.push(format!("Hex: {}", hex(value)));
+    }
+
+    Some(hover)
+}
+
+/// Shows the body of the macro with the arguments from the call substituted
+pub fn get_macro_hover(
+    context: Arc<Context>,
+    parser: &Parser,
+    token: &SyntaxToken,
+) -> Option<Vec<String>> {
+    let arguments = macro_call_arguments(&token.parent()?);
+    let expansions = context.related_parsers(true, parser.uri().clone(), |parser| {
+        parser
+            .tree()
+            .descendants()
+            .filter_map(|d| {
+                let node = MacroNode::cast(&d)?;
+                node.name()?
+                    .text()
+                    .eq_ignore_ascii_case(token.text())
+                    .then(|| node.expand(&arguments))
+            })
+            .collect_vec()
+            .into_iter()
+    });
+
+    let expansion = expansions.first()?;
+    Some(vec![
+        String::from("# Macro Expansion"),
+        format!("```asm\n{expansion}\n```"),
+    ])
 }
 
 pub fn get_token_hover(
@@ -89,8 +294,16 @@ pub fn get_token_hover(
     parser: &Parser,
     token: SyntaxToken,
 ) -> Option<Vec<String>> {
-    let doc_strings =
-        get_definition_token(context, parser, &token, label_definition_comment).ok()?;
+    let doc_strings = get_definition_token(context, parser, &token, |parser, definition| {
+        let section = section_for_token(definition).map(|s| format!("**Section**: `{s}`"));
+        let lines = label_definition_comment(parser, definition)
+            .into_iter()
+            .chain(section)
+            .collect_vec();
+
+        (!lines.is_empty()).then(|| lines.join("  \n"))
+    })
+    .ok()?;
 
     Some(doc_strings)
 }
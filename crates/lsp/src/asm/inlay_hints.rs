@@ -1,6 +1,6 @@
 use lsp_types::{InlayHint, InlayHintLabel};
-use rowan::TextRange;
-use syntax::ast::{find_parent, SyntaxKind};
+use rowan::{NodeOrToken, TextRange};
+use syntax::ast::{find_parent, AstNode, Directive, SyntaxKind};
 
 use crate::asm::objdump_util::offset_relative_to_label;
 
@@ -30,3 +30,48 @@ pub(super) fn objdump_inlay_hints(parser: &Parser, location: TextRange) -> Vec<I
         })
         .collect()
 }
+
+/// Shows the CFA rule after each `.cfi_*` directive that changes it
+pub(super) fn cfi_inlay_hints(parser: &Parser, location: TextRange) -> Vec<InlayHint> {
+    let position = parser.position();
+    let mut previous = None;
+
+    let root = parser.tree();
+
+    parser
+        .cfi()
+        .states
+        .iter()
+        .filter_map(|(range, state)| {
+            let node = match root.covering_element(*range) {
+                NodeOrToken::Node(node) => node,
+                NodeOrToken::Token(token) => token.parent()?,
+            };
+            let changed = previous.as_ref() != Some(&state.cfa)
+                || Directive::cast(node.clone())
+                    .and_then(|d| d.name())
+                    .is_some_and(|name| name == ".cfi_startproc");
+            previous = Some(state.cfa.clone());
+            if !changed || location.intersect(node.text_range()).is_none() {
+                return None;
+            }
+
+            let token = node
+                .descendants_with_tokens()
+                .filter_map(|d| d.into_token())
+                .filter(|t| !matches!(t.kind(), SyntaxKind::WHITESPACE | SyntaxKind::COMMENT))
+                .last()?;
+
+            Some(InlayHint {
+                position: position.get_end_position(&token)?.into(),
+                label: InlayHintLabel::String(format!("CFA = {}", state.cfa)),
+                kind: None,
+                text_edits: None,
+                tooltip: None,
+                padding_left: Some(true),
+                padding_right: None,
+                data: None,
+            })
+        })
+        .collect()
+}
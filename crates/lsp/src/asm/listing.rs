@@ -0,0 +1,170 @@
+//! Removes the parts of a compiler listing that aren't usually needed to read
+//! it, in the same way as the filters in Compiler Explorer.
+
+use std::collections::HashSet;
+
+use rowan::{TextRange, TextSize};
+use syntax::ast::{AstNode as _, Directive, SyntaxKind, SyntaxNode};
+
+use super::ast::{AstNode, SectionNode};
+use super::parser::Parser;
+use crate::handler::ext::ListingFilters;
+use crate::types::LineNumber;
+
+/// Directives that emit data, these are kept so that constants and strings
+/// can still be read.
+const DATA_DIRECTIVES: [&str; 24] = [
+    ".byte", ".1byte", ".short", ".hword", ".2byte", ".value", ".word", ".long", ".int", ".4byte",
+    ".quad", ".8byte", ".xword", ".dword", ".octa", ".float", ".single", ".double", ".ascii",
+    ".asciz", ".string", ".zero", ".skip", ".space",
+];
+
+fn is_data(directive: &Directive) -> bool {
+    directive
+        .name()
+        .is_some_and(|n| DATA_DIRECTIVES.contains(&n.to_lowercase().as_str()))
+}
+
+/// Filters the listing, this returns the text that's left along with the
+/// line in the original file that each line came from.
+pub(super) fn filter(parser: &Parser, filters: &ListingFilters) -> (String, Vec<LineNumber>) {
+    let tree = parser.tree();
+    let mut removed = Vec::new();
+
+    if filters.debug_sections {
+        removed.extend(
+            tree.children()
+                .filter(|c| {
+                    SectionNode::cast(c)
+                        .and_then(|s| s.name())
+                        .is_some_and(|n| n.starts_with(".debug_"))
+                })
+                .map(|c| c.text_range()),
+        );
+    }
+
+    if filters.library_functions {
+        removed.extend(
+            tree.descendants()
+                .filter(|n| n.kind() == SyntaxKind::LABEL)
+                .filter(|n| is_library_function(parser, n))
+                .map(|n| n.text_range()),
+        );
+    }
+
+    if filters.directives {
+        removed.extend(
+            tree.descendants()
+                .filter_map(Directive::cast)
+                .filter(|d| !is_data(d))
+                .map(|d| d.syntax().text_range()),
+        );
+    }
+
+    if filters.comments {
+        removed.extend(
+            tree.descendants_with_tokens()
+                .filter(|t| t.kind() == SyntaxKind::COMMENT)
+                .map(|t| t.text_range()),
+        );
+    }
+
+    if filters.unused_labels {
+        let used = used_labels(&tree, filters);
+        removed.extend(
+            tree.descendants()
+                .filter(|n| n.kind() == SyntaxKind::LOCAL_LABEL)
+                .filter_map(|n| n.first_token())
+                .filter(|t| !used.contains(t.text().trim_end_matches(':')))
+                .map(|t| t.text_range()),
+        );
+    }
+
+    remove(parser, removed)
+}
+
+/// Functions where none of the `.loc` directives are from the source file
+/// e.g. templates and inline functions from headers
+fn is_library_function(parser: &Parser, label: &SyntaxNode) -> bool {
+    let map = parser.debug_map();
+    let mut files = label
+        .descendants()
+        .filter_map(|n| map.get_location(&n))
+        .map(|(file, _)| file)
+        .peekable();
+
+    files.peek().is_some() && files.all(|f| !map.is_source_file(f))
+}
+
+/// The names used by an instruction, or by data when the other directives
+/// are being removed
+fn used_labels(tree: &SyntaxNode, filters: &ListingFilters) -> HashSet<String> {
+    tree.descendants()
+        .filter(|n| match n.kind() {
+            SyntaxKind::INSTRUCTION => true,
+            SyntaxKind::DIRECTIVE => {
+                !filters.directives || Directive::cast(n.clone()).is_some_and(|d| is_data(&d))
+            }
+            _ => false,
+        })
+        .flat_map(|n| n.descendants_with_tokens())
+        .filter_map(|t| t.into_token())
+        .filter(|t| t.kind() == SyntaxKind::TOKEN)
+        .map(|t| t.text().to_string())
+        .collect()
+}
+
+/// Removes each of the ranges from the text, lines that are left with only
+/// whitespace are removed completely.
+fn remove(parser: &Parser, mut removed: Vec<TextRange>) -> (String, Vec<LineNumber>) {
+    removed.sort_by_key(|r| r.start());
+    let removed = removed
+        .into_iter()
+        .fold(Vec::<TextRange>::new(), |mut merged, range| {
+            match merged.last_mut() {
+                Some(last) if last.end() >= range.start() => *last = last.cover(range),
+                _ => merged.push(range),
+            }
+            merged
+        });
+    let text = parser.position().text();
+
+    let mut result = String::new();
+    let mut lines = Vec::new();
+    let mut ranges = removed.iter().peekable();
+    let mut offset = TextSize::from(0);
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.to_string();
+        let line_range = TextRange::at(offset, TextSize::of(line.as_str()));
+        offset = line_range.end();
+
+        // Skip past the ranges that end before this line
+        while ranges.next_if(|r| r.end() <= line_range.start()).is_some() {}
+
+        let mut kept = String::new();
+        let mut position = line_range.start();
+        for range in ranges.clone().take_while(|r| r.start() < line_range.end()) {
+            if range.start() > position {
+                kept.push_str(&line[TextRange::new(position, range.start()) - line_range.start()]);
+            }
+            position = position.max(range.end().min(line_range.end()));
+        }
+        if position < line_range.end() {
+            kept.push_str(&line[TextRange::new(position, line_range.end()) - line_range.start()]);
+        }
+
+        // The text after the last newline is an empty line so is skipped
+        let emptied = kept.trim().is_empty() && !line.trim().is_empty();
+        if !emptied && !line.is_empty() {
+            if kept.len() != line.len() {
+                let ending = &line[line.trim_end_matches(['\r', '\n']).len()..];
+                kept = format!("{}{ending}", kept.trim_end());
+            }
+            result.push_str(&kept);
+            lines.push(number as LineNumber);
+        }
+    }
+
+    (result, lines)
+}
@@ -1,16 +1,24 @@
 pub(crate) mod ast;
+mod cfi;
+mod conditional;
+mod control_flow;
 mod debug;
 mod definition;
 mod demangle;
 mod diff;
+mod errors;
+mod evaluate;
 pub mod handler;
 pub(crate) mod hovers;
 mod inlay_hints;
+mod listing;
 mod llvm_mca;
 mod objdump_util;
 pub mod parser;
 mod references;
+mod relocations;
 mod signature;
+mod symbols;
 
 #[cfg(test)]
 mod test;
@@ -1,20 +1,31 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fs::read_to_string;
+use std::path::Path;
 
-use super::ast::{AstToken, LabelToken};
+use super::ast::{AstToken, LabelToken, RegisterToken};
+use super::cfi::{self, CfiAnalysis};
 use super::debug::DebugMap;
+use super::symbols::SymbolTable;
 use crate::config::LSPConfig;
 use crate::file_util::make_file_relative;
+use crate::handler::types::DocumentChange;
 use crate::types::{DocumentPosition, DocumentRange, LineNumber};
-use base::{Architecture, FileType};
+use base::{Architecture, Dialect, FileType};
 use byte_unit::Byte;
 use lsp_types::Url;
 use once_cell::sync::{Lazy, OnceCell};
 use parser::config::ParserConfig;
 use parser::{ParsedData, ParsedInclude};
 use rayon::prelude::*;
-use rowan::{GreenNode, TextRange, TextSize};
+use ropey::Rope;
+use rowan::{GreenNode, NodeOrToken, TextRange, TextSize};
 use syntax::alias::Alias;
-use syntax::ast::{SyntaxKind, SyntaxNode, SyntaxToken};
+use syntax::ast::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// The number of lines at the start of an objdump that its options are
+/// detected from
+const OBJDUMP_HEADER_LINES: usize = 20;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Parser {
@@ -24,7 +35,11 @@ pub struct Parser {
     config: ParserConfig,
     line_index: PositionInfo,
     debug_map: OnceCell<DebugMap>,
+    symbols: OnceCell<SymbolTable>,
+    cfi: OnceCell<CfiAnalysis>,
+    inactive_ranges: OnceCell<Vec<TextRange>>,
     alias: Alias,
+    macros: HashSet<String>,
 }
 
 /// Helper enum for determining if tokens should be considered equal
@@ -37,6 +52,9 @@ enum SemanticEq<'a> {
     /// The token is a numeric value
     Numeric(i128),
     Float(f64),
+    /// A constant along with the location of the definition that is in
+    /// effect, as `.set` can define the same name more than once
+    Constant(&'a str, Option<TextSize>),
 }
 
 impl Parser {
@@ -44,14 +62,13 @@ impl Parser {
     /// * data: The assembly listing to parse
     pub fn from(uri: Url, data: &str, config: &LSPConfig) -> (Self, Vec<ParsedInclude>) {
         let filesize = Byte::from_bytes(data.len() as _);
-        let mut config = Self::config_from_arch(&Self::determine_architecture(data, config));
-        config.file_type = FileType::from_contents(data);
+        let config = Self::detect_config(&uri, data, config);
 
         let ParsedData {
             root,
             alias,
+            macros,
             included_files,
-            ..
         } = parser::parse_asm(data, &config, Some(uri.as_str()), Self::handle_include);
 
         (
@@ -62,12 +79,233 @@ impl Parser {
                 root,
                 config,
                 debug_map: OnceCell::new(),
+                symbols: OnceCell::new(),
+                cfi: OnceCell::new(),
+                inactive_ranges: OnceCell::new(),
                 alias,
+                macros,
             },
             included_files,
         )
     }
 
+    /// Works out how the file should be parsed from its name and contents
+    fn detect_config(uri: &Url, data: &str, config: &LSPConfig) -> ParserConfig {
+        let architecture = Self::determine_architecture(data, config);
+        // The dialect is only meaningful for x86
+        let dialect = match architecture {
+            Architecture::X86 | Architecture::X86_64 | Architecture::Unknown => {
+                Dialect::from_filename(uri.path()).unwrap_or_else(|| Dialect::from_contents(data))
+            }
+            _ => Dialect::default(),
+        };
+        let architecture = match architecture {
+            // NASM only supports x86
+            Architecture::Unknown if dialect == Dialect::Nasm => Architecture::X86_64,
+            Architecture::Unknown => Architecture::from(std::env::consts::ARCH),
+            architecture => architecture,
+        };
+
+        let mut config = Self::config_from_arch(&architecture);
+        config.file_type = FileType::from_contents(data);
+        config.dialect = dialect;
+        if dialect == Dialect::Nasm {
+            config.comment_start = String::from(";");
+        }
+
+        config
+    }
+
+    /// Applies the `changes` to the document, where possible only the lines
+    /// that have changed are parsed again and the rest of the tree is reused.
+    /// The whole file is parsed again if a change could affect anything
+    /// outside of the lines that it touches.
+    pub fn update(
+        &self,
+        changes: Vec<DocumentChange>,
+        config: &LSPConfig,
+    ) -> (Self, Vec<ParsedInclude>) {
+        let mut text = self.line_index.text().clone();
+        let mut root = Some(self.root.clone());
+        let mut alias = self.alias.clone();
+        let mut detect = false;
+
+        for change in changes {
+            let Some(range) = change.range else {
+                text = Rope::from_str(&change.text);
+                root = None;
+                continue;
+            };
+
+            let Some(range) = PositionInfo { text: text.clone() }.range_to_text_range(&range)
+            else {
+                error!("Invalid change range {range:?}");
+                continue;
+            };
+            // The position of the line after the last line is past the end
+            let length = TextSize::from(text.len_bytes() as u32);
+            let range = TextRange::new(range.start().min(length), range.end().min(length));
+            let old_lines = Self::lines_around(&text, range);
+            let start = text.byte_to_char(range.start().into());
+            let end = text.byte_to_char(range.end().into());
+            text.remove(start..end);
+            text.insert(start, &change.text);
+            let new_range = TextRange::at(range.start(), TextSize::from(change.text.len() as u32));
+            // The file type is only detected from the first few lines
+            let header = text.byte_to_line(range.start().into()) <= OBJDUMP_HEADER_LINES;
+            detect |= Self::affects_detection(&old_lines, &self.config, header)
+                || Self::affects_detection(
+                    &Self::lines_around(&text, new_range),
+                    &self.config,
+                    header,
+                );
+
+            root = root.and_then(|root| self.reparse(root, range, &change.text, &text, &alias));
+            alias.edit(range, TextSize::from(change.text.len() as u32));
+        }
+
+        // Anything that changes how the file is detected e.g. `.arch` or
+        // `.intel_syntax` needs everything to be parsed again
+        let data = text.to_string();
+        if detect && Self::detect_config(&self.id, &data, config) != self.config {
+            root = None;
+        }
+
+        match root {
+            Some(root) => (
+                Self {
+                    id: self.id.clone(),
+                    root,
+                    filesize: Byte::from_bytes(text.len_bytes() as _),
+                    config: self.config.clone(),
+                    line_index: PositionInfo { text },
+                    debug_map: OnceCell::new(),
+                    symbols: OnceCell::new(),
+                    cfi: OnceCell::new(),
+                    inactive_ranges: OnceCell::new(),
+                    alias,
+                    macros: self.macros.clone(),
+                },
+                Vec::new(),
+            ),
+            None => Self::from(self.id.clone(), &data, config),
+        }
+    }
+
+    /// The whole lines of `text` that `range` is within
+    fn lines_around(text: &Rope, range: TextRange) -> String {
+        let first = text.byte_to_line(range.start().into());
+        let last = text.byte_to_line(range.end().into());
+        let end = if last + 1 < text.len_lines() {
+            text.line_to_byte(last + 1)
+        } else {
+            text.len_bytes()
+        };
+
+        text.byte_slice(text.line_to_byte(first)..end).to_string()
+    }
+
+    /// Runs the detection on just the lines that an edit touched, the
+    /// detection for the whole file only has to be run again if something
+    /// is detected from them.
+    fn affects_detection(lines: &str, config: &ParserConfig, header: bool) -> bool {
+        Self::detect_architecture(lines).is_some()
+            || Dialect::from_contents(lines) != Dialect::default()
+            || (header
+                && (matches!(config.file_type, FileType::ObjDump(_))
+                    || lines.contains("file format")
+                    || lines.contains("Disassembly of section")))
+    }
+
+    /// Parses the lines around `range` again after it has been replaced by
+    /// `replacement`, `text` is the updated document and `alias` has the
+    /// definitions from before the change. The nodes are spliced
+    /// into `root` returning the new tree, None is returned if the lines
+    /// can't be parsed on their own.
+    fn reparse(
+        &self,
+        root: GreenNode,
+        range: TextRange,
+        replacement: &str,
+        text: &Rope,
+        alias: &Alias,
+    ) -> Option<GreenNode> {
+        let root = SyntaxNode::new_root(root);
+        let container = match root.covering_element(range) {
+            NodeOrToken::Node(node) => node,
+            NodeOrToken::Token(token) => token.parent()?,
+        }
+        .ancestors()
+        .find(|n| {
+            matches!(
+                n.kind(),
+                SyntaxKind::ROOT
+                    | SyntaxKind::SECTION
+                    | SyntaxKind::LABEL
+                    | SyntaxKind::LOCAL_LABEL
+                    | SyntaxKind::CONDITIONAL_BRANCH
+            )
+        })?;
+
+        // Extend the edit out to the surrounding line breaks, as whitespace
+        // includes the indentation of the next line the lines start and end
+        // within whitespace.
+        let children = container.children_with_tokens().collect::<Vec<_>>();
+        let is_line_break =
+            |c: &SyntaxElement| c.kind() == SyntaxKind::WHITESPACE && c.to_string().contains('\n');
+
+        let first = children
+            .iter()
+            .position(|c| c.text_range().end() >= range.start())?;
+        let last = children
+            .iter()
+            .rposition(|c| c.text_range().start() <= range.end())?;
+        let first = children[..=first]
+            .iter()
+            .rposition(is_line_break)
+            .unwrap_or(0);
+        let last = children[last..]
+            .iter()
+            .position(is_line_break)
+            .map_or(children.len() - 1, |p| p + last);
+
+        let start = children[first].text_range().start();
+        let end = children[last].text_range().end();
+        let at_start = !is_line_break(&children[first]) && start == TextSize::default();
+        let at_end = !is_line_break(&children[last]) && end == root.text_range().end();
+        if !(is_line_break(&children[first]) || at_start)
+            || !(is_line_break(&children[last]) || at_end)
+            || children[first..=last].iter().any(|c| match c {
+                NodeOrToken::Node(n) => parser::is_structural(NodeOrToken::Node(&n.green())),
+                NodeOrToken::Token(t) => parser::is_structural(NodeOrToken::Token(t.green())),
+            })
+        {
+            return None;
+        }
+
+        let end = usize::from(end) + replacement.len() - usize::from(range.len());
+        let lines = text.byte_slice(usize::from(start)..end).to_string();
+
+        // The lines must still start and end at a line break otherwise they
+        // have been joined with the lines around them
+        let leading = lines.trim_start_matches([' ', '\t', '\n']);
+        let trailing = lines.trim_end_matches([' ', '\t']);
+        if !(at_start || lines[..lines.len() - leading.len()].contains('\n'))
+            || !(at_end || trailing.ends_with('\n'))
+        {
+            return None;
+        }
+
+        // The lines are parsed with the aliases that are in effect at the
+        // start of them, as `.unreq` could remove an alias later on
+        let nodes = parser::parse_lines(&lines, &self.config, &alias.at(start), &self.macros)?;
+        let green = container
+            .green()
+            .splice_children(first..last + 1, nodes.children().map(|c| c.to_owned()));
+
+        Some(container.replace_with(green))
+    }
+
     fn handle_include(config: &ParserConfig, from: &str, file: &str) -> Option<ParsedInclude> {
         let included_file = make_file_relative(from, file)?;
         let file_uri = Url::from_file_path(&included_file).ok()?;
@@ -76,12 +314,13 @@ impl Parser {
         let ParsedData {
             root,
             alias,
+            macros,
             included_files,
-            ..
-        } = parser::parse_asm(&data, config, Some(from), Self::handle_include);
+        } = parser::parse_asm(&data, config, Some(file_uri.as_str()), Self::handle_include);
 
         Some(ParsedInclude {
             alias,
+            macros,
             root,
             included_files,
             id: file_uri.to_string(),
@@ -106,7 +345,27 @@ impl Parser {
     }
 
     pub(super) fn debug_map(&self) -> &DebugMap {
-        self.debug_map.get_or_init(|| DebugMap::new(&self.tree()))
+        self.debug_map.get_or_init(|| {
+            let path = self.id.to_file_path().ok();
+            DebugMap::new(&self.tree(), path.as_deref().and_then(Path::parent))
+        })
+    }
+
+    pub(crate) fn symbols(&self) -> &SymbolTable {
+        self.symbols.get_or_init(|| SymbolTable::new(&self.tree()))
+    }
+
+    pub(crate) fn cfi(&self) -> &CfiAnalysis {
+        self.cfi.get_or_init(|| cfi::analyse(self))
+    }
+
+    /// Determines if the token is within a conditional branch that won't be
+    /// assembled.
+    pub(crate) fn is_inactive(&self, token: &SyntaxToken) -> bool {
+        self.inactive_ranges
+            .get_or_init(|| super::conditional::inactive_ranges(&self.tree(), &self.alias))
+            .iter()
+            .any(|range| range.contains_range(token.text_range()))
     }
 
     pub fn alias(&self) -> &Alias {
@@ -125,18 +384,8 @@ impl Parser {
         &self.config.file_type
     }
 
-    pub(super) fn reconstruct_file(&self) -> String {
-        let mut buffer: Vec<u8> = Vec::with_capacity(self.tree().text_range().end().into());
-        for token in self.tree().descendants_with_tokens() {
-            if let Some(token) = token.as_token() {
-                buffer.extend_from_slice(token.text().as_bytes());
-            }
-        }
-
-        // The data that we have copied into here has come from a &str which
-        // is always valid UTF8, therefore it should be perfectly fine to use
-        // the unchecked variant of the function, as validation isn't required.
-        unsafe { String::from_utf8_unchecked(buffer) }
+    pub fn dialect(&self) -> Dialect {
+        self.config.dialect
     }
 
     pub(super) fn reconstruct_from_tokens(
@@ -202,11 +451,13 @@ impl Parser {
     fn token_value<'a>(&self, token: &'a SyntaxToken) -> Option<SemanticEq<'a>> {
         match token.kind() {
             SyntaxKind::REGISTER => Some(SemanticEq::Register(arch::registers::register_id(
-                token.text(),
+                &self.token::<RegisterToken>(token)?.name(),
                 &self.config,
             )?)),
             SyntaxKind::REGISTER_ALIAS => {
-                let register = self.alias.get_register_for_alias(token.text())?;
+                let register = self
+                    .alias
+                    .get_register_for_alias(token.text(), token.text_range().start())?;
                 Some(SemanticEq::Register(arch::registers::register_id(
                     register,
                     &self.config,
@@ -219,8 +470,11 @@ impl Parser {
                 .token::<LabelToken>(token)
                 .map(|t| SemanticEq::String(t.name())),
             SyntaxKind::RELOCATION => Some(SemanticEq::String(token.text())),
-            SyntaxKind::CONSTANT => Some(SemanticEq::String(token.text())),
-            SyntaxKind::NAME => Some(SemanticEq::String(token.text())),
+            SyntaxKind::CONSTANT | SyntaxKind::NAME => Some(SemanticEq::Constant(
+                token.text(),
+                self.alias
+                    .definition_offset(token.text(), token.text_range().start()),
+            )),
             SyntaxKind::L_PAREN
             | SyntaxKind::R_PAREN
             | SyntaxKind::L_SQ
@@ -237,32 +491,62 @@ impl Parser {
             | SyntaxKind::LOCAL_LABEL
             | SyntaxKind::COMMENT
             | SyntaxKind::IMMEDIATE
+            | SyntaxKind::KEYWORD
             | SyntaxKind::ALIAS
             | SyntaxKind::INSTRUCTION
             | SyntaxKind::DIRECTIVE
             | SyntaxKind::BRACKETS
+            | SyntaxKind::MEMORY_OPERAND
+            | SyntaxKind::BASE
+            | SyntaxKind::INDEX
+            | SyntaxKind::SCALE
+            | SyntaxKind::DISPLACEMENT
+            | SyntaxKind::WRITEBACK
             | SyntaxKind::METADATA
             | SyntaxKind::OBJDUMP_OFFSET
+            | SyntaxKind::SOURCE_LINE
+            | SyntaxKind::SOURCE_LOCATION
             | SyntaxKind::EXPR
             | SyntaxKind::CONST_DEF
+            | SyntaxKind::MACRO_DEF
+            | SyntaxKind::MACRO_CALL
+            | SyntaxKind::CONDITIONAL
+            | SyntaxKind::CONDITIONAL_BRANCH
+            | SyntaxKind::SECTION
+            | SyntaxKind::ERROR
             | SyntaxKind::ROOT => None,
         }
     }
 
-    /// Attempt to determine the architecture that the assembly data is for.
+    /// Attempt to determine the architecture that the assembly data is for,
+    /// this is `Unknown` when neither the file nor the config gives one.
     fn determine_architecture(filedata: &str, config: &LSPConfig) -> Architecture {
+        let arch = Self::detect_architecture(filedata).unwrap_or(config.architecture);
+
+        debug!("Architecture detected: {:?}", arch);
+
+        arch
+    }
+
+    /// The architecture that the directives or instructions in `filedata`
+    /// are for, None if nothing in it names one.
+    fn detect_architecture(filedata: &str) -> Option<Architecture> {
         use regex::Regex;
 
-        static ARCH_DETECTION: Lazy<[Regex; 4]> = Lazy::new(|| {
+        static ARCH_DETECTION: Lazy<[Regex; 8]> = Lazy::new(|| {
             [
                 Regex::new(r#"lsp-asm-architecture: (.+) ?"#).unwrap(),
                 Regex::new(r#"^\s*\.arch (.*)"#).unwrap(),
-                Regex::new(r#".*:[\t ]+file format elf64-(.*)"#).unwrap(),
+                Regex::new(r#"(?m)^\s*\.attribute\s+(?:arch|5),\s*"(rv64[^"]*)""#).unwrap(),
+                Regex::new(r#".*:[\t ]+file format elf(?:32|64)-(.*)"#).unwrap(),
                 Regex::new(r#".*:[\t ]+file format (?i)mach-o (.*)"#).unwrap(),
+                Regex::new(r#"(?m)^\s*\.cpu\s+(cortex-[mr]\S*)"#).unwrap(),
+                Regex::new(r#"(?m)^\s*\.(thumb|arm)\s*$"#).unwrap(),
+                Regex::new(r#"(?m)^\s*\.machine\s+"?(power\d+|ppc64)"#).unwrap(),
             ]
         });
 
-        let arch = ARCH_DETECTION
+        ARCH_DETECTION
             .par_iter()
             .filter_map(|regex| regex.captures(filedata))
             .find_map_first(|captures| {
@@ -270,14 +554,40 @@ impl Parser {
                     .get(1)
                     .map(|arch| Architecture::from(arch.as_str().trim()))
             })
-            .unwrap_or(config.architecture);
+            .or_else(|| Self::detect_from_instructions(filedata))
+    }
 
-        debug!("Architecture detected: {:?}", arch);
+    /// Compiler listings such as those produced by `-m32` don't contain any
+    /// directive that names the architecture, fall back to looking for
+    /// instructions that are only valid for a specific architecture.
+    fn detect_from_instructions(filedata: &str) -> Option<Architecture> {
+        use regex::Regex;
 
-        match arch {
-            Architecture::Unknown => Architecture::from(std::env::consts::ARCH),
-            a => a,
-        }
+        static DETECTION: Lazy<[(Regex, Architecture); 3]> = Lazy::new(|| {
+            [
+                // The frame setup for 32 bit x86 pushes the 32 bit base pointer,
+                // this isn't encodable on x86-64.
+                (
+                    Regex::new(r#"(?m)^\s*(?:pushl\s+%ebp|push\s+ebp|\.code32)\b"#).unwrap(),
+                    Architecture::X86,
+                ),
+                // NASM selects the instruction encoding with the `bits` directive
+                (
+                    Regex::new(r#"(?mi)^\s*\[?bits\s+(?:16|32)\b"#).unwrap(),
+                    Architecture::X86,
+                ),
+                // The ELFv2 ABI version is only emitted for 64 bit PowerPC
+                (
+                    Regex::new(r#"(?m)^\s*\.abiversion\s+2\b"#).unwrap(),
+                    Architecture::PowerPC64,
+                ),
+            ]
+        });
+
+        DETECTION
+            .iter()
+            .find(|(regex, _)| regex.is_match(filedata))
+            .map(|(_, arch)| *arch)
     }
 
     pub(crate) fn config_from_arch(arch: &Architecture) -> ParserConfig {
@@ -288,11 +598,35 @@ impl Parser {
                 architecture: *arch,
                 ..ParserConfig::default()
             },
+            Architecture::X86 => ParserConfig {
+                registers: Some(&arch::register_names::X86_REGISTERS),
+                architecture: *arch,
+                ..ParserConfig::default()
+            },
+            Architecture::AArch32 => ParserConfig {
+                comment_start: String::from(Architecture::AArch32.default_comment_start()),
+                registers: Some(&arch::register_names::AARCH32_REGISTERS),
+                architecture: *arch,
+                ..ParserConfig::default()
+            },
             Architecture::X86_64 => ParserConfig {
                 registers: Some(&arch::register_names::X86_64_REGISTERS),
                 architecture: *arch,
                 ..ParserConfig::default()
             },
+            Architecture::RiscV64 => ParserConfig {
+                comment_start: String::from(Architecture::RiscV64.default_comment_start()),
+                registers: Some(&arch::register_names::RISCV64_REGISTERS),
+                architecture: *arch,
+                ..ParserConfig::default()
+            },
+            Architecture::PowerPC64 => ParserConfig {
+                comment_start: String::from(Architecture::PowerPC64.default_comment_start()),
+                registers: Some(&arch::register_names::POWERPC64_REGISTERS),
+                register_operands: Some(&arch::register_names::POWERPC64_OPERANDS),
+                architecture: *arch,
+                ..ParserConfig::default()
+            },
             Architecture::Unknown => ParserConfig::default(),
         }
     }
@@ -315,24 +649,34 @@ pub fn split_parsed_include(include: ParsedInclude) -> (Parser, Vec<ParsedInclud
             root: include.root,
             config: Default::default(),
             debug_map: OnceCell::new(),
+            symbols: OnceCell::new(),
+            cfi: OnceCell::new(),
+            inactive_ranges: OnceCell::new(),
             alias: include.alias,
+            macros: include.macros,
         },
         include.included_files,
     )
 }
 
 /// Provides a method for converting `TextSize` data into document line and
-/// column numbers.
+/// column numbers, this also holds the text of the document.
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct PositionInfo {
-    lines: Vec<TextSize>,
+    text: Rope,
 }
 
 impl PositionInfo {
-    /// Construct a position info structure from a `SyntaxNode`
+    /// Construct a position info structure from the document text
     pub fn new(data: &str) -> Self {
-        let lines = Self::build_lines(data);
-        Self { lines }
+        Self {
+            text: Rope::from_str(data),
+        }
+    }
+
+    /// Gets the text of the document
+    pub fn text(&self) -> &Rope {
+        &self.text
     }
 
     /// Gets a document position for the given `token`
@@ -362,7 +706,7 @@ impl PositionInfo {
 
     /// Converts a `DocumentPosition` into a `TextSize` position
     pub fn point_for_position(&self, position: &DocumentPosition) -> Option<TextSize> {
-        let line = self.lines.get(position.line as usize)?;
+        let line = self.line_start(position.line as usize)?;
         line.checked_add(position.column.into())
     }
 
@@ -370,23 +714,12 @@ impl PositionInfo {
     /// If the requested start or end fall outside the range of the document then
     /// the document start or end will be returned
     pub fn make_range_for_lines(&self, start: LineNumber, end: LineNumber) -> TextRange {
-        // Unwrap on first or last is fine as we always insert one line
-        let start = self
-            .lines
-            .get(start as usize)
-            .unwrap_or_else(|| self.lines.first().unwrap());
+        let start = self.line_start(start as usize).unwrap_or_default();
         let end = self
-            .lines
-            .get(end as usize)
-            .unwrap_or_else(|| self.lines.last().unwrap());
-
-        TextRange::new(*start, *end)
-    }
+            .line_start(end as usize)
+            .unwrap_or_else(|| self.end_of_lines());
 
-    pub fn offset_for_line(&self, line: LineNumber) -> Option<u32> {
-        self.lines
-            .get(line as usize)
-            .map(|s: &TextSize| u32::from(*s))
+        TextRange::new(start, end)
     }
 
     pub fn range_to_text_range(&self, range: &DocumentRange) -> Option<TextRange> {
@@ -398,29 +731,32 @@ impl PositionInfo {
 
     /// Helper function to get the line and column for a text size
     pub fn get_position_for_size(&self, ts: &TextSize) -> Option<DocumentPosition> {
-        let line = self.lines.partition_point(|l| l <= ts) - 1;
-        let pos = self.lines.get(line)?;
+        let line = if usize::from(*ts) <= self.text.len_bytes() {
+            self.text.byte_to_line(usize::from(*ts))
+        } else {
+            self.text.len_lines()
+        };
+        let pos = self.line_start(line)?;
 
-        let column = ts.checked_sub(*pos).map(|c| c.into()).unwrap_or(0);
+        let column = ts.checked_sub(pos).map(|c| c.into()).unwrap_or(0);
         Some(DocumentPosition {
             line: line as _,
             column,
         })
     }
 
-    /// Build a vector with an item for each line in in the data, the item will
-    /// contain the `TextSize` for the start of the line.
-    fn build_lines(data: &str) -> Vec<TextSize> {
-        std::iter::once(TextSize::default())
-            .chain(data.char_indices().filter_map(|(pos, c)| {
-                if c == '\n' {
-                    Some(((pos + 1) as u32).into())
-                } else {
-                    None
-                }
-            }))
-            .chain(std::iter::once(((data.len() + 1) as u32).into()))
-            .collect()
+    /// Gets the `TextSize` for the start of `line`, there is an extra line
+    /// after the last line of the document which starts past the end.
+    fn line_start(&self, line: usize) -> Option<TextSize> {
+        match line.cmp(&self.text.len_lines()) {
+            Ordering::Less => Some((self.text.line_to_byte(line) as u32).into()),
+            Ordering::Equal => Some(self.end_of_lines()),
+            Ordering::Greater => None,
+        }
+    }
+
+    fn end_of_lines(&self) -> TextSize {
+        ((self.text.len_bytes() + 1) as u32).into()
     }
 }
 
@@ -435,6 +771,205 @@ second line
 another"#;
         let map = PositionInfo::new(data);
         let result: Vec<TextSize> = vec![0u32.into(), 11u32.into(), 23u32.into(), 31u32.into()];
-        assert_eq!(result, map.lines);
+        assert_eq!(
+            result,
+            (0..4)
+                .filter_map(|line| map.point_for_position(&DocumentPosition { line, column: 0 }))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_nasm_extension_architecture() {
+        let parse = |uri: &str, data: &str, architecture: Architecture| {
+            let config = LSPConfig {
+                architecture,
+                ..Default::default()
+            };
+            let (parser, _) = Parser::from(Url::parse(uri).unwrap(), data, &config);
+            (*parser.architecture(), parser.dialect())
+        };
+
+        assert_eq!(
+            parse("file:///src/a.asm", "\tmov rax, 1", Architecture::Unknown),
+            (Architecture::X86_64, Dialect::Nasm)
+        );
+        assert_eq!(
+            parse("file:///src/a.asm", "\tmov eax, 1", Architecture::X86),
+            (Architecture::X86, Dialect::Nasm)
+        );
+        assert_eq!(
+            parse("file:///src/a.asm", "\tmov x0, x1", Architecture::AArch64),
+            (Architecture::AArch64, Dialect::Att)
+        );
+        assert_eq!(
+            parse(
+                "file:///src/a.asm",
+                "// lsp-asm-architecture: arm\n\tmov r0, r1",
+                Architecture::Unknown
+            ),
+            (Architecture::AArch32, Dialect::Att)
+        );
+    }
+
+    const UPDATE_SOURCE: &str = r#"    .text
+entry:
+    mov x0, x1
+    add x0, x0, #1
+    ret
+    .data
+value:
+    .word 1
+    .equ SIZE, 4
+// lsp-asm-architecture: AArch64"#;
+
+    fn change(start: (u32, u32), end: (u32, u32), text: &str) -> DocumentChange {
+        DocumentChange {
+            text: String::from(text),
+            range: Some(DocumentRange {
+                start: DocumentPosition {
+                    line: start.0,
+                    column: start.1,
+                },
+                end: DocumentPosition {
+                    line: end.0,
+                    column: end.1,
+                },
+            }),
+        }
+    }
+
+    /// Applies the changes and checks that the result is the same as parsing
+    /// `expected` from scratch.
+    fn assert_update(changes: Vec<DocumentChange>, expected: &str) -> (Parser, Parser) {
+        assert_update_source(UPDATE_SOURCE, changes, expected)
+    }
+
+    fn assert_update_source(
+        source: &str,
+        changes: Vec<DocumentChange>,
+        expected: &str,
+    ) -> (Parser, Parser) {
+        let config = LSPConfig::default();
+        let uri = Url::parse("file://temp").unwrap();
+        let (parser, _) = Parser::from(uri.clone(), source, &config);
+        let (updated, _) = parser.update(changes, &config);
+        let (full, _) = Parser::from(uri, expected, &config);
+
+        assert_eq!(expected, updated.position().text().to_string());
+        assert_eq!(
+            format!("{:#?}", full.tree()),
+            format!("{:#?}", updated.tree())
+        );
+        assert_eq!(full, updated);
+
+        (parser, updated)
+    }
+
+    fn find_label(parser: &Parser, name: &str) -> SyntaxNode {
+        parser
+            .tree()
+            .descendants()
+            .find(|n| n.kind() == SyntaxKind::LABEL && n.to_string().starts_with(name))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_update_incremental() {
+        let (parser, updated) = assert_update(
+            vec![change((3, 17), (3, 18), "16")],
+            &UPDATE_SOURCE.replace("#1", "#16"),
+        );
+
+        // Only the edited lines are parsed again, the rest of the tree is
+        // shared with the previous version.
+        let before = find_label(&parser, "value:");
+        let after = find_label(&updated, "value:");
+        assert!(std::ptr::eq(&*before.green(), &*after.green()));
+
+        let before = find_label(&parser, "entry:");
+        let after = find_label(&updated, "entry:");
+        assert!(!std::ptr::eq(&*before.green(), &*after.green()));
+    }
+
+    #[test]
+    fn test_update_multiple_changes() {
+        assert_update(
+            vec![
+                change((2, 4), (2, 14), "mov x2, x3"),
+                change((4, 7), (4, 7), "\n    nop"),
+                change((8, 11), (8, 11), ", 2"),
+            ],
+            &UPDATE_SOURCE
+                .replace("mov x0, x1", "mov x2, x3")
+                .replace("ret\n", "ret\n    nop\n")
+                .replace(".word 1", ".word 1, 2"),
+        );
+    }
+
+    #[test]
+    fn test_update_structural() {
+        // New labels and sections change the nodes around them so the whole
+        // file is parsed again.
+        assert_update(
+            vec![change((4, 0), (4, 0), "other:\n")],
+            &UPDATE_SOURCE.replace("    ret", "other:\n    ret"),
+        );
+        assert_update(
+            vec![change((4, 0), (4, 0), "    .section .rodata\n")],
+            &UPDATE_SOURCE.replace("    ret", "    .section .rodata\n    ret"),
+        );
+        assert_update(
+            vec![change((2, 14), (3, 4), " ")],
+            &UPDATE_SOURCE.replace("x1\n    add", "x1 add"),
+        );
+    }
+
+    #[test]
+    fn test_update_detection() {
+        // Lines that change the architecture or dialect that is detected
+        // change how the rest of the file is parsed
+        const SOURCE: &str = "main:\n    push %rbp\n    mov %rsp, %rbp\n    ret\n";
+
+        assert_update_source(
+            SOURCE,
+            vec![change((1, 4), (1, 13), "pushl %ebp")],
+            &SOURCE.replace("push %rbp", "pushl %ebp"),
+        );
+        assert_update_source(
+            SOURCE,
+            vec![change((3, 7), (3, 7), "\n    .code32")],
+            &SOURCE.replace("ret\n", "ret\n    .code32\n"),
+        );
+        assert_update_source(
+            SOURCE,
+            vec![change((0, 0), (0, 0), "    .intel_syntax noprefix\n")],
+            &format!("    .intel_syntax noprefix\n{SOURCE}"),
+        );
+        assert_update_source(
+            SOURCE,
+            vec![change((3, 7), (3, 7), "\n%define SIZE 16")],
+            &SOURCE.replace("ret\n", "ret\n%define SIZE 16\n"),
+        );
+        assert_update_source(
+            SOURCE,
+            vec![change((3, 7), (3, 7), "\n    .abiversion 2")],
+            &SOURCE.replace("ret\n", "ret\n    .abiversion 2\n"),
+        );
+    }
+
+    #[test]
+    fn test_update_full() {
+        let config = LSPConfig::default();
+        let (parser, _) = Parser::from(Url::parse("file://temp").unwrap(), UPDATE_SOURCE, &config);
+        let (updated, _) = parser.update(
+            vec![DocumentChange {
+                text: String::from("    nop"),
+                range: None,
+            }],
+            &config,
+        );
+
+        assert_eq!("    nop", updated.position().text().to_string());
     }
 }
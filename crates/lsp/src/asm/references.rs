@@ -2,7 +2,7 @@ use std::iter;
 
 use rowan::TextRange;
 use syntax::ast::{find_parent, SyntaxKind, SyntaxToken};
-use syntax::utils::token_is_local_label;
+use syntax::utils::{token_is_local_label, token_is_macro_name};
 
 use super::parser::Parser;
 
@@ -31,12 +31,24 @@ pub fn find_references<'a>(
     range: TextRange,
     include_decl: bool,
 ) -> Box<dyn Iterator<Item = SyntaxToken> + 'a> {
+    if token_is_macro_name(token) {
+        return Box::new(
+            parser
+                .tokens_in_range(range)
+                .filter(token_is_macro_name)
+                .filter(|t| !parser.is_inactive(t))
+                .filter(move |t| include_decl || t.kind() != SyntaxKind::NAME)
+                .filter(move |t| t.text().eq_ignore_ascii_case(token.text())),
+        );
+    }
+
     if matches!(token.kind(), SyntaxKind::NUMBER | SyntaxKind::MNEMONIC) {
         return Box::new(iter::empty::<SyntaxToken>());
     }
 
     let references = parser
         .tokens_in_range(range)
+        .filter(|t| !parser.is_inactive(t))
         .filter(move |t| parser.token_text_equal(token, t));
 
     let label_fn =
@@ -0,0 +1,149 @@
+//! Descriptions of the relocation types that `objdump -r` lists after an
+//! instruction.
+
+/// The architecture that each relocation prefix belongs to
+const ARCHITECTURES: [(&str, &str); 6] = [
+    ("R_X86_64_", "x86-64"),
+    ("R_386_", "x86"),
+    ("R_AARCH64_", "AArch64"),
+    ("R_ARM_", "Arm"),
+    ("R_RISCV_", "RISC-V"),
+    ("R_PPC64_", "PowerPC 64"),
+];
+
+const RELOCATIONS: &[(&str, &str)] = &[
+    ("R_X86_64_NONE", "No relocation"),
+    ("R_X86_64_64", "The 64-bit address of the symbol"),
+    ("R_X86_64_32", "The 32-bit address of the symbol, zero extended to 64 bits"),
+    ("R_X86_64_32S", "The 32-bit address of the symbol, sign extended to 64 bits"),
+    ("R_X86_64_16", "The 16-bit address of the symbol"),
+    ("R_X86_64_8", "The 8-bit address of the symbol"),
+    ("R_X86_64_PC64", "The 64-bit offset from here to the symbol"),
+    ("R_X86_64_PC32", "The 32-bit offset from here to the symbol"),
+    ("R_X86_64_PC16", "The 16-bit offset from here to the symbol"),
+    ("R_X86_64_PC8", "The 8-bit offset from here to the symbol"),
+    ("R_X86_64_PLT32", "The 32-bit offset from here to the symbol's PLT entry, or to the symbol when it is local"),
+    ("R_X86_64_GOT32", "The 32-bit offset of the symbol's GOT entry from the start of the GOT"),
+    ("R_X86_64_GOTPCREL", "The 32-bit offset from here to the symbol's GOT entry"),
+    ("R_X86_64_GOTPCRELX", "The 32-bit offset from here to the symbol's GOT entry, the linker can replace the load with the address of the symbol"),
+    ("R_X86_64_REX_GOTPCRELX", "The 32-bit offset from here to the symbol's GOT entry for an instruction with a REX prefix, the linker can replace the load with the address of the symbol"),
+    ("R_X86_64_GOTPC32", "The 32-bit offset from here to the GOT"),
+    ("R_X86_64_GOTOFF64", "The 64-bit offset of the symbol from the GOT"),
+    ("R_X86_64_TPOFF32", "The 32-bit offset of the thread local symbol from the thread pointer"),
+    ("R_X86_64_GOTTPOFF", "The 32-bit offset from here to the GOT entry holding the thread pointer offset of the symbol"),
+    ("R_X86_64_TLSGD", "The 32-bit offset from here to the GOT entries used by `__tls_get_addr` for the symbol"),
+    ("R_X86_64_TLSLD", "The 32-bit offset from here to the GOT entries used by `__tls_get_addr` for the module"),
+    ("R_X86_64_DTPOFF32", "The 32-bit offset of the thread local symbol in the module's TLS block"),
+    ("R_X86_64_COPY", "Copies the symbol's data from the shared object at load time"),
+    ("R_X86_64_GLOB_DAT", "Sets a GOT entry to the address of the symbol at load time"),
+    ("R_X86_64_JUMP_SLOT", "Sets a PLT entry's GOT slot to the address of the symbol at load time"),
+    ("R_X86_64_RELATIVE", "Adds the load address of the object at load time"),
+    ("R_386_NONE", "No relocation"),
+    ("R_386_32", "The 32-bit address of the symbol"),
+    ("R_386_PC32", "The 32-bit offset from here to the symbol"),
+    ("R_386_GOT32", "The offset of the symbol's GOT entry from the start of the GOT"),
+    ("R_386_GOT32X", "The offset of the symbol's GOT entry from the start of the GOT, the linker can replace the load with the address of the symbol"),
+    ("R_386_PLT32", "The 32-bit offset from here to the symbol's PLT entry"),
+    ("R_386_GOTOFF", "The offset of the symbol from the GOT"),
+    ("R_386_GOTPC", "The offset from here to the GOT"),
+    ("R_386_COPY", "Copies the symbol's data from the shared object at load time"),
+    ("R_386_GLOB_DAT", "Sets a GOT entry to the address of the symbol at load time"),
+    ("R_386_JUMP_SLOT", "Sets a PLT entry's GOT slot to the address of the symbol at load time"),
+    ("R_386_RELATIVE", "Adds the load address of the object at load time"),
+    ("R_AARCH64_NONE", "No relocation"),
+    ("R_AARCH64_ABS64", "The 64-bit address of the symbol"),
+    ("R_AARCH64_ABS32", "The 32-bit address of the symbol"),
+    ("R_AARCH64_ABS16", "The 16-bit address of the symbol"),
+    ("R_AARCH64_PREL64", "The 64-bit offset from here to the symbol"),
+    ("R_AARCH64_PREL32", "The 32-bit offset from here to the symbol"),
+    ("R_AARCH64_PREL16", "The 16-bit offset from here to the symbol"),
+    ("R_AARCH64_CALL26", "The offset from here to the symbol for a `bl`, the linker can add a veneer if it is out of range"),
+    ("R_AARCH64_JUMP26", "The offset from here to the symbol for a `b`, the linker can add a veneer if it is out of range"),
+    ("R_AARCH64_CONDBR19", "The offset from here to the symbol for a conditional branch"),
+    ("R_AARCH64_TSTBR14", "The offset from here to the symbol for a `tbz` or `tbnz`"),
+    ("R_AARCH64_ADR_PREL_LO21", "The offset from here to the symbol for an `adr`"),
+    ("R_AARCH64_ADR_PREL_PG_HI21", "The offset from this page to the symbol's 4KB page for an `adrp`"),
+    ("R_AARCH64_ADR_PREL_PG_HI21_NC", "The offset from this page to the symbol's 4KB page for an `adrp`, without an overflow check"),
+    ("R_AARCH64_ADD_ABS_LO12_NC", "The low 12 bits of the symbol's address for an `add`, used with `adrp`"),
+    ("R_AARCH64_LDST8_ABS_LO12_NC", "The low 12 bits of the symbol's address for a byte load or store"),
+    ("R_AARCH64_LDST16_ABS_LO12_NC", "The low 12 bits of the symbol's address for a halfword load or store"),
+    ("R_AARCH64_LDST32_ABS_LO12_NC", "The low 12 bits of the symbol's address for a word load or store"),
+    ("R_AARCH64_LDST64_ABS_LO12_NC", "The low 12 bits of the symbol's address for a doubleword load or store"),
+    ("R_AARCH64_LDST128_ABS_LO12_NC", "The low 12 bits of the symbol's address for a quadword load or store"),
+    ("R_AARCH64_MOVW_UABS_G0", "Bits 0 to 15 of the symbol's address for a `movz` or `movk`"),
+    ("R_AARCH64_MOVW_UABS_G0_NC", "Bits 0 to 15 of the symbol's address for a `movk`, without an overflow check"),
+    ("R_AARCH64_MOVW_UABS_G1", "Bits 16 to 31 of the symbol's address for a `movz` or `movk`"),
+    ("R_AARCH64_MOVW_UABS_G1_NC", "Bits 16 to 31 of the symbol's address for a `movk`, without an overflow check"),
+    ("R_AARCH64_MOVW_UABS_G2", "Bits 32 to 47 of the symbol's address for a `movz` or `movk`"),
+    ("R_AARCH64_MOVW_UABS_G2_NC", "Bits 32 to 47 of the symbol's address for a `movk`, without an overflow check"),
+    ("R_AARCH64_MOVW_UABS_G3", "Bits 48 to 63 of the symbol's address for a `movz` or `movk`"),
+    ("R_AARCH64_ADR_GOT_PAGE", "The offset from this page to the 4KB page of the symbol's GOT entry for an `adrp`"),
+    ("R_AARCH64_LD64_GOT_LO12_NC", "The low 12 bits of the address of the symbol's GOT entry for an `ldr`"),
+    ("R_AARCH64_TLSDESC_ADR_PAGE21", "The offset from this page to the 4KB page of the symbol's TLS descriptor for an `adrp`"),
+    ("R_AARCH64_TLSDESC_LD64_LO12", "The low 12 bits of the address of the symbol's TLS descriptor for an `ldr`"),
+    ("R_AARCH64_TLSDESC_ADD_LO12", "The low 12 bits of the address of the symbol's TLS descriptor for an `add`"),
+    ("R_AARCH64_TLSDESC_CALL", "Marks the call to the TLS descriptor's resolver so the linker can relax it"),
+    ("R_AARCH64_COPY", "Copies the symbol's data from the shared object at load time"),
+    ("R_AARCH64_GLOB_DAT", "Sets a GOT entry to the address of the symbol at load time"),
+    ("R_AARCH64_JUMP_SLOT", "Sets a PLT entry's GOT slot to the address of the symbol at load time"),
+    ("R_AARCH64_RELATIVE", "Adds the load address of the object at load time"),
+    ("R_ARM_NONE", "No relocation"),
+    ("R_ARM_ABS32", "The 32-bit address of the symbol"),
+    ("R_ARM_REL32", "The 32-bit offset from here to the symbol"),
+    ("R_ARM_CALL", "The offset from here to the symbol for a `bl` or `blx`"),
+    ("R_ARM_JUMP24", "The offset from here to the symbol for a `b`"),
+    ("R_ARM_THM_CALL", "The offset from here to the symbol for a Thumb `bl` or `blx`"),
+    ("R_ARM_THM_JUMP24", "The offset from here to the symbol for a Thumb `b.w`"),
+    ("R_ARM_MOVW_ABS_NC", "The low 16 bits of the symbol's address for a `movw`"),
+    ("R_ARM_MOVT_ABS", "The high 16 bits of the symbol's address for a `movt`"),
+    ("R_ARM_THM_MOVW_ABS_NC", "The low 16 bits of the symbol's address for a Thumb `movw`"),
+    ("R_ARM_THM_MOVT_ABS", "The high 16 bits of the symbol's address for a Thumb `movt`"),
+    ("R_ARM_GOT_BREL", "The offset of the symbol's GOT entry from the start of the GOT"),
+    ("R_ARM_GOT_PREL", "The offset from here to the symbol's GOT entry"),
+    ("R_ARM_V4BX", "Marks a `bx` so the linker can replace it for ARMv4"),
+    ("R_RISCV_NONE", "No relocation"),
+    ("R_RISCV_32", "The 32-bit address of the symbol"),
+    ("R_RISCV_64", "The 64-bit address of the symbol"),
+    ("R_RISCV_BRANCH", "The offset from here to the symbol for a conditional branch"),
+    ("R_RISCV_JAL", "The offset from here to the symbol for a `jal`"),
+    ("R_RISCV_CALL", "The offset from here to the symbol for an `auipc` and `jalr` pair"),
+    ("R_RISCV_CALL_PLT", "The offset from here to the symbol's PLT entry for an `auipc` and `jalr` pair"),
+    ("R_RISCV_GOT_HI20", "The high 20 bits of the offset from here to the symbol's GOT entry for an `auipc`"),
+    ("R_RISCV_PCREL_HI20", "The high 20 bits of the offset from here to the symbol for an `auipc`"),
+    ("R_RISCV_PCREL_LO12_I", "The low 12 bits of the offset from the `auipc` to the symbol for an I-type instruction"),
+    ("R_RISCV_PCREL_LO12_S", "The low 12 bits of the offset from the `auipc` to the symbol for an S-type instruction"),
+    ("R_RISCV_HI20", "The high 20 bits of the symbol's address for a `lui`"),
+    ("R_RISCV_LO12_I", "The low 12 bits of the symbol's address for an I-type instruction"),
+    ("R_RISCV_LO12_S", "The low 12 bits of the symbol's address for an S-type instruction"),
+    ("R_RISCV_RVC_BRANCH", "The offset from here to the symbol for a compressed conditional branch"),
+    ("R_RISCV_RVC_JUMP", "The offset from here to the symbol for a compressed jump"),
+    ("R_RISCV_RELAX", "The linker can relax the instruction that the previous relocation applies to"),
+    ("R_RISCV_ALIGN", "Padding that the linker removes to align the next instruction"),
+    ("R_PPC64_NONE", "No relocation"),
+    ("R_PPC64_ADDR64", "The 64-bit address of the symbol"),
+    ("R_PPC64_ADDR32", "The 32-bit address of the symbol"),
+    ("R_PPC64_REL24", "The offset from here to the symbol for a `bl`"),
+    ("R_PPC64_REL32", "The 32-bit offset from here to the symbol"),
+    ("R_PPC64_REL64", "The 64-bit offset from here to the symbol"),
+    ("R_PPC64_REL16_HA", "The adjusted high 16 bits of the offset from here to the symbol"),
+    ("R_PPC64_REL16_LO", "The low 16 bits of the offset from here to the symbol"),
+    ("R_PPC64_TOC16_HA", "The adjusted high 16 bits of the symbol's offset from the TOC"),
+    ("R_PPC64_TOC16_LO", "The low 16 bits of the symbol's offset from the TOC"),
+    ("R_PPC64_TOC16_LO_DS", "The low 16 bits of the symbol's offset from the TOC for a DS-form instruction"),
+    ("R_PPC64_TOC", "The address of the TOC"),
+];
+
+/// The architecture that the relocation is for e.g. `x86-64`
+pub(super) fn architecture(name: &str) -> Option<&'static str> {
+    ARCHITECTURES
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, architecture)| *architecture)
+}
+
+pub(super) fn description(name: &str) -> Option<&'static str> {
+    RELOCATIONS
+        .iter()
+        .find(|(relocation, _)| *relocation == name)
+        .map(|(_, description)| *description)
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use rowan::TextSize;
+use syntax::ast::{AstNode, Directive, SyntaxNode};
+
+/// How the symbol is visible to the linker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Binding {
+    #[default]
+    Local,
+    Global,
+    Weak,
+}
+
+/// How the symbol is visible outside of the shared object that defines it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Visibility {
+    #[default]
+    Default,
+    Hidden,
+    Protected,
+    Internal,
+}
+
+/// The symbol type set with `.type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SymbolType {
+    Function,
+    IndirectFunction,
+    Object,
+    TlsObject,
+    Common,
+    NoType,
+}
+
+/// What the linkage directives in the file say about a symbol
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct Symbol {
+    pub binding: Binding,
+    pub visibility: Visibility,
+    pub kind: Option<SymbolType>,
+    /// The expression given to `.size` and where it's written, this usually
+    /// refers to labels e.g. `.Lfunc_end0-foo`
+    pub size: Option<(String, TextSize)>,
+}
+
+/// The symbols of a file that have been named in a `.globl`, `.weak`,
+/// `.local`, `.hidden`, `.protected`, `.internal`, `.type` or `.size`
+/// directive.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct SymbolTable {
+    symbols: HashMap<String, Symbol>,
+}
+
+impl SymbolTable {
+    pub(crate) fn new(tree: &SyntaxNode) -> Self {
+        let mut symbols: HashMap<String, Symbol> = HashMap::new();
+
+        for directive in tree.descendants().filter_map(Directive::cast) {
+            let Some(name) = directive.name() else {
+                continue;
+            };
+            let operands = directive.operands();
+            let mut names = operands.iter().map(|o| o.text());
+
+            match name.as_str() {
+                ".globl" | ".global" | ".weak" | ".local" => {
+                    let binding = match name.as_str() {
+                        ".weak" => Binding::Weak,
+                        ".local" => Binding::Local,
+                        _ => Binding::Global,
+                    };
+                    for name in names {
+                        let symbol = symbols.entry(name).or_default();
+                        // A weak symbol is still weak if it's also global
+                        if symbol.binding != Binding::Weak {
+                            symbol.binding = binding;
+                        }
+                    }
+                }
+                ".hidden" | ".protected" | ".internal" => {
+                    let visibility = match name.as_str() {
+                        ".hidden" => Visibility::Hidden,
+                        ".protected" => Visibility::Protected,
+                        _ => Visibility::Internal,
+                    };
+                    for name in names {
+                        symbols.entry(name).or_default().visibility = visibility;
+                    }
+                }
+                ".type" => {
+                    let (Some(name), Some(kind)) = (names.next(), names.next()) else {
+                        continue;
+                    };
+                    if let Some(kind) = SymbolType::from_text(&kind) {
+                        symbols.entry(name).or_default().kind = Some(kind);
+                    }
+                }
+                ".size" => {
+                    let (Some(name), Some(size)) = (names.next(), operands.get(1)) else {
+                        continue;
+                    };
+                    symbols.entry(name).or_default().size =
+                        Some((size.text(), size.text_range().start()));
+                }
+                _ => {}
+            }
+        }
+
+        Self { symbols }
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.get(name)
+    }
+}
+
+impl SymbolType {
+    /// Parses the type from a `.type` directive, this can be written as
+    /// `@function`, `%function`, `#function`, `"function"` or `STT_FUNC`
+    fn from_text(text: &str) -> Option<Self> {
+        let text = text.trim_matches('"').trim_start_matches(['@', '%', '#']);
+
+        Some(match text {
+            "function" | "STT_FUNC" => Self::Function,
+            "gnu_indirect_function" | "STT_GNU_IFUNC" => Self::IndirectFunction,
+            "object" | "STT_OBJECT" => Self::Object,
+            "tls_object" | "STT_TLS" => Self::TlsObject,
+            "common" | "STT_COMMON" => Self::Common,
+            "notype" | "STT_NOTYPE" => Self::NoType,
+            _ => return None,
+        })
+    }
+}
+
+/// A short description for the document symbols e.g. `global hidden function`
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.binding)?;
+        if self.visibility != Visibility::Default {
+            write!(f, " {}", self.visibility)?;
+        }
+        if let Some(kind) = self.kind {
+            write!(f, " {kind}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Binding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Binding::Local => "local",
+            Binding::Global => "global",
+            Binding::Weak => "weak",
+        })
+    }
+}
+
+impl Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Visibility::Default => "default",
+            Visibility::Hidden => "hidden",
+            Visibility::Protected => "protected",
+            Visibility::Internal => "internal",
+        })
+    }
+}
+
+impl Display for SymbolType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SymbolType::Function => "function",
+            SymbolType::IndirectFunction => "indirect function",
+            SymbolType::Object => "object",
+            SymbolType::TlsObject => "TLS object",
+            SymbolType::Common => "common",
+            SymbolType::NoType => "no type",
+        })
+    }
+}
@@ -0,0 +1,142 @@
+use crate::assert_listing;
+
+#[test]
+fn test_thumb_register_list() {
+    assert_listing!(
+        r#"	.syntax unified
+	.cpu cortex-m4
+	.thumb
+Reset_Handler:
+	push	{r4-r11, lr}
+	ldr	r0, =_estack @ stack top
+	stmdb	sp!, {r4, r5}"#,
+        r#"ROOT@0..125
+  WHITESPACE@0..1 "\t"
+  DIRECTIVE@1..16
+    MNEMONIC@1..8 ".syntax"
+    WHITESPACE@8..9 " "
+    TOKEN@9..16 "unified"
+  WHITESPACE@16..18 "\n\t"
+  DIRECTIVE@18..32
+    MNEMONIC@18..22 ".cpu"
+    WHITESPACE@22..23 " "
+    TOKEN@23..29 "cortex"
+    OPERATOR@29..30 "-"
+    TOKEN@30..32 "m4"
+  WHITESPACE@32..34 "\n\t"
+  DIRECTIVE@34..40
+    MNEMONIC@34..40 ".thumb"
+  WHITESPACE@40..41 "\n"
+  LABEL@41..125
+    LABEL@41..55 "Reset_Handler:"
+    WHITESPACE@55..57 "\n\t"
+    INSTRUCTION@57..74
+      MNEMONIC@57..61 "push"
+      WHITESPACE@61..62 "\t"
+      BRACKETS@62..74
+        L_CURLY@62..63 "{"
+        REGISTER@63..65 "r4"
+        OPERATOR@65..66 "-"
+        REGISTER@66..69 "r11"
+        COMMA@69..70 ","
+        WHITESPACE@70..71 " "
+        REGISTER@71..73 "lr"
+        R_CURLY@73..74 "}"
+    WHITESPACE@74..76 "\n\t"
+    INSTRUCTION@76..104
+      MNEMONIC@76..79 "ldr"
+      WHITESPACE@79..80 "\t"
+      REGISTER@80..82 "r0"
+      COMMA@82..83 ","
+      WHITESPACE@83..84 " "
+      OPERATOR@84..85 "="
+      TOKEN@85..92 "_estack"
+      WHITESPACE@92..93 " "
+      COMMENT@93..104 "@ stack top"
+    WHITESPACE@104..106 "\n\t"
+    INSTRUCTION@106..125
+      MNEMONIC@106..111 "stmdb"
+      WHITESPACE@111..112 "\t"
+      REGISTER@112..114 "sp"
+      TOKEN@114..115 "!"
+      COMMA@115..116 ","
+      WHITESPACE@116..117 " "
+      BRACKETS@117..125
+        L_CURLY@117..118 "{"
+        REGISTER@118..120 "r4"
+        COMMA@120..121 ","
+        WHITESPACE@121..122 " "
+        REGISTER@122..124 "r5"
+        R_CURLY@124..125 "}"
+"#
+    );
+}
+
+#[test]
+fn test_objdump_detection() {
+    assert_listing!(
+        r#"
+a.out:     file format elf32-littlearm
+
+
+Disassembly of section .text:
+
+00008000 <main>:
+    8000:	e92d4800 	push	{fp, lr}
+    8004:	e59f0004 	ldr	r0, [pc, #4]	@ 8010 <main+0x10>
+"#,
+        r##"ROOT@0..180
+  WHITESPACE@0..1 "\n"
+  METADATA@1..39 "a.out:     file forma ..."
+  WHITESPACE@39..42 "\n\n\n"
+  METADATA@42..71 "Disassembly of sectio ..."
+  WHITESPACE@71..73 "\n\n"
+  LABEL@73..180
+    OBJDUMP_OFFSET@73..81 "00008000"
+    WHITESPACE@81..82 " "
+    LABEL@82..89 "<main>:"
+    WHITESPACE@89..94 "\n    "
+    INSTRUCTION@94..123
+      OBJDUMP_OFFSET@94..98 "8000"
+      METADATA@98..99 ":"
+      WHITESPACE@99..100 "\t"
+      METADATA@100..109 "e92d4800 "
+      WHITESPACE@109..110 "\t"
+      MNEMONIC@110..114 "push"
+      WHITESPACE@114..115 "\t"
+      BRACKETS@115..123
+        L_CURLY@115..116 "{"
+        REGISTER@116..118 "fp"
+        COMMA@118..119 ","
+        WHITESPACE@119..120 " "
+        REGISTER@120..122 "lr"
+        R_CURLY@122..123 "}"
+    WHITESPACE@123..128 "\n    "
+    INSTRUCTION@128..179
+      OBJDUMP_OFFSET@128..132 "8004"
+      METADATA@132..133 ":"
+      WHITESPACE@133..134 "\t"
+      METADATA@134..143 "e59f0004 "
+      WHITESPACE@143..144 "\t"
+      MNEMONIC@144..147 "ldr"
+      WHITESPACE@147..148 "\t"
+      REGISTER@148..150 "r0"
+      COMMA@150..151 ","
+      WHITESPACE@151..152 " "
+      MEMORY_OPERAND@152..160
+        BRACKETS@152..160
+          L_SQ@152..153 "["
+          BASE@153..155
+            REGISTER@153..155 "pc"
+          COMMA@155..156 ","
+          WHITESPACE@156..157 " "
+          DISPLACEMENT@157..159
+            IMMEDIATE@157..158 "#"
+            NUMBER@158..159 "4"
+          R_SQ@159..160 "]"
+      WHITESPACE@160..161 "\t"
+      COMMENT@161..179 "@ 8010 <main+0x10>"
+    WHITESPACE@179..180 "\n"
+"##
+    );
+}
@@ -15,14 +15,18 @@ fn test_sq_brackets() {
     REGISTER@9..12 "x30"
     COMMA@12..13 ","
     WHITESPACE@13..14 " "
-    BRACKETS@14..23
-      L_SQ@14..15 "["
-      REGISTER@15..17 "sp"
-      COMMA@17..18 ","
-      WHITESPACE@18..19 " "
-      NUMBER@19..22 "-32"
-      R_SQ@22..23 "]"
-    TOKEN@23..24 "!"
+    MEMORY_OPERAND@14..24
+      BRACKETS@14..23
+        L_SQ@14..15 "["
+        BASE@15..17
+          REGISTER@15..17 "sp"
+        COMMA@17..18 ","
+        WHITESPACE@18..19 " "
+        DISPLACEMENT@19..22
+          NUMBER@19..22 "-32"
+        R_SQ@22..23 "]"
+      WRITEBACK@23..24
+        TOKEN@23..24 "!"
 "#,
         Architecture::AArch64
     );
@@ -54,14 +58,18 @@ end:
       REGISTER@35..38 "x21"
       COMMA@38..39 ","
       WHITESPACE@39..40 " "
-      BRACKETS@40..49
-        L_SQ@40..41 "["
-        REGISTER@41..43 "sp"
-        COMMA@43..44 ","
-        WHITESPACE@44..45 " "
-        NUMBER@45..48 "-32"
-        R_SQ@48..49 "]"
-      TOKEN@49..50 "!"
+      MEMORY_OPERAND@40..50
+        BRACKETS@40..49
+          L_SQ@40..41 "["
+          BASE@41..43
+            REGISTER@41..43 "sp"
+          COMMA@43..44 ","
+          WHITESPACE@44..45 " "
+          DISPLACEMENT@45..48
+            NUMBER@45..48 "-32"
+          R_SQ@48..49 "]"
+        WRITEBACK@49..50
+          TOKEN@49..50 "!"
     WHITESPACE@50..51 "\n"
     LOCAL_LABEL@51..66
       LABEL@51..55 ".L2:"
@@ -123,15 +131,19 @@ fn test_negative_imm() {
     REGISTER@9..12 "x30"
     COMMA@12..13 ","
     WHITESPACE@13..14 " "
-    BRACKETS@14..24
-      L_SQ@14..15 "["
-      REGISTER@15..17 "sp"
-      COMMA@17..18 ","
-      WHITESPACE@18..19 " "
-      IMMEDIATE@19..20 "#"
-      NUMBER@20..23 "-32"
-      R_SQ@23..24 "]"
-    TOKEN@24..25 "!"
+    MEMORY_OPERAND@14..25
+      BRACKETS@14..24
+        L_SQ@14..15 "["
+        BASE@15..17
+          REGISTER@15..17 "sp"
+        COMMA@17..18 ","
+        WHITESPACE@18..19 " "
+        DISPLACEMENT@19..23
+          IMMEDIATE@19..20 "#"
+          NUMBER@20..23 "-32"
+        R_SQ@23..24 "]"
+      WRITEBACK@24..25
+        TOKEN@24..25 "!"
 "##,
         Architecture::AArch64
     );
@@ -148,14 +160,17 @@ fn test_arm_comment() {
     REGISTER@4..6 "d0"
     COMMA@6..7 ","
     WHITESPACE@7..8 " "
-    BRACKETS@8..28
-      L_SQ@8..9 "["
-      REGISTER@9..11 "x9"
-      COMMA@11..12 ","
-      WHITESPACE@12..13 " "
-      RELOCATION@13..19 ":lo12:"
-      TOKEN@19..27 ".LCPI0_0"
-      R_SQ@27..28 "]"
+    MEMORY_OPERAND@8..28
+      BRACKETS@8..28
+        L_SQ@8..9 "["
+        BASE@9..11
+          REGISTER@9..11 "x9"
+        COMMA@11..12 ","
+        WHITESPACE@12..13 " "
+        DISPLACEMENT@13..27
+          RELOCATION@13..19 ":lo12:"
+          TOKEN@19..27 ".LCPI0_0"
+        R_SQ@27..28 "]"
     WHITESPACE@28..29 " "
     COMMENT@29..39 "// Comment"
 "#,
@@ -291,6 +306,64 @@ orr x1, x1, two"#,
     );
 }
 
+#[test]
+fn test_unreq() {
+    assert_listing!(
+        r#"count .req x0
+.unreq count
+mov count, #1"#,
+        r##"ROOT@0..40
+  ALIAS@0..13
+    REGISTER_ALIAS@0..5 "count"
+    WHITESPACE@5..6 " "
+    MNEMONIC@6..10 ".req"
+    WHITESPACE@10..11 " "
+    REGISTER@11..13 "x0"
+  WHITESPACE@13..14 "\n"
+  DIRECTIVE@14..26
+    MNEMONIC@14..20 ".unreq"
+    WHITESPACE@20..21 " "
+    REGISTER_ALIAS@21..26 "count"
+  WHITESPACE@26..27 "\n"
+  INSTRUCTION@27..40
+    MNEMONIC@27..30 "mov"
+    WHITESPACE@30..31 " "
+    TOKEN@31..36 "count"
+    COMMA@36..37 ","
+    WHITESPACE@37..38 " "
+    IMMEDIATE@38..39 "#"
+    NUMBER@39..40 "1"
+"##,
+        Architecture::AArch64
+    );
+}
+
+#[test]
+fn test_assignment() {
+    assert_listing!(
+        r#"SIZE = 16
+mov x0, SIZE"#,
+        r#"ROOT@0..22
+  CONST_DEF@0..9
+    NAME@0..4 "SIZE"
+    WHITESPACE@4..5 " "
+    MNEMONIC@5..6 "="
+    EXPR@6..9
+      WHITESPACE@6..7 " "
+      NUMBER@7..9 "16"
+  WHITESPACE@9..10 "\n"
+  INSTRUCTION@10..22
+    MNEMONIC@10..13 "mov"
+    WHITESPACE@13..14 " "
+    REGISTER@14..16 "x0"
+    COMMA@16..17 ","
+    WHITESPACE@17..18 " "
+    CONSTANT@18..22 "SIZE"
+"#,
+        Architecture::AArch64
+    );
+}
+
 #[test]
 fn test_arm_relocation() {
     assert_listing!(
@@ -302,14 +375,17 @@ fn test_arm_relocation() {
     REGISTER@4..6 "d0"
     COMMA@6..7 ","
     WHITESPACE@7..8 " "
-    BRACKETS@8..28
-      L_SQ@8..9 "["
-      REGISTER@9..11 "x9"
-      COMMA@11..12 ","
-      WHITESPACE@12..13 " "
-      RELOCATION@13..19 ":lo12:"
-      TOKEN@19..27 ".LCPI0_0"
-      R_SQ@27..28 "]"
+    MEMORY_OPERAND@8..28
+      BRACKETS@8..28
+        L_SQ@8..9 "["
+        BASE@9..11
+          REGISTER@9..11 "x9"
+        COMMA@11..12 ","
+        WHITESPACE@12..13 " "
+        DISPLACEMENT@13..27
+          RELOCATION@13..19 ":lo12:"
+          TOKEN@19..27 ".LCPI0_0"
+        R_SQ@27..28 "]"
 "#,
         Architecture::AArch64
     );
@@ -326,14 +402,34 @@ fn test_arm_relocation_incomplete() {
     REGISTER@4..6 "d0"
     COMMA@6..7 ","
     WHITESPACE@7..8 " "
-    BRACKETS@8..20
-      L_SQ@8..9 "["
-      REGISTER@9..11 "x9"
-      COMMA@11..12 ","
-      WHITESPACE@12..13 " "
-      TOKEN@13..18 ":lo12"
-      WHITESPACE@18..19 " "
-      R_SQ@19..20 "]"
+    MEMORY_OPERAND@8..20
+      BRACKETS@8..20
+        L_SQ@8..9 "["
+        BASE@9..11
+          REGISTER@9..11 "x9"
+        COMMA@11..12 ","
+        WHITESPACE@12..13 " "
+        DISPLACEMENT@13..18
+          TOKEN@13..18 ":lo12"
+        WHITESPACE@18..19 " "
+        R_SQ@19..20 "]"
+"#,
+        Architecture::AArch64
+    );
+}
+
+#[test]
+fn test_literal_pool_load() {
+    assert_listing!(
+        "ldr x0, =sym",
+        r#"ROOT@0..12
+  INSTRUCTION@0..12
+    MNEMONIC@0..3 "ldr"
+    WHITESPACE@3..4 " "
+    REGISTER@4..6 "x0"
+    COMMA@6..7 ","
+    WHITESPACE@7..8 " "
+    TOKEN@8..12 "=sym"
 "#,
         Architecture::AArch64
     );
@@ -75,3 +75,222 @@ fn test_label_mnemonic_line_2() {
 "#
     );
 }
+
+#[test]
+fn test_macro() {
+    assert_listing!(
+        r#".macro inc_counter name, amount
+1:
+	add \name, \name, \amount
+.endm
+label:
+	inc_counter foo, 2"#,
+        r#"ROOT@0..94
+  MACRO_DEF@0..67
+    MNEMONIC@0..6 ".macro"
+    WHITESPACE@6..7 " "
+    NAME@7..18 "inc_counter"
+    WHITESPACE@18..19 " "
+    TOKEN@19..23 "name"
+    COMMA@23..24 ","
+    WHITESPACE@24..25 " "
+    TOKEN@25..31 "amount"
+    WHITESPACE@31..32 "\n"
+    LABEL@32..62
+      LABEL@32..34 "1:"
+      WHITESPACE@34..36 "\n\t"
+      INSTRUCTION@36..61
+        MNEMONIC@36..39 "add"
+        WHITESPACE@39..40 " "
+        TOKEN@40..45 "\\name"
+        COMMA@45..46 ","
+        WHITESPACE@46..47 " "
+        TOKEN@47..52 "\\name"
+        COMMA@52..53 ","
+        WHITESPACE@53..54 " "
+        TOKEN@54..61 "\\amount"
+      WHITESPACE@61..62 "\n"
+    DIRECTIVE@62..67
+      MNEMONIC@62..67 ".endm"
+  WHITESPACE@67..68 "\n"
+  LABEL@68..94
+    LABEL@68..74 "label:"
+    WHITESPACE@74..76 "\n\t"
+    MACRO_CALL@76..94
+      MNEMONIC@76..87 "inc_counter"
+      WHITESPACE@87..88 " "
+      TOKEN@88..91 "foo"
+      COMMA@91..92 ","
+      WHITESPACE@92..93 " "
+      NUMBER@93..94 "2"
+"#
+    );
+}
+
+#[test]
+fn test_conditional() {
+    assert_listing!(
+        r#".equ VARIANT, 2
+.ifeq VARIANT
+	nop
+.elseif VARIANT
+label:
+	nop
+.else
+	nop
+.endif"#,
+        r#"ROOT@0..80
+  CONST_DEF@0..15
+    MNEMONIC@0..4 ".equ"
+    WHITESPACE@4..5 " "
+    NAME@5..12 "VARIANT"
+    COMMA@12..13 ","
+    EXPR@13..15
+      WHITESPACE@13..14 " "
+      NUMBER@14..15 "2"
+  WHITESPACE@15..16 "\n"
+  CONDITIONAL@16..80
+    CONDITIONAL_BRANCH@16..35
+      DIRECTIVE@16..29
+        MNEMONIC@16..21 ".ifeq"
+        WHITESPACE@21..22 " "
+        CONSTANT@22..29 "VARIANT"
+      WHITESPACE@29..31 "\n\t"
+      INSTRUCTION@31..34
+        MNEMONIC@31..34 "nop"
+      WHITESPACE@34..35 "\n"
+    CONDITIONAL_BRANCH@35..63
+      DIRECTIVE@35..50
+        MNEMONIC@35..42 ".elseif"
+        WHITESPACE@42..43 " "
+        CONSTANT@43..50 "VARIANT"
+      WHITESPACE@50..51 "\n"
+      LABEL@51..63
+        LABEL@51..57 "label:"
+        WHITESPACE@57..59 "\n\t"
+        INSTRUCTION@59..62
+          MNEMONIC@59..62 "nop"
+        WHITESPACE@62..63 "\n"
+    CONDITIONAL_BRANCH@63..74
+      DIRECTIVE@63..68
+        MNEMONIC@63..68 ".else"
+      WHITESPACE@68..70 "\n\t"
+      INSTRUCTION@70..73
+        MNEMONIC@70..73 "nop"
+      WHITESPACE@73..74 "\n"
+    DIRECTIVE@74..80
+      MNEMONIC@74..80 ".endif"
+"#
+    );
+}
+
+#[test]
+fn test_preprocessor() {
+    assert_listing!(
+        r#"#include <asm/linkage.h>
+#define SIZE 16
+#define ALIGN(x) x
+#if defined(SIZE)
+	nop
+#else
+	nop
+#endif"#,
+        r##"ROOT@0..100
+  DIRECTIVE@0..24
+    MNEMONIC@0..8 "#include"
+    WHITESPACE@8..9 " "
+    TOKEN@9..24 "<asm/linkage.h>"
+  WHITESPACE@24..25 "\n"
+  CONST_DEF@25..40
+    MNEMONIC@25..32 "#define"
+    WHITESPACE@32..33 " "
+    NAME@33..37 "SIZE"
+    WHITESPACE@37..38 " "
+    EXPR@38..40
+      NUMBER@38..40 "16"
+  WHITESPACE@40..41 "\n"
+  DIRECTIVE@41..59
+    MNEMONIC@41..48 "#define"
+    WHITESPACE@48..49 " "
+    TOKEN@49..54 "ALIGN"
+    BRACKETS@54..57
+      L_PAREN@54..55 "("
+      TOKEN@55..56 "x"
+      R_PAREN@56..57 ")"
+    WHITESPACE@57..58 " "
+    TOKEN@58..59 "x"
+  WHITESPACE@59..60 "\n"
+  CONDITIONAL@60..100
+    CONDITIONAL_BRANCH@60..83
+      DIRECTIVE@60..77
+        MNEMONIC@60..63 "#if"
+        WHITESPACE@63..64 " "
+        TOKEN@64..71 "defined"
+        BRACKETS@71..77
+          L_PAREN@71..72 "("
+          CONSTANT@72..76 "SIZE"
+          R_PAREN@76..77 ")"
+      WHITESPACE@77..79 "\n\t"
+      INSTRUCTION@79..82
+        MNEMONIC@79..82 "nop"
+      WHITESPACE@82..83 "\n"
+    CONDITIONAL_BRANCH@83..94
+      DIRECTIVE@83..88
+        MNEMONIC@83..88 "#else"
+      WHITESPACE@88..90 "\n\t"
+      INSTRUCTION@90..93
+        MNEMONIC@90..93 "nop"
+      WHITESPACE@93..94 "\n"
+    DIRECTIVE@94..100
+      MNEMONIC@94..100 "#endif"
+"##
+    );
+}
+
+#[test]
+fn test_sections() {
+    assert_listing!(
+        r#".text
+entry:
+	nop
+.section .rodata
+.pushsection .data
+value:
+.popsection
+	.byte 1"#,
+        r#"ROOT@0..81
+  SECTION@0..18
+    DIRECTIVE@0..5
+      MNEMONIC@0..5 ".text"
+    WHITESPACE@5..6 "\n"
+    LABEL@6..18
+      LABEL@6..12 "entry:"
+      WHITESPACE@12..14 "\n\t"
+      INSTRUCTION@14..17
+        MNEMONIC@14..17 "nop"
+      WHITESPACE@17..18 "\n"
+  SECTION@18..81
+    DIRECTIVE@18..34
+      MNEMONIC@18..26 ".section"
+      WHITESPACE@26..27 " "
+      TOKEN@27..34 ".rodata"
+    WHITESPACE@34..35 "\n"
+    SECTION@35..61
+      DIRECTIVE@35..53
+        MNEMONIC@35..47 ".pushsection"
+        WHITESPACE@47..48 " "
+        TOKEN@48..53 ".data"
+      WHITESPACE@53..54 "\n"
+      LABEL@54..61
+        LABEL@54..60 "value:"
+        WHITESPACE@60..61 "\n"
+    DIRECTIVE@61..72
+      MNEMONIC@61..72 ".popsection"
+    WHITESPACE@72..74 "\n\t"
+    DIRECTIVE@74..81
+      MNEMONIC@74..79 ".byte"
+      WHITESPACE@79..80 " "
+      NUMBER@80..81 "1"
+"#
+    );
+}
@@ -35,9 +35,11 @@ fn test_unclosed_bracket() {
     REGISTER@4..6 "w8"
     COMMA@6..7 ","
     WHITESPACE@7..8 " "
-    BRACKETS@8..11
-      L_SQ@8..9 "["
-      REGISTER@9..11 "sp"
+    MEMORY_OPERAND@8..11
+      BRACKETS@8..11
+        L_SQ@8..9 "["
+        BASE@9..11
+          REGISTER@9..11 "sp"
 "#,
         Architecture::AArch64
     );
@@ -82,3 +84,50 @@ comment
         Architecture::X86_64
     );
 }
+
+#[test]
+fn test_unterminated_string() {
+    assert_listing!(
+        r#".ascii "text
+mov x0, x1"#,
+        r#"ROOT@0..23
+  DIRECTIVE@0..12
+    MNEMONIC@0..6 ".ascii"
+    WHITESPACE@6..7 " "
+    ERROR@7..12
+      STRING@7..12 "\"text"
+  WHITESPACE@12..13 "\n"
+  INSTRUCTION@13..23
+    MNEMONIC@13..16 "mov"
+    WHITESPACE@16..17 " "
+    REGISTER@17..19 "x0"
+    COMMA@19..20 ","
+    WHITESPACE@20..21 " "
+    REGISTER@21..23 "x1"
+"#,
+        Architecture::AArch64
+    );
+}
+
+#[test]
+fn test_unexpected_bracket() {
+    assert_listing!(
+        "add x0, x0, #1)",
+        r##"ROOT@0..15
+  INSTRUCTION@0..15
+    MNEMONIC@0..3 "add"
+    WHITESPACE@3..4 " "
+    REGISTER@4..6 "x0"
+    COMMA@6..7 ","
+    WHITESPACE@7..8 " "
+    REGISTER@8..10 "x0"
+    COMMA@10..11 ","
+    WHITESPACE@11..12 " "
+    IMMEDIATE@12..13 "#"
+    NUMBER@13..14 "1"
+    ERROR@14..15
+      R_PAREN@14..15 ")"
+"##,
+        Architecture::AArch64
+    );
+}
@@ -1,7 +1,11 @@
+mod aarch32;
 mod aarch64;
 mod asm;
 mod incomplete;
 mod objdump;
+mod powerpc64;
+mod riscv64;
+mod x86;
 mod x86_64;
 
 #[macro_export]
@@ -44,9 +44,9 @@ Disassembly of section .text:
       WHITESPACE@168..169 "\t"
       MNEMONIC@169..172 "xor"
       WHITESPACE@172..176 "    "
-      TOKEN@176..180 "%ebp"
+      REGISTER@176..180 "%ebp"
       COMMA@180..181 ","
-      TOKEN@181..185 "%ebp"
+      REGISTER@181..185 "%ebp"
     WHITESPACE@185..188 "\n  "
     INSTRUCTION@188..233
       OBJDUMP_OFFSET@188..194 "2015a6"
@@ -58,7 +58,7 @@ Disassembly of section .text:
       WHITESPACE@221..225 "    "
       REGISTER@225..229 "%rdx"
       COMMA@229..230 ","
-      TOKEN@230..233 "%r9"
+      REGISTER@230..233 "%r9"
     WHITESPACE@233..236 "\n  "
     INSTRUCTION@236..277
       OBJDUMP_OFFSET@236..242 "2015a9"
@@ -90,7 +90,8 @@ Disassembly of section .text:
       WHITESPACE@358..359 "\t"
       MNEMONIC@359..362 "and"
       WHITESPACE@362..366 "    "
-      NUMBER@366..385 "$0xfffffffffffffff0"
+      IMMEDIATE@366..367 "$"
+      NUMBER@367..385 "0xfffffffffffffff0"
       COMMA@385..386 ","
       REGISTER@386..390 "%rsp"
     WHITESPACE@390..391 "\n"
@@ -321,10 +322,12 @@ Disassembly of section __TEXT,__text:
       REGISTER@265..267 "x8"
       COMMA@267..268 ","
       WHITESPACE@268..269 " "
-      BRACKETS@269..273
-        L_SQ@269..270 "["
-        REGISTER@270..272 "x0"
-        R_SQ@272..273 "]"
+      MEMORY_OPERAND@269..273
+        BRACKETS@269..273
+          L_SQ@269..270 "["
+          BASE@270..272
+            REGISTER@270..272 "x0"
+          R_SQ@272..273 "]"
     WHITESPACE@273..274 "\n"
     INSTRUCTION@274..300
       OBJDUMP_OFFSET@274..283 "10002ad88"
@@ -404,3 +407,229 @@ Disassembly of section __TEXT,__text:
 "#
     );
 }
+
+#[test]
+fn test_objdump_source() {
+    assert_listing!(
+        r#"
+a.out:     file format elf64-x86-64
+
+
+Disassembly of section .text:
+
+0000000000001139 <main>:
+main():
+/home/user/test.c:3
+int main() {
+    1139:	55                   	push   %rbp
+/home/user/test.c:4 (discriminator 1)
+    return 0;
+    113a:	b8 00 00 00 00       	mov    $0x0,%eax
+"#,
+        r#"ROOT@0..281
+  WHITESPACE@0..1 "\n"
+  METADATA@1..36 "a.out:     file forma ..."
+  WHITESPACE@36..39 "\n\n\n"
+  METADATA@39..68 "Disassembly of sectio ..."
+  WHITESPACE@68..70 "\n\n"
+  LABEL@70..281
+    OBJDUMP_OFFSET@70..86 "0000000000001139"
+    WHITESPACE@86..87 " "
+    LABEL@87..94 "<main>:"
+    WHITESPACE@94..95 "\n"
+    METADATA@95..102 "main():"
+    WHITESPACE@102..103 "\n"
+    SOURCE_LOCATION@103..122
+      STRING@103..120 "/home/user/test.c"
+      OPERATOR@120..121 ":"
+      NUMBER@121..122 "3"
+    WHITESPACE@122..123 "\n"
+    SOURCE_LINE@123..135
+      COMMENT@123..135 "int main() {"
+    WHITESPACE@135..140 "\n    "
+    INSTRUCTION@140..179
+      OBJDUMP_OFFSET@140..144 "1139"
+      METADATA@144..145 ":"
+      WHITESPACE@145..146 "\t"
+      METADATA@146..167 "55                   "
+      WHITESPACE@167..168 "\t"
+      MNEMONIC@168..172 "push"
+      WHITESPACE@172..175 "   "
+      REGISTER@175..179 "%rbp"
+    WHITESPACE@179..180 "\n"
+    SOURCE_LOCATION@180..217
+      STRING@180..197 "/home/user/test.c"
+      OPERATOR@197..198 ":"
+      NUMBER@198..199 "4"
+      WHITESPACE@199..200 " "
+      METADATA@200..217 "(discriminator 1)"
+    WHITESPACE@217..222 "\n    "
+    SOURCE_LINE@222..231
+      COMMENT@222..231 "return 0;"
+    WHITESPACE@231..236 "\n    "
+    INSTRUCTION@236..280
+      OBJDUMP_OFFSET@236..240 "113a"
+      METADATA@240..241 ":"
+      WHITESPACE@241..242 "\t"
+      METADATA@242..263 "b8 00 00 00 00       "
+      WHITESPACE@263..264 "\t"
+      MNEMONIC@264..267 "mov"
+      WHITESPACE@267..271 "    "
+      IMMEDIATE@271..272 "$"
+      NUMBER@272..275 "0x0"
+      COMMA@275..276 ","
+      REGISTER@276..280 "%eax"
+    WHITESPACE@280..281 "\n"
+"#
+    );
+}
+
+#[test]
+fn test_objdump_source_hex_label() {
+    assert_listing!(
+        r#"
+a.out:     file format elf64-x86-64
+
+
+Disassembly of section .text:
+
+0000000000001139 <main>:
+add:
+    1139:	55                   	push   %rbp
+bad: return 0;
+    113a:	c3                   	ret
+"#,
+        r#"ROOT@0..195
+  WHITESPACE@0..1 "\n"
+  METADATA@1..36 "a.out:     file forma ..."
+  WHITESPACE@36..39 "\n\n\n"
+  METADATA@39..68 "Disassembly of sectio ..."
+  WHITESPACE@68..70 "\n\n"
+  LABEL@70..195
+    OBJDUMP_OFFSET@70..86 "0000000000001139"
+    WHITESPACE@86..87 " "
+    LABEL@87..94 "<main>:"
+    WHITESPACE@94..95 "\n"
+    SOURCE_LINE@95..99
+      COMMENT@95..99 "add:"
+    WHITESPACE@99..104 "\n    "
+    INSTRUCTION@104..143
+      OBJDUMP_OFFSET@104..108 "1139"
+      METADATA@108..109 ":"
+      WHITESPACE@109..110 "\t"
+      METADATA@110..131 "55                   "
+      WHITESPACE@131..132 "\t"
+      MNEMONIC@132..136 "push"
+      WHITESPACE@136..139 "   "
+      REGISTER@139..143 "%rbp"
+    WHITESPACE@143..144 "\n"
+    SOURCE_LINE@144..158
+      COMMENT@144..158 "bad: return 0;"
+    WHITESPACE@158..163 "\n    "
+    INSTRUCTION@163..194
+      OBJDUMP_OFFSET@163..167 "113a"
+      METADATA@167..168 ":"
+      WHITESPACE@168..169 "\t"
+      METADATA@169..190 "c3                   "
+      WHITESPACE@190..191 "\t"
+      MNEMONIC@191..194 "ret"
+    WHITESPACE@194..195 "\n"
+"#
+    );
+}
+
+#[test]
+fn test_objdump_relocations() {
+    assert_listing!(
+        r#"
+test.o:     file format elf64-x86-64
+
+
+Disassembly of section .text:
+
+0000000000000000 <main>:
+   0:	55                   	push   %rbp
+   1:	e8 00 00 00 00       	call   6 <main+0x6>
+			2: R_X86_64_PLT32	foo-0x4
+   6:	48 8d 05 00 00 00 00 	lea    0x0(%rip),%rax
+			9: R_X86_64_PC32	.rodata
+"#,
+        r#"ROOT@0..291
+  WHITESPACE@0..1 "\n"
+  METADATA@1..37 "test.o:     file form ..."
+  WHITESPACE@37..40 "\n\n\n"
+  METADATA@40..69 "Disassembly of sectio ..."
+  WHITESPACE@69..71 "\n\n"
+  LABEL@71..291
+    OBJDUMP_OFFSET@71..87 "0000000000000000"
+    WHITESPACE@87..88 " "
+    LABEL@88..95 "<main>:"
+    WHITESPACE@95..99 "\n   "
+    INSTRUCTION@99..135
+      OBJDUMP_OFFSET@99..100 "0"
+      METADATA@100..101 ":"
+      WHITESPACE@101..102 "\t"
+      METADATA@102..123 "55                   "
+      WHITESPACE@123..124 "\t"
+      MNEMONIC@124..128 "push"
+      WHITESPACE@128..131 "   "
+      REGISTER@131..135 "%rbp"
+    WHITESPACE@135..139 "\n   "
+    INSTRUCTION@139..212
+      OBJDUMP_OFFSET@139..140 "1"
+      METADATA@140..141 ":"
+      WHITESPACE@141..142 "\t"
+      METADATA@142..163 "e8 00 00 00 00       "
+      WHITESPACE@163..164 "\t"
+      MNEMONIC@164..168 "call"
+      WHITESPACE@168..171 "   "
+      NUMBER@171..172 "6"
+      WHITESPACE@172..173 " "
+      METADATA@173..183
+        BRACKETS@173..183
+          L_ANGLE@173..174 "<"
+          TOKEN@174..178 "main"
+          OPERATOR@178..179 "+"
+          NUMBER@179..182 "0x6"
+          R_ANGLE@182..183 ">"
+      WHITESPACE@183..187 "\n\t\t\t"
+      RELOCATION@187..212
+        OBJDUMP_OFFSET@187..188 "2"
+        METADATA@188..189 ":"
+        WHITESPACE@189..190 " "
+        RELOCATION@190..204 "R_X86_64_PLT32"
+        WHITESPACE@204..205 "\t"
+        TOKEN@205..208 "foo"
+        OPERATOR@208..209 "-"
+        NUMBER@209..212 "0x4"
+    WHITESPACE@212..216 "\n   "
+    INSTRUCTION@216..290
+      OBJDUMP_OFFSET@216..217 "6"
+      METADATA@217..218 ":"
+      WHITESPACE@218..219 "\t"
+      METADATA@219..240 "48 8d 05 00 00 00 00 "
+      WHITESPACE@240..241 "\t"
+      MNEMONIC@241..244 "lea"
+      WHITESPACE@244..248 "    "
+      MEMORY_OPERAND@248..257
+        DISPLACEMENT@248..251
+          NUMBER@248..251 "0x0"
+        BRACKETS@251..257
+          L_PAREN@251..252 "("
+          BASE@252..256
+            REGISTER@252..256 "%rip"
+          R_PAREN@256..257 ")"
+      COMMA@257..258 ","
+      REGISTER@258..262 "%rax"
+      WHITESPACE@262..266 "\n\t\t\t"
+      RELOCATION@266..290
+        OBJDUMP_OFFSET@266..267 "9"
+        METADATA@267..268 ":"
+        WHITESPACE@268..269 " "
+        RELOCATION@269..282 "R_X86_64_PC32"
+        WHITESPACE@282..283 "\t"
+        TOKEN@283..290 ".rodata"
+    WHITESPACE@290..291 "\n"
+"#
+    );
+}
@@ -0,0 +1,240 @@
+use crate::assert_listing;
+
+#[test]
+fn test_bare_registers() {
+    assert_listing!(
+        r#"	.abiversion 2
+	addi 9,9,1
+	stdu 1,-32(1)
+	lfd 0,8(9)
+	cmpwi 7,9,0
+	cmpdi 9,0
+	xxlor 34,0,1"#,
+        r#"ROOT@0..91
+  WHITESPACE@0..1 "\t"
+  DIRECTIVE@1..14
+    MNEMONIC@1..12 ".abiversion"
+    WHITESPACE@12..13 " "
+    NUMBER@13..14 "2"
+  WHITESPACE@14..16 "\n\t"
+  INSTRUCTION@16..26
+    MNEMONIC@16..20 "addi"
+    WHITESPACE@20..21 " "
+    REGISTER@21..22 "9"
+    COMMA@22..23 ","
+    REGISTER@23..24 "9"
+    COMMA@24..25 ","
+    NUMBER@25..26 "1"
+  WHITESPACE@26..28 "\n\t"
+  INSTRUCTION@28..41
+    MNEMONIC@28..32 "stdu"
+    WHITESPACE@32..33 " "
+    REGISTER@33..34 "1"
+    COMMA@34..35 ","
+    MEMORY_OPERAND@35..41
+      DISPLACEMENT@35..38
+        NUMBER@35..38 "-32"
+      BRACKETS@38..41
+        L_PAREN@38..39 "("
+        BASE@39..40
+          REGISTER@39..40 "1"
+        R_PAREN@40..41 ")"
+  WHITESPACE@41..43 "\n\t"
+  INSTRUCTION@43..53
+    MNEMONIC@43..46 "lfd"
+    WHITESPACE@46..47 " "
+    REGISTER@47..48 "0"
+    COMMA@48..49 ","
+    MEMORY_OPERAND@49..53
+      DISPLACEMENT@49..50
+        NUMBER@49..50 "8"
+      BRACKETS@50..53
+        L_PAREN@50..51 "("
+        BASE@51..52
+          REGISTER@51..52 "9"
+        R_PAREN@52..53 ")"
+  WHITESPACE@53..55 "\n\t"
+  INSTRUCTION@55..66
+    MNEMONIC@55..60 "cmpwi"
+    WHITESPACE@60..61 " "
+    REGISTER@61..62 "7"
+    COMMA@62..63 ","
+    REGISTER@63..64 "9"
+    COMMA@64..65 ","
+    NUMBER@65..66 "0"
+  WHITESPACE@66..68 "\n\t"
+  INSTRUCTION@68..77
+    MNEMONIC@68..73 "cmpdi"
+    WHITESPACE@73..74 " "
+    REGISTER@74..75 "9"
+    COMMA@75..76 ","
+    NUMBER@76..77 "0"
+  WHITESPACE@77..79 "\n\t"
+  INSTRUCTION@79..91
+    MNEMONIC@79..84 "xxlor"
+    WHITESPACE@84..85 " "
+    REGISTER@85..87 "34"
+    COMMA@87..88 ","
+    REGISTER@88..89 "0"
+    COMMA@89..90 ","
+    REGISTER@90..91 "1"
+"#
+    );
+}
+
+#[test]
+fn test_bare_registers_brackets() {
+    assert_listing!(
+        r#"	.abiversion 2
+	li 3,(4+8)
+	addi 3,1,(16)
+	ld 3,(8)(1)"#,
+        r#"ROOT@0..54
+  WHITESPACE@0..1 "\t"
+  DIRECTIVE@1..14
+    MNEMONIC@1..12 ".abiversion"
+    WHITESPACE@12..13 " "
+    NUMBER@13..14 "2"
+  WHITESPACE@14..16 "\n\t"
+  INSTRUCTION@16..26
+    MNEMONIC@16..18 "li"
+    WHITESPACE@18..19 " "
+    REGISTER@19..20 "3"
+    COMMA@20..21 ","
+    BRACKETS@21..26
+      L_PAREN@21..22 "("
+      NUMBER@22..23 "4"
+      OPERATOR@23..24 "+"
+      NUMBER@24..25 "8"
+      R_PAREN@25..26 ")"
+  WHITESPACE@26..28 "\n\t"
+  INSTRUCTION@28..41
+    MNEMONIC@28..32 "addi"
+    WHITESPACE@32..33 " "
+    REGISTER@33..34 "3"
+    COMMA@34..35 ","
+    REGISTER@35..36 "1"
+    COMMA@36..37 ","
+    BRACKETS@37..41
+      L_PAREN@37..38 "("
+      NUMBER@38..40 "16"
+      R_PAREN@40..41 ")"
+  WHITESPACE@41..43 "\n\t"
+  INSTRUCTION@43..54
+    MNEMONIC@43..45 "ld"
+    WHITESPACE@45..46 " "
+    REGISTER@46..47 "3"
+    COMMA@47..48 ","
+    MEMORY_OPERAND@48..54
+      DISPLACEMENT@48..51
+        BRACKETS@48..51
+          L_PAREN@48..49 "("
+          NUMBER@49..50 "8"
+          R_PAREN@50..51 ")"
+      BRACKETS@51..54
+        L_PAREN@51..52 "("
+        BASE@52..53
+          REGISTER@52..53 "1"
+        R_PAREN@53..54 ")"
+"#
+    );
+}
+
+#[test]
+fn test_objdump_detection() {
+    assert_listing!(
+        r#"
+a.out:     file format elf64-powerpcle
+
+
+Disassembly of section .text:
+
+0000000000000650 <main>:
+ 650:	f8 ff e1 fb 	std     r31,-8(r1)
+ 654:	d1 ff 21 f8 	stdu    r1,-48(r1)
+ 658:	00 00 3e 81 	lwz     r9,0(r30)
+ 65c:	00 00 89 2f 	cmpwi   cr7,r9,0"#,
+        r#"ROOT@0..246
+  WHITESPACE@0..1 "\n"
+  METADATA@1..39 "a.out:     file forma ..."
+  WHITESPACE@39..42 "\n\n\n"
+  METADATA@42..71 "Disassembly of sectio ..."
+  WHITESPACE@71..73 "\n\n"
+  LABEL@73..246
+    OBJDUMP_OFFSET@73..89 "0000000000000650"
+    WHITESPACE@89..90 " "
+    LABEL@90..97 "<main>:"
+    WHITESPACE@97..99 "\n "
+    INSTRUCTION@99..135
+      OBJDUMP_OFFSET@99..102 "650"
+      METADATA@102..103 ":"
+      WHITESPACE@103..104 "\t"
+      METADATA@104..116 "f8 ff e1 fb "
+      WHITESPACE@116..117 "\t"
+      MNEMONIC@117..120 "std"
+      WHITESPACE@120..125 "     "
+      REGISTER@125..128 "r31"
+      COMMA@128..129 ","
+      MEMORY_OPERAND@129..135
+        DISPLACEMENT@129..131
+          NUMBER@129..131 "-8"
+        BRACKETS@131..135
+          L_PAREN@131..132 "("
+          BASE@132..134
+            REGISTER@132..134 "r1"
+          R_PAREN@134..135 ")"
+    WHITESPACE@135..137 "\n "
+    INSTRUCTION@137..173
+      OBJDUMP_OFFSET@137..140 "654"
+      METADATA@140..141 ":"
+      WHITESPACE@141..142 "\t"
+      METADATA@142..154 "d1 ff 21 f8 "
+      WHITESPACE@154..155 "\t"
+      MNEMONIC@155..159 "stdu"
+      WHITESPACE@159..163 "    "
+      REGISTER@163..165 "r1"
+      COMMA@165..166 ","
+      MEMORY_OPERAND@166..173
+        DISPLACEMENT@166..169
+          NUMBER@166..169 "-48"
+        BRACKETS@169..173
+          L_PAREN@169..170 "("
+          BASE@170..172
+            REGISTER@170..172 "r1"
+          R_PAREN@172..173 ")"
+    WHITESPACE@173..175 "\n "
+    INSTRUCTION@175..210
+      OBJDUMP_OFFSET@175..178 "658"
+      METADATA@178..179 ":"
+      WHITESPACE@179..180 "\t"
+      METADATA@180..192 "00 00 3e 81 "
+      WHITESPACE@192..193 "\t"
+      MNEMONIC@193..196 "lwz"
+      WHITESPACE@196..201 "     "
+      REGISTER@201..203 "r9"
+      COMMA@203..204 ","
+      MEMORY_OPERAND@204..210
+        DISPLACEMENT@204..205
+          NUMBER@204..205 "0"
+        BRACKETS@205..210
+          L_PAREN@205..206 "("
+          BASE@206..209
+            REGISTER@206..209 "r30"
+          R_PAREN@209..210 ")"
+    WHITESPACE@210..212 "\n "
+    INSTRUCTION@212..246
+      OBJDUMP_OFFSET@212..215 "65c"
+      METADATA@215..216 ":"
+      WHITESPACE@216..217 "\t"
+      METADATA@217..229 "00 00 89 2f "
+      WHITESPACE@229..230 "\t"
+      MNEMONIC@230..235 "cmpwi"
+      WHITESPACE@235..238 "   "
+      REGISTER@238..241 "cr7"
+      COMMA@241..242 ","
+      REGISTER@242..244 "r9"
+      COMMA@244..245 ","
+      NUMBER@245..246 "0"
+"#
+    );
+}
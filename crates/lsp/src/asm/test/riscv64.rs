@@ -0,0 +1,118 @@
+use crate::assert_listing;
+
+#[test]
+fn test_attribute_arch_detection() {
+    assert_listing!(
+        r#"	.attribute	arch, "rv64i2p1_m2p0_a2p1_f2p2_d2p2_c2p0"
+	addi	sp, sp, -16
+	sd	ra, 8(sp)
+	fmv.d	fa0, ft1 # comment"#,
+        r##"ROOT@0..111
+  WHITESPACE@0..1 "\t"
+  DIRECTIVE@1..53
+    MNEMONIC@1..11 ".attribute"
+    WHITESPACE@11..12 "\t"
+    TOKEN@12..16 "arch"
+    COMMA@16..17 ","
+    WHITESPACE@17..18 " "
+    STRING@18..53 "\"rv64i2p1_m2p0_a2p1_f ..."
+  WHITESPACE@53..55 "\n\t"
+  INSTRUCTION@55..71
+    MNEMONIC@55..59 "addi"
+    WHITESPACE@59..60 "\t"
+    REGISTER@60..62 "sp"
+    COMMA@62..63 ","
+    WHITESPACE@63..64 " "
+    REGISTER@64..66 "sp"
+    COMMA@66..67 ","
+    WHITESPACE@67..68 " "
+    NUMBER@68..71 "-16"
+  WHITESPACE@71..73 "\n\t"
+  INSTRUCTION@73..85
+    MNEMONIC@73..75 "sd"
+    WHITESPACE@75..76 "\t"
+    REGISTER@76..78 "ra"
+    COMMA@78..79 ","
+    WHITESPACE@79..80 " "
+    MEMORY_OPERAND@80..85
+      DISPLACEMENT@80..81
+        NUMBER@80..81 "8"
+      BRACKETS@81..85
+        L_PAREN@81..82 "("
+        BASE@82..84
+          REGISTER@82..84 "sp"
+        R_PAREN@84..85 ")"
+  WHITESPACE@85..87 "\n\t"
+  INSTRUCTION@87..111
+    MNEMONIC@87..92 "fmv.d"
+    WHITESPACE@92..93 "\t"
+    REGISTER@93..96 "fa0"
+    COMMA@96..97 ","
+    WHITESPACE@97..98 " "
+    REGISTER@98..101 "ft1"
+    WHITESPACE@101..102 " "
+    COMMENT@102..111 "# comment"
+"##
+    );
+}
+
+#[test]
+fn test_objdump_detection() {
+    assert_listing!(
+        r#"
+a.out:     file format elf64-littleriscv
+
+
+Disassembly of section .text:
+
+0000000000010120 <main>:
+   10120:	1141                	addi	sp,sp,-16
+   10122:	e406                	sd	ra,8(sp)
+"#,
+        r#"ROOT@0..189
+  WHITESPACE@0..1 "\n"
+  METADATA@1..41 "a.out:     file forma ..."
+  WHITESPACE@41..44 "\n\n\n"
+  METADATA@44..73 "Disassembly of sectio ..."
+  WHITESPACE@73..75 "\n\n"
+  LABEL@75..189
+    OBJDUMP_OFFSET@75..91 "0000000000010120"
+    WHITESPACE@91..92 " "
+    LABEL@92..99 "<main>:"
+    WHITESPACE@99..103 "\n   "
+    INSTRUCTION@103..145
+      OBJDUMP_OFFSET@103..108 "10120"
+      METADATA@108..109 ":"
+      WHITESPACE@109..110 "\t"
+      METADATA@110..130 "1141                "
+      WHITESPACE@130..131 "\t"
+      MNEMONIC@131..135 "addi"
+      WHITESPACE@135..136 "\t"
+      REGISTER@136..138 "sp"
+      COMMA@138..139 ","
+      REGISTER@139..141 "sp"
+      COMMA@141..142 ","
+      NUMBER@142..145 "-16"
+    WHITESPACE@145..149 "\n   "
+    INSTRUCTION@149..188
+      OBJDUMP_OFFSET@149..154 "10122"
+      METADATA@154..155 ":"
+      WHITESPACE@155..156 "\t"
+      METADATA@156..176 "e406                "
+      WHITESPACE@176..177 "\t"
+      MNEMONIC@177..179 "sd"
+      WHITESPACE@179..180 "\t"
+      REGISTER@180..182 "ra"
+      COMMA@182..183 ","
+      MEMORY_OPERAND@183..188
+        DISPLACEMENT@183..184
+          NUMBER@183..184 "8"
+        BRACKETS@184..188
+          L_PAREN@184..185 "("
+          BASE@185..187
+            REGISTER@185..187 "sp"
+          R_PAREN@187..188 ")"
+    WHITESPACE@188..189 "\n"
+"#
+    );
+}
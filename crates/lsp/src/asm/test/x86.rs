@@ -0,0 +1,102 @@
+use crate::assert_listing;
+
+#[test]
+fn test_m32_listing_detection() {
+    assert_listing!(
+        r#"main:
+	pushl	%ebp
+	movl	%esp, %ebp
+	movl	8(%ebp), %eax
+	movl	%eax, %r8d"#,
+        r#"ROOT@0..71
+  LABEL@0..71
+    LABEL@0..5 "main:"
+    WHITESPACE@5..7 "\n\t"
+    INSTRUCTION@7..17
+      MNEMONIC@7..12 "pushl"
+      WHITESPACE@12..13 "\t"
+      REGISTER@13..17 "%ebp"
+    WHITESPACE@17..19 "\n\t"
+    INSTRUCTION@19..34
+      MNEMONIC@19..23 "movl"
+      WHITESPACE@23..24 "\t"
+      REGISTER@24..28 "%esp"
+      COMMA@28..29 ","
+      WHITESPACE@29..30 " "
+      REGISTER@30..34 "%ebp"
+    WHITESPACE@34..36 "\n\t"
+    INSTRUCTION@36..54
+      MNEMONIC@36..40 "movl"
+      WHITESPACE@40..41 "\t"
+      MEMORY_OPERAND@41..48
+        DISPLACEMENT@41..42
+          NUMBER@41..42 "8"
+        BRACKETS@42..48
+          L_PAREN@42..43 "("
+          BASE@43..47
+            REGISTER@43..47 "%ebp"
+          R_PAREN@47..48 ")"
+      COMMA@48..49 ","
+      WHITESPACE@49..50 " "
+      REGISTER@50..54 "%eax"
+    WHITESPACE@54..56 "\n\t"
+    INSTRUCTION@56..71
+      MNEMONIC@56..60 "movl"
+      WHITESPACE@60..61 "\t"
+      REGISTER@61..65 "%eax"
+      COMMA@65..66 ","
+      WHITESPACE@66..67 " "
+      TOKEN@67..71 "%r8d"
+"#
+    );
+}
+
+#[test]
+fn test_objdump_detection() {
+    assert_listing!(
+        r#"
+a.out:     file format elf32-i386
+
+
+Disassembly of section .text:
+
+08049000 <main>:
+ 8049000:	55                   	push   %ebp
+ 8049001:	89 e5                	mov    %esp,%ebp
+"#,
+        r#"ROOT@0..178
+  WHITESPACE@0..1 "\n"
+  METADATA@1..34 "a.out:     file forma ..."
+  WHITESPACE@34..37 "\n\n\n"
+  METADATA@37..66 "Disassembly of sectio ..."
+  WHITESPACE@66..68 "\n\n"
+  LABEL@68..178
+    OBJDUMP_OFFSET@68..76 "08049000"
+    WHITESPACE@76..77 " "
+    LABEL@77..84 "<main>:"
+    WHITESPACE@84..86 "\n "
+    INSTRUCTION@86..128
+      OBJDUMP_OFFSET@86..93 "8049000"
+      METADATA@93..94 ":"
+      WHITESPACE@94..95 "\t"
+      METADATA@95..116 "55                   "
+      WHITESPACE@116..117 "\t"
+      MNEMONIC@117..121 "push"
+      WHITESPACE@121..124 "   "
+      REGISTER@124..128 "%ebp"
+    WHITESPACE@128..130 "\n "
+    INSTRUCTION@130..177
+      OBJDUMP_OFFSET@130..137 "8049001"
+      METADATA@137..138 ":"
+      WHITESPACE@138..139 "\t"
+      METADATA@139..160 "89 e5                "
+      WHITESPACE@160..161 "\t"
+      MNEMONIC@161..164 "mov"
+      WHITESPACE@164..168 "    "
+      REGISTER@168..172 "%esp"
+      COMMA@172..173 ","
+      REGISTER@173..177 "%ebp"
+    WHITESPACE@177..178 "\n"
+"#
+    );
+}
@@ -155,11 +155,14 @@ fn test_register_brackets() {
     REGISTER@5..9 "%rsi"
     COMMA@9..10 ","
     WHITESPACE@10..11 " "
-    NUMBER@11..14 "-16"
-    BRACKETS@14..20
-      L_PAREN@14..15 "("
-      REGISTER@15..19 "%rbp"
-      R_PAREN@19..20 ")"
+    MEMORY_OPERAND@11..20
+      DISPLACEMENT@11..14
+        NUMBER@11..14 "-16"
+      BRACKETS@14..20
+        L_PAREN@14..15 "("
+        BASE@15..19
+          REGISTER@15..19 "%rbp"
+        R_PAREN@19..20 ")"
 "#,
         Architecture::X86_64
     );
@@ -173,12 +176,16 @@ fn test_relocation_at() {
   INSTRUCTION@0..81
     MNEMONIC@0..5 "callq"
     WHITESPACE@5..6 " "
-    TOKEN@6..66 "*_ZN4core6result19Res ..."
-    RELOCATION@66..75 "@GOTPCREL"
-    BRACKETS@75..81
-      L_PAREN@75..76 "("
-      REGISTER@76..80 "%rip"
-      R_PAREN@80..81 ")"
+    OPERATOR@6..7 "*"
+    MEMORY_OPERAND@7..81
+      DISPLACEMENT@7..75
+        TOKEN@7..66 "_ZN4core6result19Resu ..."
+        RELOCATION@66..75 "@GOTPCREL"
+      BRACKETS@75..81
+        L_PAREN@75..76 "("
+        BASE@76..80
+          REGISTER@76..80 "%rip"
+        R_PAREN@80..81 ")"
 "#,
         Architecture::X86_64
     );
@@ -245,14 +252,15 @@ fn test_asm_with_empty_string() {
     assert_listing!(
         r#".section        .debug_loc,"",@progbits"#,
         r#"ROOT@0..39
-  DIRECTIVE@0..39
-    MNEMONIC@0..8 ".section"
-    WHITESPACE@8..16 "        "
-    TOKEN@16..26 ".debug_loc"
-    COMMA@26..27 ","
-    STRING@27..29 "\"\""
-    COMMA@29..30 ","
-    TOKEN@30..39 "@progbits"
+  SECTION@0..39
+    DIRECTIVE@0..39
+      MNEMONIC@0..8 ".section"
+      WHITESPACE@8..16 "        "
+      TOKEN@16..26 ".debug_loc"
+      COMMA@26..27 ","
+      STRING@27..29 "\"\""
+      COMMA@29..30 ","
+      TOKEN@30..39 "@progbits"
 "#,
         Architecture::X86_64
     );
@@ -275,28 +283,29 @@ main:
     WHITESPACE@6..7 "\t"
     STRING@7..20 "\"something.c\""
   WHITESPACE@20..22 "\n\t"
-  DIRECTIVE@22..27
-    MNEMONIC@22..27 ".text"
-  WHITESPACE@27..28 "\n"
-  LOCAL_LABEL@28..73
-    LABEL@28..36 ".Ltext0:"
-    WHITESPACE@36..38 "\n\t"
-    DIRECTIVE@38..49
-      MNEMONIC@38..44 ".globl"
-      WHITESPACE@44..45 "\t"
-      TOKEN@45..49 "main"
-    WHITESPACE@49..51 "\n\t"
-    DIRECTIVE@51..72
-      MNEMONIC@51..56 ".type"
-      WHITESPACE@56..57 "\t"
-      TOKEN@57..61 "main"
-      COMMA@61..62 ","
-      WHITESPACE@62..63 " "
-      TOKEN@63..72 "@function"
-    WHITESPACE@72..73 "\n"
-  LABEL@73..79
-    LABEL@73..78 "main:"
-    WHITESPACE@78..79 "\n"
+  SECTION@22..79
+    DIRECTIVE@22..27
+      MNEMONIC@22..27 ".text"
+    WHITESPACE@27..28 "\n"
+    LOCAL_LABEL@28..73
+      LABEL@28..36 ".Ltext0:"
+      WHITESPACE@36..38 "\n\t"
+      DIRECTIVE@38..49
+        MNEMONIC@38..44 ".globl"
+        WHITESPACE@44..45 "\t"
+        TOKEN@45..49 "main"
+      WHITESPACE@49..51 "\n\t"
+      DIRECTIVE@51..72
+        MNEMONIC@51..56 ".type"
+        WHITESPACE@56..57 "\t"
+        TOKEN@57..61 "main"
+        COMMA@61..62 ","
+        WHITESPACE@62..63 " "
+        TOKEN@63..72 "@function"
+      WHITESPACE@72..73 "\n"
+    LABEL@73..79
+      LABEL@73..78 "main:"
+      WHITESPACE@78..79 "\n"
 "#,
         Architecture::X86_64
     );
@@ -374,3 +383,263 @@ fn test_multiline_comment_middle_ins() {
         Architecture::X86_64
     );
 }
+
+#[test]
+fn test_intel_syntax() {
+    assert_listing!(
+        r#"	.intel_syntax noprefix
+	mov	rax, qword ptr fs:[rbp - 8]
+	lea	rax, [rax+rbx*4+8]
+	mov	%eax, offset label"#,
+        r#"ROOT@0..104
+  WHITESPACE@0..1 "\t"
+  DIRECTIVE@1..23
+    MNEMONIC@1..14 ".intel_syntax"
+    WHITESPACE@14..15 " "
+    TOKEN@15..23 "noprefix"
+  WHITESPACE@23..25 "\n\t"
+  INSTRUCTION@25..56
+    MNEMONIC@25..28 "mov"
+    WHITESPACE@28..29 "\t"
+    REGISTER@29..32 "rax"
+    COMMA@32..33 ","
+    WHITESPACE@33..34 " "
+    KEYWORD@34..39 "qword"
+    WHITESPACE@39..40 " "
+    KEYWORD@40..43 "ptr"
+    WHITESPACE@43..44 " "
+    REGISTER@44..46 "fs"
+    OPERATOR@46..47 ":"
+    MEMORY_OPERAND@47..56
+      BRACKETS@47..56
+        L_SQ@47..48 "["
+        BASE@48..51
+          REGISTER@48..51 "rbp"
+        WHITESPACE@51..52 " "
+        DISPLACEMENT@52..55
+          OPERATOR@52..53 "-"
+          WHITESPACE@53..54 " "
+          NUMBER@54..55 "8"
+        R_SQ@55..56 "]"
+  WHITESPACE@56..58 "\n\t"
+  INSTRUCTION@58..80
+    MNEMONIC@58..61 "lea"
+    WHITESPACE@61..62 "\t"
+    REGISTER@62..65 "rax"
+    COMMA@65..66 ","
+    WHITESPACE@66..67 " "
+    MEMORY_OPERAND@67..80
+      BRACKETS@67..80
+        L_SQ@67..68 "["
+        BASE@68..71
+          REGISTER@68..71 "rax"
+        OPERATOR@71..72 "+"
+        INDEX@72..75
+          REGISTER@72..75 "rbx"
+        OPERATOR@75..76 "*"
+        SCALE@76..77
+          NUMBER@76..77 "4"
+        OPERATOR@77..78 "+"
+        DISPLACEMENT@78..79
+          NUMBER@78..79 "8"
+        R_SQ@79..80 "]"
+  WHITESPACE@80..82 "\n\t"
+  INSTRUCTION@82..104
+    MNEMONIC@82..85 "mov"
+    WHITESPACE@85..86 "\t"
+    TOKEN@86..90 "%eax"
+    COMMA@90..91 ","
+    WHITESPACE@91..92 " "
+    KEYWORD@92..98 "offset"
+    WHITESPACE@98..99 " "
+    TOKEN@99..104 "label"
+"#,
+        Architecture::X86_64
+    );
+}
+
+#[test]
+fn test_att_syntax() {
+    assert_listing!(
+        r#"	movl	$1, %eax
+	movq	%fs:40, %rax
+	movq	$label, rax"#,
+        r#"ROOT@0..51
+  WHITESPACE@0..1 "\t"
+  INSTRUCTION@1..14
+    MNEMONIC@1..5 "movl"
+    WHITESPACE@5..6 "\t"
+    IMMEDIATE@6..7 "$"
+    NUMBER@7..8 "1"
+    COMMA@8..9 ","
+    WHITESPACE@9..10 " "
+    REGISTER@10..14 "%eax"
+  WHITESPACE@14..16 "\n\t"
+  INSTRUCTION@16..33
+    MNEMONIC@16..20 "movq"
+    WHITESPACE@20..21 "\t"
+    REGISTER@21..24 "%fs"
+    OPERATOR@24..25 ":"
+    NUMBER@25..27 "40"
+    COMMA@27..28 ","
+    WHITESPACE@28..29 " "
+    REGISTER@29..33 "%rax"
+  WHITESPACE@33..35 "\n\t"
+  INSTRUCTION@35..51
+    MNEMONIC@35..39 "movq"
+    WHITESPACE@39..40 "\t"
+    IMMEDIATE@40..41 "$"
+    TOKEN@41..46 "label"
+    COMMA@46..47 ","
+    WHITESPACE@47..48 " "
+    TOKEN@48..51 "rax"
+"#,
+        Architecture::X86_64
+    );
+}
+
+#[test]
+fn test_preprocessor_comment() {
+    assert_listing!(
+        r#"#define SIZE 16 # bytes
+# define is a comment
+	movq $SIZE, %rax"#,
+        r##"ROOT@0..63
+  CONST_DEF@0..23
+    MNEMONIC@0..7 "#define"
+    WHITESPACE@7..8 " "
+    NAME@8..12 "SIZE"
+    WHITESPACE@12..13 " "
+    EXPR@13..15
+      NUMBER@13..15 "16"
+    WHITESPACE@15..16 " "
+    COMMENT@16..23 "# bytes"
+  WHITESPACE@23..24 "\n"
+  COMMENT@24..45 "# define is a comment"
+  WHITESPACE@45..47 "\n\t"
+  INSTRUCTION@47..63
+    MNEMONIC@47..51 "movq"
+    WHITESPACE@51..52 " "
+    IMMEDIATE@52..53 "$"
+    CONSTANT@53..57 "SIZE"
+    COMMA@57..58 ","
+    WHITESPACE@58..59 " "
+    REGISTER@59..63 "%rax"
+"##,
+        Architecture::X86_64
+    );
+}
+
+#[test]
+fn test_nasm() {
+    assert_listing!(
+        r#"%define SIZE 16
+section .data
+msg db "hi", 0 ; greeting
+section .text
+global main
+%macro zero 1
+	xor %1, %1
+%endmacro
+main:
+	zero rax
+	mov rcx, qword [rel msg]
+.loop:
+	dec rcx
+	jnz .loop"#,
+        r#"ROOT@0..186
+  CONST_DEF@0..15
+    MNEMONIC@0..7 "%define"
+    WHITESPACE@7..8 " "
+    NAME@8..12 "SIZE"
+    WHITESPACE@12..13 " "
+    EXPR@13..15
+      NUMBER@13..15 "16"
+  WHITESPACE@15..16 "\n"
+  SECTION@16..56
+    DIRECTIVE@16..29
+      MNEMONIC@16..23 "section"
+      WHITESPACE@23..24 " "
+      TOKEN@24..29 ".data"
+    WHITESPACE@29..30 "\n"
+    LABEL@30..56
+      LABEL@30..33 "msg"
+      WHITESPACE@33..34 " "
+      DIRECTIVE@34..55
+        MNEMONIC@34..36 "db"
+        WHITESPACE@36..37 " "
+        STRING@37..41 "\"hi\""
+        COMMA@41..42 ","
+        WHITESPACE@42..43 " "
+        NUMBER@43..44 "0"
+        WHITESPACE@44..45 " "
+        COMMENT@45..55 "; greeting"
+      WHITESPACE@55..56 "\n"
+  SECTION@56..186
+    DIRECTIVE@56..69
+      MNEMONIC@56..63 "section"
+      WHITESPACE@63..64 " "
+      TOKEN@64..69 ".text"
+    WHITESPACE@69..70 "\n"
+    DIRECTIVE@70..81
+      MNEMONIC@70..76 "global"
+      WHITESPACE@76..77 " "
+      TOKEN@77..81 "main"
+    WHITESPACE@81..82 "\n"
+    MACRO_DEF@82..117
+      MNEMONIC@82..88 "%macro"
+      WHITESPACE@88..89 " "
+      NAME@89..93 "zero"
+      WHITESPACE@93..94 " "
+      NUMBER@94..95 "1"
+      WHITESPACE@95..97 "\n\t"
+      INSTRUCTION@97..107
+        MNEMONIC@97..100 "xor"
+        WHITESPACE@100..101 " "
+        TOKEN@101..103 "%1"
+        COMMA@103..104 ","
+        WHITESPACE@104..105 " "
+        TOKEN@105..107 "%1"
+      WHITESPACE@107..108 "\n"
+      DIRECTIVE@108..117
+        MNEMONIC@108..117 "%endmacro"
+    WHITESPACE@117..118 "\n"
+    LABEL@118..186
+      LABEL@118..123 "main:"
+      WHITESPACE@123..125 "\n\t"
+      MACRO_CALL@125..133
+        MNEMONIC@125..129 "zero"
+        WHITESPACE@129..130 " "
+        REGISTER@130..133 "rax"
+      WHITESPACE@133..135 "\n\t"
+      INSTRUCTION@135..159
+        MNEMONIC@135..138 "mov"
+        WHITESPACE@138..139 " "
+        REGISTER@139..142 "rcx"
+        COMMA@142..143 ","
+        WHITESPACE@143..144 " "
+        KEYWORD@144..149 "qword"
+        WHITESPACE@149..150 " "
+        BRACKETS@150..159
+          L_SQ@150..151 "["
+          KEYWORD@151..154 "rel"
+          WHITESPACE@154..155 " "
+          TOKEN@155..158 "msg"
+          R_SQ@158..159 "]"
+      WHITESPACE@159..160 "\n"
+      LOCAL_LABEL@160..186
+        LABEL@160..166 ".loop:"
+        WHITESPACE@166..168 "\n\t"
+        INSTRUCTION@168..175
+          MNEMONIC@168..171 "dec"
+          WHITESPACE@171..172 " "
+          REGISTER@172..175 "rcx"
+        WHITESPACE@175..177 "\n\t"
+        INSTRUCTION@177..186
+          MNEMONIC@177..180 "jnz"
+          WHITESPACE@180..181 " "
+          TOKEN@181..186 ".loop"
+"#,
+        Architecture::X86_64
+    );
+}
@@ -43,7 +43,7 @@ pub fn get_server_capabilities() -> ServerCapabilities {
                 },
                 legend: SemanticTokensLegend {
                     token_types: crate::handler::semantic::TOKEN_TYPES.to_vec(),
-                    token_modifiers: vec![],
+                    token_modifiers: crate::handler::semantic::TOKEN_MODIFIERS.to_vec(),
                 },
 
                 full: Some(SemanticTokensFullOptions::Bool(true)),
@@ -61,10 +61,16 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::iter;
+    use std::sync::Arc;
+
+    use documentation::{DocumentationMap, Instruction, InstructionTemplate};
 
     use crate::asm::parser::Parser;
+    use crate::completion::handle_completion;
     use crate::config::LSPConfig;
+    use crate::types::DocumentPosition;
 
     use super::*;
     use base::Architecture;
@@ -149,4 +155,37 @@ mod tests {
         );
         assert_eq!(complete_registers(register, &context), expected,);
     }
+
+    #[test]
+    fn test_aarch64_unclosed_brackets() {
+        let mut docs = HashMap::new();
+        docs.insert(
+            "ldr".into(),
+            vec![Instruction {
+                opcode: "LDR".into(),
+                header: None,
+                architecture: None,
+                description: "".into(),
+                asm_template: vec![InstructionTemplate {
+                    asm: vec!["LDR <gp_64>, [<gp|sp_64>]".into()],
+                    display_asm: "".into(),
+                    items: vec![],
+                    access_map: Default::default(),
+                }],
+            }],
+        );
+        let docs = Arc::new(DocumentationMap::from(docs));
+        let parser = Parser::in_memory(
+            "ldr x0, [",
+            &LSPConfig {
+                architecture: Architecture::AArch64,
+                ..Default::default()
+            },
+        );
+        let position = DocumentPosition { line: 0, column: 9 };
+
+        let items = handle_completion(&parser, &position, Some(docs)).unwrap();
+        assert!(items.iter().any(|i| i.text == "sp"));
+        assert!(items.iter().any(|i| i.text == "x30"));
+    }
 }
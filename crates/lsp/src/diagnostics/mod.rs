@@ -9,6 +9,7 @@ pub mod assembler_flags;
 mod clang;
 pub mod compile_commands;
 mod gcc;
+mod nasm;
 mod util;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -106,6 +107,8 @@ impl TryInto<Box<dyn Assembler>> for CompileCommand {
             Ok(Box::new(crate::diagnostics::clang::Clang { command: self }))
         } else if cmd.contains("gcc") || cmd.contains("g++") {
             Ok(Box::new(crate::diagnostics::gcc::Gcc { command: self }))
+        } else if cmd.contains("nasm") || cmd.contains("yasm") {
+            Ok(Box::new(crate::diagnostics::nasm::Nasm { command: self }))
         } else {
             warn!("`{cmd}` is not a known assembler");
             Err(format!("`{cmd}` is not a known assembler"))
@@ -0,0 +1,121 @@
+use itertools::Itertools;
+
+use crate::types::LineNumber;
+
+use super::util::{run_command, TemporaryFile};
+use super::{Assembler, CompileCommand, Error};
+
+/// NASM and YASM both report errors in the same format
+pub struct Nasm {
+    pub command: CompileCommand,
+}
+
+impl Nasm {
+    fn process_errors(errors: String) -> Vec<Error> {
+        errors
+            .lines()
+            .filter_map(Self::process_error_line)
+            .collect_vec()
+    }
+
+    fn process_error_line(error: &str) -> Option<Error> {
+        let file = error
+            .split(':')
+            .enumerate()
+            .take_while(|(idx, a)| *idx == 0 || (a.starts_with('/') || a.starts_with('\\')))
+            .map(|(_, a)| a)
+            .join(":");
+
+        if file.len() == error.len() {
+            return None;
+        }
+
+        let mut colons = error[file.len() + 1..].split(':');
+        let line = colons.next()?.parse::<LineNumber>().map(|l| l - 1).ok()?;
+        let column = 0;
+        let level = match colons.next()?.trim() {
+            "warning" => "warn",
+            level => level,
+        }
+        .into();
+        let description = colons.join(":").trim().to_string();
+
+        Some(Error {
+            code: "".into(),
+            description,
+            file,
+            line,
+            column,
+            level,
+        })
+    }
+}
+
+impl Assembler for Nasm {
+    fn get_errors(&self) -> Vec<Error> {
+        let mut args = self.command.get_arguments().to_vec();
+        let command = self.command.get_command();
+
+        let temp_file = TemporaryFile::new();
+        args.push(String::from("-o"));
+        args.push(temp_file.filename().clone());
+
+        run_command(command, &args)
+            .map(Self::process_errors)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostics::nasm::Nasm;
+    use crate::diagnostics::{Error, ErrorLevel};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_nasm_error_line() {
+        let expected = Error {
+            file: String::from("codec.asm"),
+            line: 11,
+            column: 0,
+            level: ErrorLevel::Error,
+            code: Default::default(),
+            description: String::from("symbol `frame_size' not defined"),
+        };
+
+        assert_eq!(
+            Nasm::process_error_line("codec.asm:12: error: symbol `frame_size' not defined")
+                .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_nasm_errors() {
+        let errors = r#"codec.asm:3: warning: label alone on a line without a colon might be in error [-w+label-orphan]
+codec.asm:7: error: parser: instruction expected"#;
+
+        let expected = vec![
+            Error {
+                file: String::from("codec.asm"),
+                line: 2,
+                column: 0,
+                level: ErrorLevel::Warning,
+                code: Default::default(),
+                description: String::from(
+                    "label alone on a line without a colon might be in error [-w+label-orphan]",
+                ),
+            },
+            Error {
+                file: String::from("codec.asm"),
+                line: 6,
+                column: 0,
+                level: ErrorLevel::Error,
+                code: Default::default(),
+                description: String::from("parser: instruction expected"),
+            },
+        ];
+
+        assert_eq!(Nasm::process_errors(String::from(errors)), expected);
+    }
+}
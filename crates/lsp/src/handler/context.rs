@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use base::rwlock::RwLock;
-use lsp_types::Url;
+use lsp_types::{Diagnostic, Url};
 
 use crate::asm::handler::AssemblyLanguageServerProtocol;
 use crate::asm::parser::Parser;
@@ -19,6 +19,10 @@ pub struct Context {
     pub commands: Option<Box<dyn Diagnostics + Send + Sync>>,
     pub root: String,
     pub file_graph: RwLock<FileGraph>,
+    /// The errors from the last time the assembler was run and the source
+    /// checksums were checked for each file, as this only happens on open and
+    /// save they're kept to be published on each change.
+    pub assembler_diagnostics: RwLock<HashMap<Url, Vec<Diagnostic>>>,
 }
 
 impl Context {
@@ -40,6 +44,7 @@ impl Context {
             commands,
             root,
             file_graph: Default::default(),
+            assembler_diagnostics: Default::default(),
         }
     }
 
@@ -1,5 +1,5 @@
 use lsp_types::request::Request;
-use lsp_types::{Range, TextDocumentIdentifier};
+use lsp_types::{Location, Range, TextDocumentIdentifier, Url};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -50,3 +50,121 @@ impl Request for FileStats {
     type Result = FileStatsResult;
     const METHOD: &'static str = "diag/fileStats";
 }
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceMappingParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+/// The assembly generated for a line of source, `line` is zero based.
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceLineMapping {
+    pub uri: Url,
+    pub line: u32,
+    pub ranges: Vec<Range>,
+}
+
+pub enum SourceMapping {}
+
+impl Request for SourceMapping {
+    type Params = SourceMappingParams;
+    type Result = Vec<SourceLineMapping>;
+    const METHOD: &'static str = "asm/sourceMapping";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssemblyForSourceParams {
+    pub uri: Url,
+    pub line: u32,
+}
+
+pub enum AssemblyForSource {}
+
+impl Request for AssemblyForSource {
+    type Params = AssemblyForSourceParams;
+    type Result = Vec<Location>;
+    const METHOD: &'static str = "asm/assemblyForSource";
+}
+
+/// The parts of a listing to remove, everything is removed by default
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListingFilters {
+    pub directives: bool,
+    pub unused_labels: bool,
+    pub comments: bool,
+    pub debug_sections: bool,
+    pub library_functions: bool,
+}
+
+impl Default for ListingFilters {
+    fn default() -> Self {
+        Self {
+            directives: true,
+            unused_labels: true,
+            comments: true,
+            debug_sections: true,
+            library_functions: true,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredListingParams {
+    pub text_document: TextDocumentIdentifier,
+    #[serde(default)]
+    pub filters: ListingFilters,
+}
+
+/// The filtered text along with the zero based line in the original file
+/// for each of its lines.
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredListingResult {
+    pub text: String,
+    pub lines: Vec<u32>,
+}
+
+pub enum FilteredListing {}
+
+impl Request for FilteredListing {
+    type Params = FilteredListingParams;
+    type Result = FilteredListingResult;
+    const METHOD: &'static str = "asm/filteredListing";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlFlowParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ControlFlowKind {
+    Jump,
+    Call,
+}
+
+/// A jump or call from an instruction to an instruction or label in the same
+/// file.
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlFlowEdge {
+    pub source: Range,
+    pub target: Range,
+    pub kind: ControlFlowKind,
+    pub conditional: bool,
+}
+
+pub enum ControlFlow {}
+
+impl Request for ControlFlow {
+    type Params = ControlFlowParams;
+    type Result = Vec<ControlFlowEdge>;
+    const METHOD: &'static str = "asm/controlFlow";
+}
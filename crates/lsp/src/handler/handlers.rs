@@ -9,7 +9,10 @@ use crate::diagnostics::{Error, UrlPath};
 
 use super::context::Context;
 use super::error::{lsp_error_map, ErrorCode};
-use super::ext::{FileStatsParams, FileStatsResult};
+use super::ext::{
+    AssemblyForSourceParams, ControlFlowEdge, ControlFlowParams, FileStatsParams, FileStatsResult,
+    FilteredListingParams, FilteredListingResult, SourceLineMapping,
+};
 use super::types::{
     CodeActionMessage, DocumentChange, DocumentRangeMessage, FindReferencesMessage,
     LocationMessage, RenameMessage,
@@ -17,7 +20,8 @@ use super::types::{
 
 use lsp_server::ResponseError;
 use lsp_types::{
-    CompletionList, DidChangeTextDocumentParams, SignatureHelp, TextEdit, Url, WorkspaceEdit,
+    CompletionList, Diagnostic, DidChangeTextDocumentParams, Location, SignatureHelp, TextEdit,
+    Url, WorkspaceEdit,
 };
 
 pub fn open_file(
@@ -72,6 +76,7 @@ pub fn update_file(
 pub fn close_file(context: Arc<Context>, url: Url) -> Result<(), ResponseError> {
     // Only close the file if nothing else has a reference to it.
     if !context.file_graph.read().has_references(url.as_ref()) {
+        context.assembler_diagnostics.write().remove(&url);
         if let Entry::Occupied(entry) = context.actors.write().entry(url) {
             entry.remove_entry();
         }
@@ -243,6 +248,24 @@ pub fn get_diagnostics(context: Arc<Context>, uri: &Url) -> Option<Vec<Error>> {
     )
 }
 
+pub fn get_syntax_diagnostics(context: Arc<Context>, uri: &Url) -> Vec<Diagnostic> {
+    context
+        .actors
+        .read()
+        .get(uri)
+        .map(|actor| actor.read().syntax_diagnostics())
+        .unwrap_or_default()
+}
+
+pub fn get_checksum_diagnostics(context: Arc<Context>, uri: &Url) -> Vec<Diagnostic> {
+    context
+        .actors
+        .read()
+        .get(uri)
+        .map(|actor| actor.read().checksum_diagnostics())
+        .unwrap_or_default()
+}
+
 pub fn format(context: Arc<Context>, url: Url) -> Result<Option<Vec<TextEdit>>, ResponseError> {
     context
         .actors
@@ -276,6 +299,65 @@ pub fn syntax_tree(context: Arc<Context>, url: Url) -> Result<String, ResponseEr
         .syntax_tree()
 }
 
+pub fn source_mapping(
+    context: Arc<Context>,
+    url: Url,
+) -> Result<Vec<SourceLineMapping>, ResponseError> {
+    Ok(context
+        .actors
+        .read()
+        .get(&url)
+        .ok_or_else(|| lsp_error_map(ErrorCode::FileNotFound))?
+        .read()
+        .source_mapping())
+}
+
+/// Finds the assembly for a line of source in each of the open files
+pub fn assembly_for_source(
+    context: Arc<Context>,
+    request: AssemblyForSourceParams,
+) -> Result<Vec<Location>, ResponseError> {
+    Ok(context
+        .actors
+        .read()
+        .iter()
+        .sorted_by_key(|(url, _)| url.as_str())
+        .flat_map(|(url, actor)| {
+            actor
+                .read()
+                .assembly_for_source(&request.uri, request.line)
+                .into_iter()
+                .map(|range| Location::new(url.clone(), range))
+        })
+        .collect())
+}
+
+pub fn filtered_listing(
+    context: Arc<Context>,
+    request: FilteredListingParams,
+) -> Result<FilteredListingResult, ResponseError> {
+    Ok(context
+        .actors
+        .read()
+        .get(&request.text_document.uri)
+        .ok_or_else(|| lsp_error_map(ErrorCode::FileNotFound))?
+        .read()
+        .filtered_listing(&request.filters))
+}
+
+pub fn control_flow(
+    context: Arc<Context>,
+    request: ControlFlowParams,
+) -> Result<Vec<ControlFlowEdge>, ResponseError> {
+    Ok(context
+        .actors
+        .read()
+        .get(&request.text_document.uri)
+        .ok_or_else(|| lsp_error_map(ErrorCode::FileNotFound))?
+        .read()
+        .control_flow())
+}
+
 pub fn analysis(
     context: Arc<Context>,
     request: DocumentRangeMessage,
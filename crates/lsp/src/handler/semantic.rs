@@ -1,6 +1,6 @@
 use std::iter;
 
-use lsp_types::{SemanticToken, SemanticTokenType};
+use lsp_types::{SemanticToken, SemanticTokenModifier, SemanticTokenType};
 
 pub static TOKEN_TYPES: &[SemanticTokenType] = &[
     SemanticTokenType::KEYWORD,
@@ -30,6 +30,11 @@ pub const FP_REGISTER_INDEX: u32 = 9;
 pub const RELOCATION_INDEX: u32 = 10;
 pub const CONSTANT_INDEX: u32 = 11;
 
+pub static TOKEN_MODIFIERS: &[SemanticTokenModifier] = &[SemanticTokenModifier::new("inactive")];
+
+/// Bitset for tokens in conditional assembly branches that won't be assembled
+pub const INACTIVE_MODIFIER: u32 = 1;
+
 pub(crate) fn semantic_delta_transform(tokens: &[SemanticToken]) -> Vec<SemanticToken> {
     let prev = tokens.iter();
     let current = tokens.iter().skip(1);
@@ -171,6 +171,26 @@ fn process_message(connection: Arc<Connection>, context: Arc<Context>, msg: Mess
                         get_message::<crate::handler::ext::RunAnalysis>(request).unwrap();
                     make_result(handlers::analysis(context, data.into()))
                 }
+                "asm/sourceMapping" => {
+                    let (_, data) =
+                        get_message::<crate::handler::ext::SourceMapping>(request).unwrap();
+                    make_result(handlers::source_mapping(context, data.text_document.uri))
+                }
+                "asm/assemblyForSource" => {
+                    let (_, data) =
+                        get_message::<crate::handler::ext::AssemblyForSource>(request).unwrap();
+                    make_result(handlers::assembly_for_source(context, data))
+                }
+                "asm/filteredListing" => {
+                    let (_, data) =
+                        get_message::<crate::handler::ext::FilteredListing>(request).unwrap();
+                    make_result(handlers::filtered_listing(context, data))
+                }
+                "asm/controlFlow" => {
+                    let (_, data) =
+                        get_message::<crate::handler::ext::ControlFlow>(request).unwrap();
+                    make_result(handlers::control_flow(context, data))
+                }
                 "diag/fileStats" => {
                     let (_, data) = get_message::<crate::handler::ext::FileStats>(request).unwrap();
                     make_result(handlers::file_stats(context, data))
@@ -200,18 +220,23 @@ fn process_message(connection: Arc<Connection>, context: Arc<Context>, msg: Mess
                         &data.text,
                         data.version as _,
                     );
-                    handle_diagnostics(connection, context, data.uri);
+                    handle_diagnostics(connection, context, data.uri, true);
                     Ok(())
                 }
                 "textDocument/didChange" => {
                     let data = get_notification::<DidChangeTextDocument>(notification).unwrap();
+                    let uri = data.text_document.uri.clone();
 
-                    if let Err(e) = handlers::update_file(context, data) {
-                        if let Some(params) = e.data {
-                            let _ = connection.sender.send(Message::Notification(Notification {
-                                method: String::from("textDocument/resync"),
-                                params,
-                            }));
+                    match handlers::update_file(context.clone(), data) {
+                        Ok(()) => handle_diagnostics(connection, context, uri, false),
+                        Err(e) => {
+                            if let Some(params) = e.data {
+                                let _ =
+                                    connection.sender.send(Message::Notification(Notification {
+                                        method: String::from("textDocument/resync"),
+                                        params,
+                                    }));
+                            }
                         }
                     }
 
@@ -219,7 +244,7 @@ fn process_message(connection: Arc<Connection>, context: Arc<Context>, msg: Mess
                 }
                 "textDocument/didSave" => {
                     let data = get_notification::<DidSaveTextDocument>(notification).unwrap();
-                    handle_diagnostics(connection, context, data.text_document.uri);
+                    handle_diagnostics(connection, context, data.text_document.uri, true);
                     Ok(())
                 }
                 "textDocument/didClose" => {
@@ -241,7 +266,16 @@ fn process_message(connection: Arc<Connection>, context: Arc<Context>, msg: Mess
     }
 }
 
-fn handle_diagnostics(connection: Arc<Connection>, context: Arc<Context>, uri: Url) {
+/// Publishes the syntax errors for the file along with the errors from the
+/// assembler, the assembler is only run and the source checksums are only
+/// checked if `run_assembler` is set otherwise the errors from the previous
+/// run are used.
+fn handle_diagnostics(
+    connection: Arc<Connection>,
+    context: Arc<Context>,
+    uri: Url,
+    run_assembler: bool,
+) {
     use crate::handler::handlers;
 
     if !context.config().diagnostics.enabled {
@@ -249,11 +283,28 @@ fn handle_diagnostics(connection: Arc<Connection>, context: Arc<Context>, uri: U
     }
     info!("Handling diagnostics for file: {}", uri);
 
-    let diagnostics = handlers::get_diagnostics(context, &uri)
-        .unwrap_or_default()
-        .into_iter()
-        .map(|e| e.into())
-        .collect();
+    if run_assembler {
+        let mut diagnostics: Vec<_> = handlers::get_diagnostics(context.clone(), &uri)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|e| e.into())
+            .collect();
+        diagnostics.extend(handlers::get_checksum_diagnostics(context.clone(), &uri));
+        context
+            .assembler_diagnostics
+            .write()
+            .insert(uri.clone(), diagnostics);
+    }
+
+    let mut diagnostics = handlers::get_syntax_diagnostics(context.clone(), &uri);
+    diagnostics.extend(
+        context
+            .assembler_diagnostics
+            .read()
+            .get(&uri)
+            .cloned()
+            .unwrap_or_default(),
+    );
 
     let params = PublishDiagnosticsParams {
         uri,
@@ -0,0 +1,161 @@
+//! Compilers for PowerPC write registers as plain numbers e.g. `addi 9,9,1`,
+//! which register file the number refers to depends on the instruction and
+//! the operand that it is used in.
+
+use rowan::Language;
+use rowan::{GreenNode, GreenToken, NodeOrToken};
+use syntax::ast::{AssemblyLanguage, SyntaxKind, SyntaxToken};
+use unicase::UniCase;
+
+use crate::config::ParserConfig;
+
+/// Gets the kind of the operand at `index` of the instruction `mnemonic`,
+/// `count` is the number of operands that were given.
+fn operand_kind(config: &ParserConfig, mnemonic: &str, index: usize, count: usize) -> Option<char> {
+    // Record forms (`add.`) and branch prediction hints (`beq+`) take the same
+    // operands as the base instruction.
+    let mnemonic = mnemonic.trim_end_matches(['.', '+', '-']);
+    let operands = config.register_operands?.get(&UniCase::ascii(mnemonic))?;
+
+    // The condition register field can be omitted in which case cr0 is used
+    if count < operands.len() {
+        operands.replace('C', "").chars().nth(index)
+    } else {
+        operands.chars().nth(index)
+    }
+}
+
+/// Gets the prefix of the register name for the operand at `index` of the
+/// instruction `mnemonic`, `count` is the number of operands that were given.
+fn operand_prefix(
+    config: &ParserConfig,
+    mnemonic: &str,
+    index: usize,
+    count: usize,
+) -> Option<&'static str> {
+    match operand_kind(config, mnemonic, index, count)? {
+        'r' => Some("r"),
+        'f' => Some("f"),
+        'v' => Some("v"),
+        'x' => Some("vs"),
+        'c' | 'C' => Some("cr"),
+        _ => None,
+    }
+}
+
+/// Converts any numbers in the instruction that are being used as registers
+/// into REGISTER tokens.
+pub(crate) fn transform_bare_registers(
+    items: &mut [NodeOrToken<GreenNode, GreenToken>],
+    config: &ParserConfig,
+) {
+    let Some(start) = items
+        .iter()
+        .position(|i| i.kind() == SyntaxKind::MNEMONIC.into())
+    else {
+        return;
+    };
+
+    let mnemonic = items[start].as_token().unwrap().text().to_string();
+    let operands = &mut items[start + 1..];
+    let count = operands
+        .iter()
+        .filter(|i| i.kind() == SyntaxKind::COMMA.into())
+        .count()
+        + 1;
+
+    // Only the brackets at the end of an operand can hold the base register
+    let ends_operand = (0..operands.len())
+        .map(|i| {
+            operands[i + 1..]
+                .iter()
+                .find(|c| c.kind() != SyntaxKind::WHITESPACE.into())
+                .is_none_or(|c| {
+                    c.kind() == SyntaxKind::COMMA.into() || c.kind() == SyntaxKind::COMMENT.into()
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let mut index = 0;
+    for (item, ends_operand) in operands.iter_mut().zip(ends_operand) {
+        match item {
+            NodeOrToken::Token(t) if t.kind() == SyntaxKind::COMMA.into() => index += 1,
+            NodeOrToken::Token(t)
+                if t.kind() == SyntaxKind::NUMBER.into()
+                    && operand_prefix(config, &mnemonic, index, count).is_some() =>
+            {
+                *t = GreenToken::new(SyntaxKind::REGISTER.into(), t.text());
+            }
+            // The base register in a `D(RA)` memory operand of a load or
+            // store, other brackets are part of an expression e.g. `(4+8)`
+            NodeOrToken::Node(n)
+                if n.kind() == SyntaxKind::BRACKETS.into()
+                    && ends_operand
+                    && operand_kind(config, &mnemonic, index, count) == Some('m') =>
+            {
+                if let Some(position) = base_register(n) {
+                    let text = n
+                        .children()
+                        .nth(position)
+                        .unwrap()
+                        .as_token()
+                        .unwrap()
+                        .text();
+                    let register = GreenToken::new(SyntaxKind::REGISTER.into(), text);
+                    *n = n.replace_child(position, NodeOrToken::Token(register));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The position of the number in brackets that only contain a number e.g.
+/// `(9)`
+fn base_register(brackets: &GreenNode) -> Option<usize> {
+    let mut contents = brackets.children().enumerate().filter(|(_, c)| {
+        !matches!(
+            AssemblyLanguage::kind_from_raw(c.kind()),
+            SyntaxKind::L_PAREN | SyntaxKind::R_PAREN | SyntaxKind::WHITESPACE
+        )
+    });
+
+    match (contents.next(), contents.next()) {
+        (Some((position, number)), None) if number.kind() == SyntaxKind::NUMBER.into() => {
+            Some(position)
+        }
+        _ => None,
+    }
+}
+
+/// Gets the name of the register that a bare number register token refers
+/// to e.g. `f0` for the first operand of `lfd 0,8(9)`.
+pub fn bare_register_name(token: &SyntaxToken, config: &ParserConfig) -> Option<String> {
+    if config.register_operands.is_none() || token.kind() != SyntaxKind::REGISTER {
+        return None;
+    }
+
+    let number = token.text().parse::<u8>().ok()?;
+    let parent = token.parent()?;
+    if parent.kind() == SyntaxKind::BASE {
+        return Some(format!("r{number}"));
+    }
+
+    let mnemonic = parent
+        .children_with_tokens()
+        .find(|c| c.kind() == SyntaxKind::MNEMONIC)?;
+    let operands = || {
+        parent
+            .children_with_tokens()
+            .skip_while(|c| c.kind() != SyntaxKind::MNEMONIC)
+            .filter(|c| c.kind() == SyntaxKind::COMMA)
+    };
+
+    let index = operands()
+        .filter(|c| c.text_range().start() < token.text_range().start())
+        .count();
+    let count = operands().count() + 1;
+
+    operand_prefix(config, mnemonic.as_token()?.text(), index, count)
+        .map(|prefix| format!("{prefix}{number}"))
+}
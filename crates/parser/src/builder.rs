@@ -1,19 +1,28 @@
 use std::cell::{Ref, RefCell};
+use std::collections::HashSet;
 
-use rowan::{GreenNode, GreenToken, Language, NodeOrToken};
+use rowan::{GreenNode, GreenToken, Language, NodeOrToken, TextSize};
 
 use syntax::alias::Alias;
 use syntax::ast::{AssemblyLanguage, SyntaxKind};
 
 use crate::config::ParserConfig;
-use crate::{equ, include, LoadFileFn, ParsedInclude};
+use crate::{
+    bare_registers, equ, include, macros, memory, preprocessor, LoadFileFn, ParsedInclude,
+};
 
 pub struct Builder<'c> {
     child: RefCell<Vec<NodeOrToken<GreenNode, GreenToken>>>,
+    /// The end offset of each item in `child`, so the position of a node is
+    /// known without adding up everything before it.
+    ends: RefCell<Vec<TextSize>>,
     parent: RefCell<Vec<(usize, SyntaxKind)>>,
     pub(crate) alias: RefCell<Alias>,
     config: &'c ParserConfig,
     pub(crate) included: RefCell<Vec<ParsedInclude>>,
+    macros: RefCell<HashSet<String>>,
+    /// The number of `.pushsection` directives that haven't been popped yet
+    pushed_sections: RefCell<usize>,
     load: LoadFileFn,
     file: Option<&'c str>,
 }
@@ -27,10 +36,13 @@ impl<'c> Builder<'c> {
     ) -> Self {
         Self {
             child: RefCell::new(Vec::with_capacity(size_hint)),
+            ends: RefCell::new(Vec::with_capacity(size_hint)),
             parent: Default::default(),
             alias: Default::default(),
             config,
             included: Default::default(),
+            macros: Default::default(),
+            pushed_sections: Default::default(),
             load,
             file,
         }
@@ -40,63 +52,163 @@ impl<'c> Builder<'c> {
         self.alias.borrow()
     }
 
+    /// Adds the aliases and macros that have been defined elsewhere in the
+    /// file, this is used when only part of a file is being parsed.
+    pub(super) fn define(&self, alias: &Alias, macros: &HashSet<String>) {
+        self.alias.borrow_mut().merge(alias, TextSize::default());
+        self.macros.borrow_mut().extend(macros.iter().cloned());
+    }
+
+    pub(super) fn macros(&self) -> HashSet<String> {
+        self.macros.borrow().clone()
+    }
+
     pub(super) fn start_node(&self, kind: SyntaxKind) {
         let pos = self.child.borrow().len();
         self.parent.borrow_mut().push((pos, kind));
     }
 
     pub(super) fn token(&self, kind: SyntaxKind, text: &str) {
-        self.child
-            .borrow_mut()
-            .push(NodeOrToken::Token(GreenToken::new(kind.into(), text)));
+        let token = GreenToken::new(kind.into(), text);
+        push_end(&mut self.ends.borrow_mut(), token.text_len());
+        self.child.borrow_mut().push(NodeOrToken::Token(token));
     }
 
     pub(super) fn finish_node(&self) {
         let mut parent = self.parent.borrow_mut();
         let mut child = self.child.borrow_mut();
+        let mut ends = self.ends.borrow_mut();
 
         let (start_pos, mut kind) = parent.pop().unwrap();
         let mut items = child.drain(start_pos..).collect::<Vec<_>>();
+        ends.truncate(start_pos);
         if kind == SyntaxKind::DIRECTIVE
             && items
                 .first()
-                .and_then(|f| f.as_token().map(|t| t.text().eq_ignore_ascii_case(".equ")))
+                .and_then(|f| f.as_token().map(|t| equ::is_equ(t.text())))
                 .unwrap_or(false)
         {
             equ::transform_equ_node(&mut items);
             kind = SyntaxKind::CONST_DEF;
+        } else if kind == SyntaxKind::DIRECTIVE
+            && items
+                .first()
+                .and_then(|f| f.as_token().map(|t| preprocessor::is_define(t.text())))
+                .unwrap_or(false)
+        {
+            if preprocessor::transform_define_node(&mut items) {
+                kind = SyntaxKind::CONST_DEF;
+            }
+        } else if kind == SyntaxKind::MACRO_DEF {
+            if let Some(name) = macros::transform_macro_node(&mut items) {
+                self.macros.borrow_mut().insert(name.to_lowercase());
+            }
+        } else if kind == SyntaxKind::INSTRUCTION && equ::is_assignment(&items) {
+            equ::transform_assignment_node(&mut items);
+            kind = SyntaxKind::CONST_DEF;
+        } else if kind == SyntaxKind::INSTRUCTION && self.is_macro_call(&items) {
+            kind = SyntaxKind::MACRO_CALL;
+        } else if kind == SyntaxKind::INSTRUCTION && self.config.register_operands.is_some() {
+            bare_registers::transform_bare_registers(&mut items, self.config);
+        }
+
+        if kind == SyntaxKind::INSTRUCTION {
+            memory::transform_memory_operands(&mut items, self.config);
         }
 
         let node = GreenNode::new(kind.into(), items);
 
+        let offset = ends.last().copied().unwrap_or_default();
+
         if include::is_include(&node) {
             if let Some(data) = include::handle_include(&node, self.config, self.file, self.load) {
-                self.alias.borrow_mut().merge(&data.alias);
+                self.alias.borrow_mut().merge(&data.alias, offset);
+                self.macros.borrow_mut().extend(
+                    macros::macro_names(&data.root)
+                        .iter()
+                        .map(|m| m.to_lowercase()),
+                );
                 self.included.borrow_mut().push(data);
             }
         }
 
         if kind == SyntaxKind::ALIAS {
-            self.alias.borrow_mut().add_alias(&node)
+            self.alias.borrow_mut().add_alias(&node, offset);
         } else if kind == SyntaxKind::CONST_DEF {
-            self.alias.borrow_mut().add_equ(&node);
+            self.alias.borrow_mut().add_equ(&node, offset);
+        } else if kind == SyntaxKind::DIRECTIVE && equ::is_unreq(&node) {
+            self.alias.borrow_mut().remove_alias(&node, offset);
         }
 
+        if kind == SyntaxKind::RELOCATION {
+            let start = parent.last().map(|(start, _)| *start).unwrap_or(0);
+            if let Some(instruction) = Self::attach_relocation(&mut child, start, &node) {
+                ends.truncate(child.len());
+                push_end(&mut ends, instruction.text_len());
+                child.push(NodeOrToken::Node(instruction));
+                return;
+            }
+        }
+
+        push_end(&mut ends, node.text_len());
         child.push(NodeOrToken::Node(node));
     }
 
+    /// The relocations from `objdump -r` are listed on the lines after the
+    /// instruction they apply to, so they are moved into that instruction.
+    fn attach_relocation(
+        child: &mut Vec<NodeOrToken<GreenNode, GreenToken>>,
+        start: usize,
+        relocation: &GreenNode,
+    ) -> Option<GreenNode> {
+        let index = child[start..]
+            .iter()
+            .rposition(|c| c.kind() != SyntaxKind::WHITESPACE.into())?
+            + start;
+        let instruction = child[index].as_node()?;
+        if instruction.kind() != SyntaxKind::INSTRUCTION.into() {
+            return None;
+        }
+
+        let items = child
+            .drain(index..)
+            .flat_map(|c| match c {
+                NodeOrToken::Node(n) if n.kind() == SyntaxKind::INSTRUCTION.into() => {
+                    n.children().map(|c| c.to_owned()).collect()
+                }
+                c => vec![c],
+            })
+            .chain([NodeOrToken::Node(relocation.clone())])
+            .collect::<Vec<_>>();
+
+        Some(GreenNode::new(SyntaxKind::INSTRUCTION.into(), items))
+    }
+
     pub(super) fn finish(&self) -> GreenNode {
         let n = self.parent.borrow().len();
         for _ in 0..n {
             self.finish_node();
         }
 
+        self.ends.borrow_mut().pop();
         match self.child.borrow_mut().pop().unwrap() {
             NodeOrToken::Node(node) => node,
             NodeOrToken::Token(_) => panic!("Invalid syntax tree built"),
         }
     }
 
+    /// Gets the current position in the tree, which can later be restored
+    /// with `rollback` to discard anything that was added after it.
+    pub(super) fn checkpoint(&self) -> (usize, usize) {
+        (self.child.borrow().len(), self.parent.borrow().len())
+    }
+
+    pub(super) fn rollback(&self, (child, parent): (usize, usize)) {
+        self.parent.borrow_mut().truncate(parent);
+        self.child.borrow_mut().truncate(child);
+        self.ends.borrow_mut().truncate(child);
+    }
+
     pub(super) fn change_node_kind(&self, new_kind: SyntaxKind) {
         let index = self.parent.borrow().len() - 1;
         if let Some(i) = self.parent.borrow_mut().get_mut(index) {
@@ -117,6 +229,18 @@ impl<'c> Builder<'c> {
         let _ = std::mem::replace(&mut self.child.borrow_mut()[index], token);
     }
 
+    pub(super) fn push_section(&self) {
+        *self.pushed_sections.borrow_mut() += 1;
+    }
+
+    /// Returns false if there isn't a pushed section to return from
+    pub(super) fn pop_section(&self) -> bool {
+        let mut pushed = self.pushed_sections.borrow_mut();
+        let popped = *pushed > 0;
+        *pushed = pushed.saturating_sub(1);
+        popped
+    }
+
     pub(super) fn current_indent_is_kind(&self, kind: SyntaxKind) -> bool {
         self.parent
             .borrow()
@@ -124,6 +248,23 @@ impl<'c> Builder<'c> {
             .map(|(_, k)| k == &kind)
             .unwrap_or(false)
     }
+    pub(super) fn is_inside_kind(&self, kind: SyntaxKind) -> bool {
+        self.parent.borrow().iter().any(|(_, k)| k == &kind)
+    }
+
+    /// Macro names are case insensitive and need to be defined before they
+    /// are used.
+    fn is_macro_call(&self, items: &[NodeOrToken<GreenNode, GreenToken>]) -> bool {
+        let macros = self.macros.borrow();
+        !macros.is_empty()
+            && items
+                .iter()
+                .filter_map(|i| i.as_token())
+                .find(|t| t.kind() == SyntaxKind::MNEMONIC.into())
+                .map(|t| macros.contains(&t.text().to_lowercase()))
+                .unwrap_or(false)
+    }
+
     pub(super) fn last_kind(&self) -> SyntaxKind {
         self.child
             .borrow()
@@ -132,3 +273,8 @@ impl<'c> Builder<'c> {
             .unwrap_or(SyntaxKind::ROOT)
     }
 }
+
+fn push_end(ends: &mut Vec<TextSize>, len: TextSize) {
+    let start = ends.last().copied().unwrap_or_default();
+    ends.push(start + len);
+}
@@ -1,9 +1,14 @@
+use crate::conditional::{is_conditional_else, is_conditional_end, is_conditional_start};
+use crate::macros::{is_macro_end, is_macro_start};
+use crate::nasm;
+use crate::preprocessor::is_preprocessor_line;
+use crate::section::{is_section_pop, is_section_push, is_section_start};
 use crate::{LoadFileFn, ParsedData};
 
 use super::builder::Builder;
 use super::config::ParserConfig;
 
-use base::{Architecture, FileType, ObjDumpOptions};
+use base::{Architecture, Dialect, FileType, ObjDumpOptions};
 use nom::bytes::complete::{tag, take_while};
 use nom::character::is_hex_digit;
 use nom::error::ErrorKind;
@@ -11,7 +16,9 @@ use nom::multi::many0;
 use nom::sequence::{delimited, preceded, terminated};
 use nom::{IResult, InputLength, InputTake};
 use rowan::GreenNode;
+use std::collections::HashSet;
 use std::num::{ParseFloatError, ParseIntError};
+use syntax::alias::Alias;
 use syntax::ast::SyntaxKind;
 use unicase::UniCase;
 
@@ -58,9 +65,27 @@ impl<'a> Span<'a> {
     pub(self) fn current_indent_is_kind(&self, kind: SyntaxKind) -> bool {
         self.extra().builder.current_indent_is_kind(kind)
     }
+    pub(self) fn is_inside_kind(&self, kind: SyntaxKind) -> bool {
+        self.extra().builder.is_inside_kind(kind)
+    }
+    pub(self) fn push_section(&self) {
+        self.extra().builder.push_section()
+    }
+    pub(self) fn pop_section(&self) -> bool {
+        self.extra().builder.pop_section()
+    }
+    pub(self) fn change_node_kind(&self, kind: SyntaxKind) {
+        self.extra().builder.change_node_kind(kind)
+    }
     pub(self) fn last_kind(&self) -> SyntaxKind {
         self.extra().builder.last_kind()
     }
+    pub(self) fn checkpoint(&self) -> (usize, usize) {
+        self.extra().builder.checkpoint()
+    }
+    pub(self) fn rollback(&self, checkpoint: (usize, usize)) {
+        self.extra().builder.rollback(checkpoint)
+    }
 }
 
 /// Performs the checks to exit out of a end many function
@@ -94,29 +119,82 @@ pub(crate) fn parse<'a>(
 ) -> ParsedData {
     let builder = Builder::new(data.len() / 4, config, file, load);
     let internal = InternalSpanConfig::new(config, &builder);
+    let len = data.len();
     let data = Span::new(data, &internal);
 
     data.start_node(SyntaxKind::ROOT);
     let data = match config.file_type {
         FileType::Assembly => data,
-        FileType::ObjDump(_) => parse_objdump_header(data).unwrap().0,
+        FileType::ObjDump(_) => match parse_objdump_header(data.clone()) {
+            Ok((remaining, _)) => remaining,
+            Err(_) => data,
+        },
     };
 
-    let result = many0(parse_next)(data);
-    let (remaining, _) = match result {
-        Ok(a) => a,
-        Err(e) => panic!("Failed to parse due to error: {e:#?}"),
-    };
-
-    debug!("Parsed assembly with data remaining: {remaining:#?}");
+    let remaining = parse_all(data, len);
 
     ParsedData {
         root: remaining.finish(),
         alias: remaining.extra().builder.alias.take(),
+        macros: remaining.extra().builder.macros(),
         included_files: remaining.extra().builder.included.take(),
     }
 }
 
+/// Parses a run of lines from the middle of a file, unlike `parse` there is
+/// no header for objdump files and includes aren't loaded.
+pub(crate) fn parse_lines(
+    data: &str,
+    config: &ParserConfig,
+    alias: &Alias,
+    macros: &HashSet<String>,
+) -> GreenNode {
+    let builder = Builder::new(data.len() / 4, config, None, |_, _, _| None);
+    builder.define(alias, macros);
+    let internal = InternalSpanConfig::new(config, &builder);
+    let data = Span::new(data, &internal);
+
+    data.start_node(SyntaxKind::ROOT);
+    let len = data.input_len();
+    parse_all(data, len).finish()
+}
+
+/// Parses each statement in `data`, anything that can't be parsed is added
+/// to the tree as an ERROR node so that the tree always covers the input.
+/// `len` is the length of the whole input, used to log where errors are.
+fn parse_all(mut data: Span, len: usize) -> Span {
+    while !data.is_empty() {
+        let checkpoint = data.checkpoint();
+        data = match parse_next(data.clone()) {
+            Ok((remaining, _)) if remaining.input_len() < data.input_len() => remaining,
+            _ => {
+                data.rollback(checkpoint);
+                let (remaining, line) = match take_while::<_, _, ()>(|a| a != '\n')(data.clone()) {
+                    Ok((remaining, line)) if !line.is_empty() => (remaining, line),
+                    _ => data.take_split(data.chars().next().map_or(1, char::len_utf8)),
+                };
+                error!(
+                    "Failed to parse line at offset {}: {:?}",
+                    len - data.input_len(),
+                    line.as_str()
+                );
+
+                parse_error(&line, SyntaxKind::TOKEN);
+                remaining
+            }
+        };
+    }
+
+    data
+}
+
+/// Adds an ERROR node containing `span` as a single token of `kind`
+fn parse_error(span: &Span, kind: SyntaxKind) {
+    span.start_node(SyntaxKind::ERROR);
+    span.token(kind, span.as_str());
+    span.finish_node();
+}
+
 fn parse_objdump_header(expr: Span) -> nom::IResult<Span, ()> {
     let (remaining, _) = skip_whitespace(expr, true)?;
     let (remaining, format) = take_while(|a| a != '\n')(remaining)?;
@@ -166,6 +244,186 @@ fn parse_objdump_line_start<'a>(
     Ok((remaining, ()))
 }
 
+/// With `objdump -S` the source is interleaved with the disassembly and `-l`
+/// adds the function name and `file:line` before the instructions for it.
+/// These can only be told apart from instructions by the leading address.
+fn is_objdump_source(expr: &Span) -> bool {
+    let FileType::ObjDump(options) = &expr.config().file_type else {
+        return false;
+    };
+    let line = expr
+        .as_str()
+        .split('\n')
+        .next()
+        .unwrap_or_default()
+        .trim_end();
+
+    options.show_leading_addr
+        && !line.is_empty()
+        && !is_disassembly(line, options.show_raw_insn)
+        && !line.starts_with(';')
+        && !line.starts_with(&expr.config().comment_start)
+        && !line.starts_with("...")
+}
+
+/// The start of a function e.g. `0000000000001139 <main>:`, or an address
+/// followed by whitespace and the opcode bytes e.g. `1139:\t55`. Source lines
+/// such as `add:` also start with hex digits and a colon, so the opcode bytes
+/// are needed to tell them apart unless `--no-show-raw-insn` was used.
+fn is_disassembly(line: &str, show_raw_insn: bool) -> bool {
+    let address = line.find(|a: char| !is_hex(a)).unwrap_or(line.len());
+    if address == 0 {
+        return false;
+    }
+
+    let after = &line[address..];
+    if after.starts_with(" <") {
+        return after.ends_with(">:");
+    }
+
+    let Some(after) = after.strip_prefix(':') else {
+        return false;
+    };
+    let bytes = after.trim_start_matches([' ', '\t']);
+    if bytes.len() == after.len() {
+        return false;
+    }
+
+    let opcode = bytes.split([' ', '\t']).next().unwrap_or_default();
+    !show_raw_insn || (!opcode.is_empty() && opcode.len() % 2 == 0 && opcode.chars().all(is_hex))
+}
+
+/// Splits a marker from `objdump -l` into the file, line and discriminator
+/// e.g. `/src/main.c:3 (discriminator 1)`
+fn split_source_location(line: &str) -> Option<(&str, &str, &str)> {
+    let (location, discriminator) = match line.find(" (discriminator ") {
+        Some(index) if line.ends_with(')') => line.split_at(index),
+        _ => (line, ""),
+    };
+    let (file, number) = location.rsplit_once(':')?;
+
+    (!file.is_empty() && !number.is_empty() && number.chars().all(|a| a.is_ascii_digit()))
+        .then_some((file, number, discriminator))
+}
+
+fn parse_objdump_source(expr: Span) -> NomResultElement {
+    let (remaining, line) = take_while(|a| a != '\n')(expr)?;
+    let text = line.as_str().trim_end();
+
+    if let Some((file, number, discriminator)) = split_source_location(text) {
+        remaining.start_node(SyntaxKind::SOURCE_LOCATION);
+        remaining.token(SyntaxKind::STRING, file);
+        remaining.token(SyntaxKind::OPERATOR, ":");
+        remaining.token(SyntaxKind::NUMBER, number);
+        if !discriminator.is_empty() {
+            remaining.token(SyntaxKind::WHITESPACE, " ");
+            remaining.token(SyntaxKind::METADATA, discriminator.trim_start());
+        }
+        remaining.finish_node();
+    } else if text.ends_with("():") && !text.contains(char::is_whitespace) {
+        // The function name from `-l` e.g. `main():`
+        remaining.token(SyntaxKind::METADATA, text);
+    } else {
+        remaining.start_node(SyntaxKind::SOURCE_LINE);
+        remaining.token(SyntaxKind::COMMENT, text);
+        remaining.finish_node();
+    }
+
+    if text.len() < line.as_str().len() {
+        remaining.token(SyntaxKind::WHITESPACE, &line.as_str()[text.len()..]);
+    }
+
+    Ok((remaining, ()))
+}
+
+/// With `objdump -r` the relocations are listed after the instruction they
+/// apply to e.g. `1d: R_X86_64_PLT32\tfoo-0x4`
+fn is_objdump_relocation(expr: &Span) -> bool {
+    if !matches!(expr.config().file_type, FileType::ObjDump(_)) {
+        return false;
+    }
+
+    let line = expr.as_str().split('\n').next().unwrap_or_default();
+    let name = split_objdump_address(line)
+        .map(|(_, rest)| rest)
+        .unwrap_or(line)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default();
+
+    name.len() > 2
+        && name.starts_with("R_")
+        && name
+            .chars()
+            .all(|a| a.is_ascii_uppercase() || a.is_ascii_digit() || a == '_')
+}
+
+/// Splits the address from the start of a line e.g. `1d:`
+fn split_objdump_address(line: &str) -> Option<(&str, &str)> {
+    let address = line.find(|a: char| !is_hex(a)).unwrap_or(line.len());
+    let rest = line[address..].strip_prefix(':')?;
+
+    (address > 0).then_some((&line[..address], rest))
+}
+
+/// Splits the addend from the symbol a relocation is against e.g. `foo-0x4`
+fn split_relocation_addend(target: &str) -> Option<(&str, &str, &str)> {
+    let index = target.get(1..)?.rfind(['+', '-'])? + 1;
+    let (symbol, addend) = target.split_at(index);
+    let (operator, number) = addend.split_at(1);
+    let digits = number.strip_prefix("0x")?;
+
+    (!digits.is_empty() && digits.chars().all(is_hex)).then_some((symbol, operator, number))
+}
+
+/// Adds a token for the whitespace at the start of the text and returns the
+/// rest of it
+fn leading_whitespace<'a>(expr: &Span, text: &'a str) -> &'a str {
+    let trimmed = text.trim_start();
+    if trimmed.len() < text.len() {
+        expr.token(SyntaxKind::WHITESPACE, &text[..text.len() - trimmed.len()]);
+    }
+    trimmed
+}
+
+fn parse_objdump_relocation(expr: Span) -> NomResultElement {
+    let (remaining, line) = take_while(|a| a != '\n')(expr)?;
+    let text = line.as_str().trim_end();
+
+    remaining.start_node(SyntaxKind::RELOCATION);
+
+    let mut rest = text;
+    if let Some((address, after)) = split_objdump_address(rest) {
+        remaining.token(SyntaxKind::OBJDUMP_OFFSET, address);
+        remaining.token(SyntaxKind::METADATA, ":");
+        rest = after;
+    }
+
+    let rest = leading_whitespace(&remaining, rest);
+    let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    remaining.token(SyntaxKind::RELOCATION, &rest[..end]);
+
+    let target = leading_whitespace(&remaining, &rest[end..]);
+    if !target.is_empty() {
+        match split_relocation_addend(target) {
+            Some((symbol, operator, addend)) => {
+                remaining.token(SyntaxKind::TOKEN, symbol);
+                remaining.token(SyntaxKind::OPERATOR, operator);
+                remaining.token(SyntaxKind::NUMBER, addend);
+            }
+            None => remaining.token(SyntaxKind::TOKEN, target),
+        }
+    }
+
+    remaining.finish_node();
+
+    if text.len() < line.as_str().len() {
+        remaining.token(SyntaxKind::WHITESPACE, &line.as_str()[text.len()..]);
+    }
+
+    Ok((remaining, ()))
+}
+
 fn objdump_angle_brackets(expr: Span) -> NomResultElement {
     if expr.as_str().ends_with(':') {
         let (remaining, token) = take_while(|a| a != '\n')(expr)?;
@@ -199,8 +457,12 @@ fn parse_next(expr: Span) -> NomResultElement {
             remaining.token(SyntaxKind::METADATA, token.as_str());
             Ok((remaining, ()))
         }
+        _ if is_objdump_relocation(&expr) => parse_objdump_relocation(expr),
+        _ if is_objdump_source(&expr) => parse_objdump_source(expr),
         _ => {
-            process_comment!(expr, true);
+            if !is_preprocessor_line(expr.as_str()) {
+                process_comment!(expr, true);
+            }
 
             // Extract the current line from the input for processing
             let (remaining, expr) = take_while(|a| a != '\n')(expr)?;
@@ -230,7 +492,9 @@ fn parse_next(expr: Span) -> NomResultElement {
 }
 
 fn process_line(expr: Span) -> NomResultElement {
-    process_comment!(expr, false);
+    if !is_preprocessor_line(expr.as_str()) {
+        process_comment!(expr, false);
+    }
     let config = &expr.extra().config;
 
     // Check to see if we need to end any nodes before processing this one
@@ -247,6 +511,67 @@ fn process_line(expr: Span) -> NomResultElement {
             expr.finish_node();
         }
 
+        let mnemonic = first_token(expr.as_str(), config);
+
+        // Any labels within a macro end along with the macro
+        if matches!(kind, SyntaxKind::DIRECTIVE)
+            && is_macro_end(mnemonic)
+            && expr.is_inside_kind(SyntaxKind::MACRO_DEF)
+        {
+            while !expr.current_indent_is_kind(SyntaxKind::MACRO_DEF) {
+                expr.finish_node();
+            }
+        }
+
+        // A section ends any labels and the previous section, sections are
+        // only tracked outside of macros and conditionals as they can't end
+        // the enclosing node. A pushed section is within the section that it
+        // was pushed from, which carries on after the `.popsection`.
+        if matches!(kind, SyntaxKind::DIRECTIVE)
+            && is_section_start(mnemonic)
+            && !expr.is_inside_kind(SyntaxKind::MACRO_DEF)
+            && !expr.is_inside_kind(SyntaxKind::CONDITIONAL)
+        {
+            while !expr.current_indent_is_kind(SyntaxKind::SECTION)
+                && !expr.current_indent_is_kind(SyntaxKind::ROOT)
+            {
+                expr.finish_node();
+            }
+
+            if is_section_push(mnemonic) {
+                expr.push_section();
+                expr.start_node(SyntaxKind::SECTION);
+            } else if is_section_pop(mnemonic) {
+                if expr.pop_section() && expr.current_indent_is_kind(SyntaxKind::SECTION) {
+                    expr.finish_node();
+                }
+            } else {
+                if expr.current_indent_is_kind(SyntaxKind::SECTION) {
+                    expr.finish_node();
+                }
+                expr.start_node(SyntaxKind::SECTION);
+            }
+        }
+
+        // Each part of a conditional is a separate branch, labels within a
+        // branch end along with it.
+        if matches!(kind, SyntaxKind::DIRECTIVE)
+            && (is_conditional_else(mnemonic) || is_conditional_end(mnemonic))
+            && expr.is_inside_kind(SyntaxKind::CONDITIONAL_BRANCH)
+        {
+            while !expr.current_indent_is_kind(SyntaxKind::CONDITIONAL_BRANCH) {
+                expr.finish_node();
+            }
+            expr.finish_node();
+
+            if is_conditional_else(mnemonic) {
+                expr.start_node(SyntaxKind::CONDITIONAL_BRANCH);
+            }
+        } else if matches!(kind, SyntaxKind::DIRECTIVE) && is_conditional_start(mnemonic) {
+            expr.start_node(SyntaxKind::CONDITIONAL);
+            expr.start_node(SyntaxKind::CONDITIONAL_BRANCH);
+        }
+
         kind
     };
     expr.start_node(kind);
@@ -257,8 +582,6 @@ fn process_line(expr: Span) -> NomResultElement {
     };
     let (expr, _) = skip_whitespace(expr, false)?;
     let (expr, token) = take_while(|a: char| !a.is_whitespace())(expr)?;
-    let actual_kind = start_kind(token.as_str());
-    assert_eq!(kind, actual_kind);
 
     let expr = if matches!(kind, SyntaxKind::DIRECTIVE | SyntaxKind::INSTRUCTION) {
         expr.token(SyntaxKind::MNEMONIC, token.as_str());
@@ -283,20 +606,54 @@ fn process_line(expr: Span) -> NomResultElement {
     if x.current_indent_is_kind(SyntaxKind::EXPR) {
         x.finish_node();
     }
-    if matches!(kind, SyntaxKind::DIRECTIVE | SyntaxKind::INSTRUCTION) {
+    if matches!(kind, SyntaxKind::DIRECTIVE) && is_macro_start(token.as_str()) {
+        // The macro body continues until the matching `.endm`
+        x.change_node_kind(SyntaxKind::MACRO_DEF);
+    } else if matches!(kind, SyntaxKind::DIRECTIVE | SyntaxKind::INSTRUCTION) {
         x.finish_node();
+
+        if is_macro_end(token.as_str()) && x.current_indent_is_kind(SyntaxKind::MACRO_DEF) {
+            x.finish_node();
+        }
+
+        if is_conditional_end(token.as_str()) && x.current_indent_is_kind(SyntaxKind::CONDITIONAL) {
+            x.finish_node();
+        }
     }
 
     if x.as_str().starts_with("/*") {
         return Err(nom::Err::Error(nom::error::Error::new(x, ErrorKind::CrLf)));
-    } else {
-        assert!(x.as_str().is_empty());
+    } else if !x.as_str().is_empty() {
+        parse_error(&x, SyntaxKind::TOKEN);
+        return Ok((x.take_split(x.input_len()).0, ()));
     }
 
     Ok((x, ()))
 }
 
 fn pre_process_next(line: &str, config: &ParserConfig) -> SyntaxKind {
+    let token = first_token(line, config);
+    let rest = line
+        .trim_start_matches([' ', '\t'])
+        .strip_prefix(token)
+        .unwrap_or_default();
+
+    line_start_kind(token, rest, config)
+}
+
+/// Gets the kind of the line that starts with `token`, `rest` is the remainder
+/// of the line after the token.
+fn line_start_kind(token: &str, rest: &str, config: &ParserConfig) -> SyntaxKind {
+    if config.dialect == Dialect::Nasm {
+        if let Some(kind) = nasm::start_kind(token, rest.split_whitespace().next()) {
+            return kind;
+        }
+    }
+
+    start_kind(token)
+}
+
+fn first_token<'a>(line: &'a str, config: &ParserConfig) -> &'a str {
     let token = match config.file_type {
         FileType::Assembly => line.trim_start_matches(|a| a == ' ' || a == '\t'),
         FileType::ObjDump(_) => {
@@ -316,7 +673,7 @@ fn pre_process_next(line: &str, config: &ParserConfig) -> SyntaxKind {
     };
 
     let mut split = token.split(|a: char| a.is_whitespace());
-    start_kind(split.next().unwrap_or(token))
+    split.next().unwrap_or(token)
 }
 
 fn start_kind(token: &str) -> SyntaxKind {
@@ -345,7 +702,15 @@ fn parse_line(expr: Span) -> NomResultElement {
             // If we start with a comment parse it.
             process_comment!(expr, false);
 
-            let (remaining, token) = take_while(|a: char| !is_special_char(a, config))(expr)?;
+            let (remaining, token) =
+                take_while(|a: char| !is_special_char(a, config))(expr.clone())?;
+            if token.is_empty() {
+                // A special character without an action can't be parsed
+                let (remaining, token) = expr.take_split(first.len_utf8());
+                parse_error(&token, SyntaxKind::TOKEN);
+                return Ok((remaining, ()));
+            }
+
             span_to_token(&token);
             Ok((remaining, ()))
         }
@@ -364,6 +729,12 @@ pub fn register_name(name: &str) -> &str {
 
 /// Determine if `name` is a valid register
 fn is_register(name: &str, config: &ParserConfig) -> bool {
+    // AT&T syntax requires the `%` prefix on registers, without it the name
+    // refers to a symbol, Intel syntax never uses the prefix.
+    if is_x86(&config.architecture) && name.starts_with('%') != (config.dialect == Dialect::Att) {
+        return false;
+    }
+
     if let Some(registers) = config.registers {
         let name = register_name(name);
         registers.get(&UniCase::ascii(name)).is_some()
@@ -372,6 +743,26 @@ fn is_register(name: &str, config: &ParserConfig) -> bool {
     }
 }
 
+/// Determine if `name` is one of the Intel syntax keywords that can be used
+/// within an operand e.g. `qword ptr [rax]` or `offset label`
+fn is_intel_keyword(name: &str, config: &ParserConfig) -> bool {
+    const KEYWORDS: [&str; 13] = [
+        "byte", "word", "dword", "qword", "tbyte", "xword", "oword", "xmmword", "ymmword",
+        "zmmword", "ptr", "offset", "flat",
+    ];
+
+    // NASM drops the `ptr` and has its own addressing keywords e.g. `[rel label]`
+    const NASM_KEYWORDS: [&str; 9] = [
+        "yword", "zword", "tword", "rel", "abs", "strict", "near", "far", "short",
+    ];
+
+    is_x86(&config.architecture)
+        && config.dialect.is_intel()
+        && (KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(name))
+            || (config.dialect == Dialect::Nasm
+                && NASM_KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(name))))
+}
+
 /// Converts the span into a GreenToken
 fn span_to_token(token: &Span) {
     if is_numeric(token.as_str()) {
@@ -380,7 +771,7 @@ fn span_to_token(token: &Span) {
         token.token(SyntaxKind::FLOAT, token.as_str());
     } else if token.as_str().ends_with(':') {
         token.token(SyntaxKind::LABEL, token.as_str());
-    } else if token.config().architecture == Architecture::AArch64 && token.as_str() == ".req" {
+    } else if is_arm(&token.config().architecture) && token.as_str() == ".req" {
         token.extra().builder.change_node_kind(SyntaxKind::ALIAS);
         token
             .extra()
@@ -400,6 +791,8 @@ fn span_to_token(token: &Span) {
             .change_previous_token_kind(1, SyntaxKind::NAME);
         token.token(SyntaxKind::MNEMONIC, token.as_str());
         token.start_node(SyntaxKind::EXPR);
+    } else if is_intel_keyword(token.as_str(), token.config()) {
+        token.token(SyntaxKind::KEYWORD, token.as_str());
     } else if let Some(kind) = token.extra().builder.alias().get_kind(token.as_str()) {
         token.token(kind, token.as_str());
     } else if is_register(token.as_str(), token.extra().config) {
@@ -458,7 +851,7 @@ fn parse_brackets(remaining: Span, tokens: (SyntaxKind, SyntaxKind)) -> NomResul
         (SyntaxKind::L_SQ, SyntaxKind::R_SQ) => ("[", "]"),
         (SyntaxKind::L_CURLY, SyntaxKind::R_CURLY) => ("{", "}"),
         (SyntaxKind::L_ANGLE, SyntaxKind::R_ANGLE) => ("<", ">"),
-        _ => panic!("Unexpected bracket type"),
+        _ => unreachable!("Unexpected bracket type"),
     };
 
     let span = get_bracket_span(remaining.clone(), pair);
@@ -472,15 +865,31 @@ fn parse_brackets(remaining: Span, tokens: (SyntaxKind, SyntaxKind)) -> NomResul
 
         Ok((remaining, ()))
     } else {
+        // The bracket isn't closed, the rest of the line is treated as if it
+        // were within the brackets. The opening bracket is kept as a normal
+        // token so that completions still work while the operand is typed.
         remaining.start_node(SyntaxKind::BRACKETS);
-        remaining.token(tokens.0, pair.0);
         let (remaining, _) = remaining.take_split(1);
+        remaining.token(tokens.0, pair.0);
         let (remaining, _) = many0(parse_line)(remaining)?;
         remaining.finish_node();
         Ok((remaining, ()))
     }
 }
 
+/// A closing bracket without a matching opening bracket
+fn parse_stray_bracket(expr: Span) -> NomResultElement {
+    let (remaining, bracket) = expr.take_split(1);
+    let kind = match bracket.as_str() {
+        ")" => SyntaxKind::R_PAREN,
+        "]" => SyntaxKind::R_SQ,
+        _ => SyntaxKind::R_CURLY,
+    };
+
+    parse_error(&bracket, kind);
+    Ok((remaining, ()))
+}
+
 /// Skip the parser to the end of the line and generate a `TokenValue::Comment` with the contents
 /// of `remaining`
 /// # Arguments
@@ -600,10 +1009,18 @@ where
 
 /// Parse a string constant
 fn parse_string(remaining: Span) -> NomResultElement {
-    let (remaining, inner) = terminated(preceded(tag("\""), str_parse), tag("\""))(remaining)?;
-    remaining.token(SyntaxKind::STRING, &format!(r#""{}""#, inner.as_str()));
-
-    Ok((remaining, ()))
+    match terminated(preceded(tag("\""), str_parse), tag("\""))(remaining.clone()) {
+        Ok((remaining, inner)) => {
+            remaining.token(SyntaxKind::STRING, &format!(r#""{}""#, inner.as_str()));
+            Ok((remaining, ()))
+        }
+        Err(_) => {
+            // An unterminated string continues to the end of the line
+            let (remaining, string) = take_while(|a| a != '\n')(remaining)?;
+            parse_error(&string, SyntaxKind::STRING);
+            Ok((remaining, ()))
+        }
+    }
 }
 
 fn handle_arm_relocation(expr: Span) -> NomResultElement {
@@ -668,13 +1085,30 @@ fn get_action(c: char, config: &ParserConfig) -> Option<ProcessFunction> {
         '(' => Some(|expr| parse_brackets(expr, (SyntaxKind::L_PAREN, SyntaxKind::R_PAREN))),
         '[' => Some(|expr| parse_brackets(expr, (SyntaxKind::L_SQ, SyntaxKind::R_SQ))),
         '{' => Some(|expr| parse_brackets(expr, (SyntaxKind::L_CURLY, SyntaxKind::R_CURLY))),
+        ')' | ']' | '}' => Some(parse_stray_bracket),
         '"' => Some(parse_string),
-        '@' => Some(handle_at_relocation),
+        // On AArch32 the `@` character starts a comment
+        '@' if config.architecture != Architecture::AArch32 => Some(handle_at_relocation),
         '<' if matches!(config.file_type, FileType::ObjDump(_)) => Some(objdump_angle_brackets),
-        '#' if config.architecture == Architecture::AArch64 => {
+        '#' if is_arm(&config.architecture) => {
             Some(|expr| process_token(expr, '#', SyntaxKind::IMMEDIATE))
         }
-        ':' if config.architecture == Architecture::AArch64 => Some(handle_arm_relocation),
+        ':' if is_arm(&config.architecture) => Some(handle_arm_relocation),
+        '!' if config.architecture == Architecture::AArch32 => {
+            Some(|expr| process_token(expr, '!', SyntaxKind::TOKEN))
+        }
+        '=' if config.architecture == Architecture::AArch32 => {
+            Some(|expr| process_token(expr, '=', SyntaxKind::OPERATOR))
+        }
+        '$' if is_x86(&config.architecture) && config.dialect == Dialect::Att => {
+            Some(|expr| process_token(expr, '$', SyntaxKind::IMMEDIATE))
+        }
+        '*' if is_x86(&config.architecture) => {
+            Some(|expr| process_token(expr, '*', SyntaxKind::OPERATOR))
+        }
+        ':' if is_x86(&config.architecture) => {
+            Some(|expr| process_token(expr, ':', SyntaxKind::OPERATOR))
+        }
         _ => None,
     }
 }
@@ -683,12 +1117,26 @@ fn get_action(c: char, config: &ParserConfig) -> Option<ProcessFunction> {
 fn is_special_char(c: char, config: &ParserConfig) -> bool {
     match c {
         ' ' | ',' | '\n' | '\t' | '+' | '-' | '(' | '[' | '{' | '"' | '@' => true,
+        ')' | ']' | '}' => true,
         '<' if matches!(config.file_type, FileType::ObjDump(_)) => true,
-        '#' | ':' if config.architecture == Architecture::AArch64 => true,
+        '#' | ':' if is_arm(&config.architecture) => true,
+        '!' | '=' if config.architecture == Architecture::AArch32 => true,
+        '*' | ':' if is_x86(&config.architecture) => true,
+        ';' if config.dialect == Dialect::Nasm => true,
         _ => false,
     }
 }
 
+#[inline]
+fn is_x86(arch: &Architecture) -> bool {
+    matches!(arch, Architecture::X86_64 | Architecture::X86)
+}
+
+#[inline]
+fn is_arm(arch: &Architecture) -> bool {
+    matches!(arch, Architecture::AArch64 | Architecture::AArch32)
+}
+
 fn is_hex(data: char) -> bool {
     is_hex_digit(data as u8)
 }
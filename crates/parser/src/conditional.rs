@@ -0,0 +1,48 @@
+//! Conditional assembly e.g. `.if`, `.ifdef`, `.else` and `.endif`, each of
+//! the directives starts a new branch within a CONDITIONAL node. The C and
+//! NASM preprocessor conditionals e.g. `#ifdef` and `%ifdef` are handled in
+//! the same way.
+
+pub(crate) fn is_conditional_start(mnemonic: &str) -> bool {
+    const STARTS: [&str; 25] = [
+        ".if",
+        ".ifdef",
+        ".ifndef",
+        ".ifnotdef",
+        ".ifeq",
+        ".ifne",
+        ".ifgt",
+        ".ifge",
+        ".iflt",
+        ".ifle",
+        ".ifb",
+        ".ifnb",
+        ".ifc",
+        ".ifnc",
+        ".ifeqs",
+        ".ifnes",
+        "#if",
+        "#ifdef",
+        "#ifndef",
+        "%if",
+        "%ifdef",
+        "%ifndef",
+        "%ifidn",
+        "%ifidni",
+        "%ifmacro",
+    ];
+
+    STARTS.iter().any(|s| s.eq_ignore_ascii_case(mnemonic))
+}
+
+pub(crate) fn is_conditional_else(mnemonic: &str) -> bool {
+    const ELSES: [&str; 6] = [".else", ".elseif", "#else", "#elif", "%else", "%elif"];
+
+    ELSES.iter().any(|s| s.eq_ignore_ascii_case(mnemonic))
+}
+
+pub(crate) fn is_conditional_end(mnemonic: &str) -> bool {
+    const ENDS: [&str; 3] = [".endif", "#endif", "%endif"];
+
+    ENDS.iter().any(|s| s.eq_ignore_ascii_case(mnemonic))
+}
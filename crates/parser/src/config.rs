@@ -1,4 +1,4 @@
-use base::{Architecture, FileType};
+use base::{Architecture, Dialect, FileType};
 use unicase::UniCase;
 
 /// Configuration for the parser
@@ -14,6 +14,14 @@ pub struct ParserConfig {
 
     /// The registers that are allowed for this parser
     pub registers: Option<&'static phf::Map<UniCase<&'static str>, i8>>,
+
+    /// The syntax dialect, this is only used for x86
+    pub dialect: Dialect,
+
+    /// The operands of instructions for architectures where registers can be
+    /// written as a plain number, this is used to find the operands that are
+    /// registers.
+    pub register_operands: Option<&'static phf::Map<UniCase<&'static str>, &'static str>>,
 }
 
 impl PartialEq for ParserConfig {
@@ -21,6 +29,7 @@ impl PartialEq for ParserConfig {
         self.comment_start == other.comment_start
             && self.architecture == other.architecture
             && self.file_type == other.file_type
+            && self.dialect == other.dialect
     }
 }
 
@@ -31,6 +40,8 @@ impl Default for ParserConfig {
             architecture: Architecture::Unknown,
             file_type: Default::default(),
             registers: None,
+            dialect: Default::default(),
+            register_operands: None,
         }
     }
 }
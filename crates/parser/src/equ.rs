@@ -1,7 +1,61 @@
 use std::mem;
 
-use rowan::{GreenNode, GreenToken, NodeOrToken};
-use syntax::ast::SyntaxKind;
+use rowan::{GreenNode, GreenToken, Language, NodeOrToken};
+use syntax::ast::{AssemblyLanguage, SyntaxKind};
+
+/// `.set` assigns a constant in the same way as `.equ` but allows the value to
+/// be changed later on.
+pub(crate) fn is_equ(mnemonic: &str) -> bool {
+    mnemonic.eq_ignore_ascii_case(".equ") || mnemonic.eq_ignore_ascii_case(".set")
+}
+
+/// `.unreq` removes a register alias so that the name can be used again
+pub(crate) fn is_unreq(node: &GreenNode) -> bool {
+    node.children()
+        .filter_map(|c| c.into_token())
+        .find(|t| t.kind() == SyntaxKind::MNEMONIC.into())
+        .is_some_and(|t| t.text().eq_ignore_ascii_case(".unreq"))
+}
+
+/// An assignment e.g. `SIZE = 16` is the same as `.set SIZE, 16` but is
+/// parsed as an instruction with the name as the mnemonic.
+pub(crate) fn is_assignment(items: &[NodeOrToken<GreenNode, GreenToken>]) -> bool {
+    items
+        .iter()
+        .skip(1)
+        .find(|i| i.kind() != SyntaxKind::WHITESPACE.into())
+        .and_then(|i| i.as_token())
+        .is_some_and(is_equals)
+}
+
+/// `=` is only an operator on some architectures e.g. ARM `ldr x0, =label`
+fn is_equals(token: &GreenToken) -> bool {
+    token.text() == "="
+        && matches!(
+            AssemblyLanguage::kind_from_raw(token.kind()),
+            SyntaxKind::OPERATOR | SyntaxKind::TOKEN
+        )
+}
+
+/// Converts an assignment into the same form as `SIZE EQU 16`, the name and
+/// `=` become the NAME and MNEMONIC and the rest is the EXPR.
+pub(crate) fn transform_assignment_node(items: &mut Vec<NodeOrToken<GreenNode, GreenToken>>) {
+    let Some(index) = items
+        .iter()
+        .position(|i| i.as_token().is_some_and(is_equals))
+    else {
+        return;
+    };
+
+    if let Some(name) = items.first().and_then(|i| i.as_token()).cloned() {
+        items[0] = NodeOrToken::Token(GreenToken::new(SyntaxKind::NAME.into(), name.text()));
+    }
+    items[index] = NodeOrToken::Token(GreenToken::new(SyntaxKind::MNEMONIC.into(), "="));
+
+    let expr = items.drain(index + 1..).collect::<Vec<_>>();
+    let node = GreenNode::new(SyntaxKind::EXPR.into(), expr);
+    items.push(NodeOrToken::Node(node));
+}
 
 pub(crate) fn transform_equ_node(items: &mut Vec<NodeOrToken<GreenNode, GreenToken>>) {
     let name_element = items
@@ -23,11 +23,15 @@ pub(super) fn handle_include(
     from: Option<&str>,
     load: LoadFileFn,
 ) -> Option<ParsedInclude> {
+    // The C preprocessor also allows system headers e.g. `#include <file.h>`
     let filename = node
         .children()
-        .find(|n| n.kind() == SyntaxKind::STRING.into())
-        .and_then(|t| t.into_token())
-        .map(|t| t.text().trim_matches('"').to_string())?;
+        .filter_map(|t| t.into_token())
+        .find(|t| {
+            t.kind() == SyntaxKind::STRING.into()
+                || (t.text().starts_with('<') && t.text().ends_with('>'))
+        })
+        .map(|t| t.text().trim_matches(['"', '<', '>']).to_string())?;
 
     load(config, from.unwrap_or_default(), filename.as_str())
 }
@@ -1,17 +1,28 @@
 use config::ParserConfig;
 use rowan::GreenNode;
+use std::collections::HashSet;
 
 #[macro_use]
 extern crate log;
 
+mod bare_registers;
 mod builder;
 mod combinators;
+mod conditional;
 pub mod config;
 mod equ;
 mod include;
+mod macros;
+mod memory;
+mod nasm;
+mod preprocessor;
+mod reparse;
+mod section;
 mod span;
 
+pub use bare_registers::bare_register_name;
 pub use combinators::*;
+pub use reparse::{is_structural, parse_lines};
 use syntax::alias::Alias;
 
 pub type LoadFileFn =
@@ -30,12 +41,14 @@ pub fn parse_asm(
 pub struct ParsedData {
     pub root: GreenNode,
     pub alias: Alias,
+    pub macros: HashSet<String>,
     pub included_files: Vec<ParsedInclude>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ParsedInclude {
     pub alias: Alias,
+    pub macros: HashSet<String>,
     pub root: GreenNode,
     pub included_files: Vec<ParsedInclude>,
     pub id: String,
@@ -0,0 +1,50 @@
+use std::mem;
+
+use rowan::{GreenNode, GreenNodeData, GreenToken, NodeOrToken};
+use syntax::ast::SyntaxKind;
+
+pub(crate) fn is_macro_start(mnemonic: &str) -> bool {
+    const STARTS: [&str; 3] = [".macro", "%macro", "%imacro"];
+
+    STARTS.iter().any(|s| s.eq_ignore_ascii_case(mnemonic))
+}
+
+pub(crate) fn is_macro_end(mnemonic: &str) -> bool {
+    mnemonic.eq_ignore_ascii_case(".endm") || mnemonic.eq_ignore_ascii_case("%endmacro")
+}
+
+/// Converts the macro name in a `.macro` node into a NAME token and returns
+/// the name of the macro.
+pub(crate) fn transform_macro_node(
+    items: &mut [NodeOrToken<GreenNode, GreenToken>],
+) -> Option<String> {
+    let name_element = items
+        .iter_mut()
+        .skip_while(|i| i.kind() != SyntaxKind::MNEMONIC.into())
+        .skip(1)
+        .find(|i| i.kind() != SyntaxKind::WHITESPACE.into())?;
+
+    let name = name_element.as_token()?.text().to_string();
+    let mut token = NodeOrToken::Token(GreenToken::new(SyntaxKind::NAME.into(), &name));
+    mem::swap(&mut token, name_element);
+
+    Some(name)
+}
+
+/// Gets the names of the macros defined in `node`
+pub(crate) fn macro_names(node: &GreenNodeData) -> Vec<String> {
+    node.children()
+        .filter_map(|c| c.into_node())
+        .flat_map(|n| {
+            if n.kind() == SyntaxKind::MACRO_DEF.into() {
+                n.children()
+                    .filter_map(|c| c.into_token())
+                    .find(|t| t.kind() == SyntaxKind::NAME.into())
+                    .map(|t| vec![t.text().to_string()])
+                    .unwrap_or_default()
+            } else {
+                macro_names(n)
+            }
+        })
+        .collect()
+}
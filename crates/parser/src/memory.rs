@@ -0,0 +1,535 @@
+//! Memory operands are grouped into a MEMORY_OPERAND node so that the parts
+//! of the address are kept together e.g. the displacement in `8(%rax)` and
+//! the writeback in `[x0, #16]!`. The parts within the brackets are split
+//! into BASE, INDEX, SCALE and DISPLACEMENT nodes.
+
+use base::Architecture;
+use rowan::{GreenNode, GreenToken, Language, NodeOrToken};
+use syntax::ast::{AssemblyLanguage, SyntaxKind};
+
+use crate::config::ParserConfig;
+
+type Element = NodeOrToken<GreenNode, GreenToken>;
+
+fn kind(element: &Element) -> SyntaxKind {
+    AssemblyLanguage::kind_from_raw(element.kind())
+}
+
+fn text_is(element: &Element, text: &str) -> bool {
+    element.as_token().is_some_and(|t| t.text() == text)
+}
+
+fn is_register(element: &Element) -> bool {
+    matches!(
+        kind(element),
+        SyntaxKind::REGISTER | SyntaxKind::REGISTER_ALIAS
+    )
+}
+
+fn children(node: &GreenNode) -> Vec<Element> {
+    node.children()
+        .map(|c| match c {
+            NodeOrToken::Node(n) => NodeOrToken::Node(n.to_owned()),
+            NodeOrToken::Token(t) => NodeOrToken::Token(t.to_owned()),
+        })
+        .collect()
+}
+
+/// Brackets are only an address if they contain a register, otherwise they
+/// are part of an expression e.g. `#(SIZE + 4)`.
+fn is_address(element: &Element) -> bool {
+    let Some(node) = element
+        .as_node()
+        .filter(|n| AssemblyLanguage::kind_from_raw(n.kind()) == SyntaxKind::BRACKETS)
+    else {
+        return false;
+    };
+
+    matches!(
+        node.children()
+            .next()
+            .map(|c| AssemblyLanguage::kind_from_raw(c.kind())),
+        Some(SyntaxKind::L_SQ | SyntaxKind::L_PAREN)
+    ) && node.children().any(|c| {
+        matches!(
+            AssemblyLanguage::kind_from_raw(c.kind()),
+            SyntaxKind::REGISTER | SyntaxKind::REGISTER_ALIAS
+        )
+    })
+}
+
+/// Checks if `items[index]` is part of the displacement before an address in
+/// parentheses e.g. `sym+8` in `sym+8(%rip)` or `(8)` in `ld 3,(8)(1)`, a
+/// segment register followed by a `:` is included as well.
+fn is_displacement(items: &[Element], index: usize) -> bool {
+    match kind(&items[index]) {
+        SyntaxKind::NUMBER
+        | SyntaxKind::TOKEN
+        | SyntaxKind::CONSTANT
+        | SyntaxKind::OPERATOR
+        | SyntaxKind::RELOCATION => true,
+        SyntaxKind::REGISTER => items.get(index + 1).is_some_and(|i| text_is(i, ":")),
+        SyntaxKind::BRACKETS => {
+            !is_address(&items[index])
+                && items[index]
+                    .as_node()
+                    .and_then(|n| n.children().next())
+                    .is_some_and(|c| {
+                        AssemblyLanguage::kind_from_raw(c.kind()) == SyntaxKind::L_PAREN
+                    })
+        }
+        _ => false,
+    }
+}
+
+/// Wraps `elements` in a node of `kind`, any whitespace at either end is
+/// left outside of the node.
+fn wrap(kind: SyntaxKind, elements: Vec<Element>) -> Vec<Element> {
+    let is_whitespace = |e: &Element| self::kind(e) == SyntaxKind::WHITESPACE;
+    let (Some(start), Some(end)) = (
+        elements.iter().position(|e| !is_whitespace(e)),
+        elements.iter().rposition(|e| !is_whitespace(e)),
+    ) else {
+        return elements;
+    };
+
+    let mut elements = elements;
+    let trailing = elements.split_off(end + 1);
+    let inner = elements.split_off(start);
+    elements.push(NodeOrToken::Node(GreenNode::new(kind.into(), inner)));
+    elements.extend(trailing);
+    elements
+}
+
+/// Splits `elements` on each comma, the commas are kept as their own parts
+fn split_commas(elements: Vec<Element>) -> Vec<Vec<Element>> {
+    let mut parts = vec![Vec::new()];
+    for element in elements {
+        if kind(&element) == SyntaxKind::COMMA {
+            parts.push(vec![element]);
+            parts.push(Vec::new());
+        } else if let Some(last) = parts.last_mut() {
+            last.push(element);
+        }
+    }
+
+    parts
+}
+
+/// Comma separated parts e.g. `[x0, x1, lsl #3]`, `[x0, #8]` or
+/// `(%rax,%rbx,4)`. In parentheses the base can be left out e.g. `(,%rbx,4)`
+/// and the displacement is outside of the brackets.
+fn comma_parts(inner: Vec<Element>, parentheses: bool) -> Vec<Element> {
+    let mut result = Vec::new();
+    let mut position = 0;
+
+    for part in split_commas(inner) {
+        if part.first().is_some_and(|e| kind(e) == SyntaxKind::COMMA) {
+            result.extend(part);
+            continue;
+        }
+
+        let kind = match position {
+            0 => SyntaxKind::BASE,
+            1 if parentheses || part.iter().any(is_register) => SyntaxKind::INDEX,
+            1 => SyntaxKind::DISPLACEMENT,
+            _ => SyntaxKind::SCALE,
+        };
+        position += 1;
+        result.extend(wrap(kind, part));
+    }
+
+    result
+}
+
+/// Intel syntax, where the address is a sum of terms e.g. `[rax+rbx*4+8]`.
+/// The `+` between the terms is left outside of the parts, while a `-` is
+/// kept with a displacement.
+fn sum_parts(inner: Vec<Element>) -> Vec<Element> {
+    let mut terms: Vec<Vec<Element>> = vec![Vec::new()];
+    for element in inner {
+        let sign = text_is(&element, "+") || text_is(&element, "-");
+        if sign && kind(&element) == SyntaxKind::OPERATOR {
+            terms.push(vec![element]);
+        } else if let Some(last) = terms.last_mut() {
+            last.push(element);
+        }
+    }
+
+    let mut has_base = false;
+    let mut result = Vec::new();
+    for mut term in terms {
+        if term.first().is_some_and(|e| text_is(e, "+")) {
+            result.push(term.remove(0));
+        }
+
+        let multiply = term.iter().position(|e| text_is(e, "*"));
+        match (term.iter().position(is_register), multiply) {
+            // `rbx*4` or `4*rbx`
+            (Some(register), Some(multiply)) => {
+                let right = term.split_off(multiply + 1);
+                let operator = term.split_off(multiply);
+                let (left, right) = if register < multiply {
+                    (
+                        wrap(SyntaxKind::INDEX, term),
+                        wrap(SyntaxKind::SCALE, right),
+                    )
+                } else {
+                    (
+                        wrap(SyntaxKind::SCALE, term),
+                        wrap(SyntaxKind::INDEX, right),
+                    )
+                };
+
+                result.extend(left);
+                result.extend(operator);
+                result.extend(right);
+            }
+            (Some(_), None) if !has_base => {
+                has_base = true;
+                result.extend(wrap(SyntaxKind::BASE, term));
+            }
+            (Some(_), None) => result.extend(wrap(SyntaxKind::INDEX, term)),
+            (None, _) => result.extend(wrap(SyntaxKind::DISPLACEMENT, term)),
+        }
+    }
+
+    result
+}
+
+/// Splits the contents of the brackets into the parts of the address
+fn address_parts(brackets: &GreenNode) -> GreenNode {
+    let mut inner = children(brackets);
+    let close = match inner.last().map(kind) {
+        Some(SyntaxKind::R_SQ | SyntaxKind::R_PAREN) => inner.pop(),
+        _ => None,
+    };
+    let open = inner.remove(0);
+
+    let parentheses = kind(&open) == SyntaxKind::L_PAREN;
+    let parts = if parentheses || inner.iter().any(|e| kind(e) == SyntaxKind::COMMA) {
+        comma_parts(inner, parentheses)
+    } else {
+        sum_parts(inner)
+    };
+
+    let children = std::iter::once(open)
+        .chain(parts)
+        .chain(close)
+        .collect::<Vec<_>>();
+    GreenNode::new(SyntaxKind::BRACKETS.into(), children)
+}
+
+/// The operand after an address in square brackets is a post-index writeback
+/// on Arm e.g. `#16` in `ldr x1, [x0], #16` or `x1` in `ld1 {v0.16b}, [x0], x1`.
+/// This returns the range of the items that make up the operand.
+fn post_index(items: &[Element], memory: usize) -> Option<(usize, usize)> {
+    let mut rest = items
+        .iter()
+        .enumerate()
+        .skip(memory + 1)
+        .filter(|(_, i)| kind(i) != SyntaxKind::WHITESPACE);
+
+    rest.next().filter(|(_, i)| kind(i) == SyntaxKind::COMMA)?;
+    let (start, first) = rest.next()?;
+    if kind(first) != SyntaxKind::IMMEDIATE && !is_register(first) {
+        return None;
+    }
+
+    let end = items
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take_while(|(_, i)| !matches!(kind(i), SyntaxKind::COMMA | SyntaxKind::COMMENT))
+        .filter(|(_, i)| kind(i) != SyntaxKind::WHITESPACE)
+        .last()
+        .map(|(index, _)| index + 1)?;
+
+    Some((start, end))
+}
+
+pub(crate) fn transform_memory_operands(items: &mut Vec<Element>, config: &ParserConfig) {
+    let mut index = 0;
+    while index < items.len() {
+        if !is_address(&items[index]) {
+            index += 1;
+            continue;
+        }
+
+        let parentheses = items[index]
+            .as_node()
+            .and_then(|n| n.children().next())
+            .is_some_and(|c| AssemblyLanguage::kind_from_raw(c.kind()) == SyntaxKind::L_PAREN);
+
+        let mut start = index;
+        while parentheses && start > 0 && is_displacement(items, start - 1) {
+            start -= 1;
+        }
+
+        // A leading `*` is an indirect branch e.g. `call *8(%rax)`
+        if start < index && text_is(&items[start], "*") {
+            start += 1;
+        }
+
+        // Pre-index writeback e.g. `[x0, #16]!`
+        let mut end = index + 1;
+        let pre_index = items.get(end).is_some_and(|i| text_is(i, "!"));
+        if pre_index {
+            end += 1;
+        }
+
+        let mut operand = items.drain(start..end).collect::<Vec<_>>();
+        let brackets = index - start;
+        if let Some(node) = operand[brackets].as_node() {
+            operand[brackets] = NodeOrToken::Node(address_parts(node));
+        }
+        if pre_index {
+            let writeback = operand.pop().unwrap();
+            operand.push(NodeOrToken::Node(GreenNode::new(
+                SyntaxKind::WRITEBACK.into(),
+                vec![writeback],
+            )));
+        }
+        if brackets > 0 {
+            let mut displacement = wrap(
+                SyntaxKind::DISPLACEMENT,
+                operand.drain(..brackets).collect(),
+            );
+            displacement.append(&mut operand);
+            operand = displacement;
+        }
+
+        let node = GreenNode::new(SyntaxKind::MEMORY_OPERAND.into(), operand);
+        items.insert(start, NodeOrToken::Node(node));
+        index = start + 1;
+
+        let arm = matches!(
+            config.architecture,
+            Architecture::AArch64 | Architecture::AArch32
+        );
+        if let Some((start, end)) =
+            post_index(items, start).filter(|_| arm && !parentheses && !pre_index)
+        {
+            let writeback = items.drain(start..end).collect::<Vec<_>>();
+            items.insert(
+                start,
+                NodeOrToken::Node(GreenNode::new(SyntaxKind::WRITEBACK.into(), writeback)),
+            );
+            index = start + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base::Dialect;
+    use pretty_assertions::assert_eq;
+    use syntax::ast::SyntaxNode;
+    use unicase::UniCase;
+
+    use super::*;
+    use crate::parse_asm;
+
+    static REGISTERS: phf::Map<UniCase<&'static str>, i8> = phf::phf_map! {
+        UniCase::ascii("x0") => 0, UniCase::ascii("x1") => 1, UniCase::ascii("x2") => 2,
+        UniCase::ascii("rax") => 0, UniCase::ascii("rbx") => 1, UniCase::ascii("rcx") => 2,
+        UniCase::ascii("rbp") => 3, UniCase::ascii("rip") => 4,
+    };
+
+    static POWERPC64_OPERANDS: phf::Map<UniCase<&'static str>, &'static str> = phf::phf_map! {
+        UniCase::ascii("ld") => "rm", UniCase::ascii("lwz") => "rm",
+    };
+
+    /// The parts of each memory operand in `data` as (kind, text)
+    fn parts(
+        data: &str,
+        architecture: Architecture,
+        dialect: Dialect,
+    ) -> Vec<(SyntaxKind, String)> {
+        let config = ParserConfig {
+            comment_start: architecture.default_comment_start().to_string(),
+            architecture,
+            dialect,
+            registers: Some(&REGISTERS),
+            ..Default::default()
+        };
+        let root = SyntaxNode::new_root(parse_asm(data, &config, None, |_, _, _| None).root);
+
+        root.descendants()
+            .filter(|d| {
+                matches!(
+                    d.kind(),
+                    SyntaxKind::BASE
+                        | SyntaxKind::INDEX
+                        | SyntaxKind::SCALE
+                        | SyntaxKind::DISPLACEMENT
+                        | SyntaxKind::WRITEBACK
+                )
+            })
+            .map(|d| (d.kind(), d.to_string()))
+            .collect()
+    }
+
+    fn aarch64(data: &str) -> Vec<(SyntaxKind, String)> {
+        parts(data, Architecture::AArch64, Dialect::default())
+    }
+
+    fn expected(parts: &[(SyntaxKind, &str)]) -> Vec<(SyntaxKind, String)> {
+        parts.iter().map(|(k, t)| (*k, t.to_string())).collect()
+    }
+
+    #[test]
+    fn test_aarch64_offset() {
+        assert_eq!(
+            aarch64("ldr x0, [x1, #8]"),
+            expected(&[(SyntaxKind::BASE, "x1"), (SyntaxKind::DISPLACEMENT, "#8")])
+        );
+        assert_eq!(
+            aarch64("ldr x0, [x1, :lo12:sym]"),
+            expected(&[
+                (SyntaxKind::BASE, "x1"),
+                (SyntaxKind::DISPLACEMENT, ":lo12:sym")
+            ])
+        );
+    }
+
+    #[test]
+    fn test_aarch64_index() {
+        assert_eq!(
+            aarch64("ldr x2, [x0, x1, lsl #3]!"),
+            expected(&[
+                (SyntaxKind::BASE, "x0"),
+                (SyntaxKind::INDEX, "x1"),
+                (SyntaxKind::SCALE, "lsl #3"),
+                (SyntaxKind::WRITEBACK, "!"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_aarch64_post_index() {
+        assert_eq!(
+            aarch64("ldr x1, [x0], #16"),
+            expected(&[(SyntaxKind::BASE, "x0"), (SyntaxKind::WRITEBACK, "#16")])
+        );
+        assert_eq!(
+            aarch64("ld1 {v0.16b}, [x0], x1 // next"),
+            expected(&[(SyntaxKind::BASE, "x0"), (SyntaxKind::WRITEBACK, "x1")])
+        );
+        assert_eq!(
+            aarch64("ldr x1, [x0]"),
+            expected(&[(SyntaxKind::BASE, "x0")])
+        );
+    }
+
+    #[test]
+    fn test_not_address() {
+        assert_eq!(aarch64("add x0, x1, #(4 + 8)"), expected(&[]));
+        assert_eq!(
+            aarch64("ld1 {v0.s}[1], [x0]"),
+            expected(&[(SyntaxKind::BASE, "x0")])
+        );
+    }
+
+    #[test]
+    fn test_att() {
+        let att = |data| parts(data, Architecture::X86_64, Dialect::Att);
+
+        assert_eq!(
+            att("movq 8(%rax,%rbx,4), %rcx"),
+            expected(&[
+                (SyntaxKind::DISPLACEMENT, "8"),
+                (SyntaxKind::BASE, "%rax"),
+                (SyntaxKind::INDEX, "%rbx"),
+                (SyntaxKind::SCALE, "4"),
+            ])
+        );
+        assert_eq!(
+            att("movl -8(%rbp,%rcx,8), %eax"),
+            expected(&[
+                (SyntaxKind::DISPLACEMENT, "-8"),
+                (SyntaxKind::BASE, "%rbp"),
+                (SyntaxKind::INDEX, "%rcx"),
+                (SyntaxKind::SCALE, "8"),
+            ])
+        );
+        assert_eq!(
+            att("movq sym(,%rbx,8), %rax"),
+            expected(&[
+                (SyntaxKind::DISPLACEMENT, "sym"),
+                (SyntaxKind::INDEX, "%rbx"),
+                (SyntaxKind::SCALE, "8"),
+            ])
+        );
+        assert_eq!(
+            att("leaq (,%rbx,8), %rax"),
+            expected(&[(SyntaxKind::INDEX, "%rbx"), (SyntaxKind::SCALE, "8")])
+        );
+        assert_eq!(
+            att("call *sym+8(%rip)"),
+            expected(&[
+                (SyntaxKind::DISPLACEMENT, "sym+8"),
+                (SyntaxKind::BASE, "%rip"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_powerpc() {
+        let powerpc = |data| {
+            let config = ParserConfig {
+                comment_start: String::from("#"),
+                architecture: Architecture::PowerPC64,
+                register_operands: Some(&POWERPC64_OPERANDS),
+                ..Default::default()
+            };
+            let root = SyntaxNode::new_root(parse_asm(data, &config, None, |_, _, _| None).root);
+
+            root.descendants()
+                .filter(|d| matches!(d.kind(), SyntaxKind::BASE | SyntaxKind::DISPLACEMENT))
+                .map(|d| (d.kind(), d.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            powerpc("ld 3,8(1)"),
+            expected(&[(SyntaxKind::DISPLACEMENT, "8"), (SyntaxKind::BASE, "1")])
+        );
+        assert_eq!(
+            powerpc("ld 3,(8)(1)"),
+            expected(&[(SyntaxKind::DISPLACEMENT, "(8)"), (SyntaxKind::BASE, "1")])
+        );
+        assert_eq!(
+            powerpc("lwz 9,-16(31)"),
+            expected(&[(SyntaxKind::DISPLACEMENT, "-16"), (SyntaxKind::BASE, "31")])
+        );
+    }
+
+    #[test]
+    fn test_intel() {
+        let intel = |data| parts(data, Architecture::X86_64, Dialect::Intel);
+
+        assert_eq!(
+            intel("mov rcx, [rax+rbx*4+8]"),
+            expected(&[
+                (SyntaxKind::BASE, "rax"),
+                (SyntaxKind::INDEX, "rbx"),
+                (SyntaxKind::SCALE, "4"),
+                (SyntaxKind::DISPLACEMENT, "8"),
+            ])
+        );
+        assert_eq!(
+            intel("mov rcx, qword ptr [rbp - 8]"),
+            expected(&[(SyntaxKind::BASE, "rbp"), (SyntaxKind::DISPLACEMENT, "- 8")])
+        );
+        assert_eq!(
+            intel("lea rax, [8*rbx]"),
+            expected(&[(SyntaxKind::SCALE, "8"), (SyntaxKind::INDEX, "rbx")])
+        );
+        // The register after the address isn't a writeback on x86
+        assert_eq!(
+            intel("mov [rax], rbx"),
+            expected(&[(SyntaxKind::BASE, "rax")])
+        );
+    }
+}
@@ -0,0 +1,47 @@
+//! NASM/YASM directives don't use a `.` prefix and labels don't need to end
+//! with a colon, so the kind of a line can't be determined from just the
+//! first token as it is for GAS.
+
+use syntax::ast::SyntaxKind;
+
+const DIRECTIVES: [&str; 22] = [
+    "section", "segment", "global", "extern", "common", "static", "bits", "default", "cpu", "org",
+    "align", "alignb", "times", "struc", "endstruc", "istruc", "iend", "at", "absolute", "incbin",
+    "use16", "use32",
+];
+
+const DATA: [&str; 17] = [
+    "db", "dw", "dd", "dq", "dt", "do", "dy", "dz", "resb", "resw", "resd", "resq", "rest", "reso",
+    "resy", "resz", "times",
+];
+
+fn is_data(token: &str) -> bool {
+    DATA.iter().any(|d| d.eq_ignore_ascii_case(token))
+}
+
+pub(crate) fn is_directive(token: &str) -> bool {
+    token.starts_with('%')
+        || DIRECTIVES.iter().any(|d| d.eq_ignore_ascii_case(token))
+        || is_data(token)
+}
+
+/// Gets the kind of the line from the first two tokens, None is returned if
+/// the line should be treated in the same way as GAS.
+pub(crate) fn start_kind(first: &str, second: Option<&str>) -> Option<SyntaxKind> {
+    if is_directive(first) {
+        return Some(SyntaxKind::DIRECTIVE);
+    }
+
+    // Labels without a colon are only recognised when they're followed by
+    // data, NASM itself warns about a label alone on a line without a colon
+    // as it could be a misspelt instruction.
+    if !first.ends_with(':') && second.map(is_data).unwrap_or(false) {
+        return Some(if first.starts_with('.') {
+            SyntaxKind::LOCAL_LABEL
+        } else {
+            SyntaxKind::LABEL
+        });
+    }
+
+    None
+}
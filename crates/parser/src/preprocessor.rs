@@ -0,0 +1,107 @@
+//! The C preprocessor is run over `.S` files before they are assembled, the
+//! preprocessor lines are modelled so that `#include`, `#define` and `#if`
+//! can be understood.
+
+use std::mem;
+
+use rowan::{GreenNode, GreenToken, NodeOrToken};
+use syntax::ast::SyntaxKind;
+
+const DIRECTIVES: [&str; 13] = [
+    "#define", "#undef", "#include", "#if", "#ifdef", "#ifndef", "#elif", "#else", "#endif",
+    "#error", "#warning", "#pragma", "#line",
+];
+
+/// Determines if the line is a preprocessor directive rather than a comment,
+/// this matters for architectures where `#` also starts a comment.
+pub(crate) fn is_preprocessor_line(line: &str) -> bool {
+    let token = line
+        .trim_start_matches([' ', '\t'])
+        .split(|c: char| c.is_whitespace())
+        .next()
+        .unwrap_or_default();
+
+    DIRECTIVES.iter().any(|d| d.eq_ignore_ascii_case(token))
+}
+
+/// NASM has its own preprocessor with `%define` and `%assign` in place of
+/// `#define`, these are handled in the same way.
+pub(crate) fn is_define(mnemonic: &str) -> bool {
+    const DEFINES: [&str; 4] = ["#define", "%define", "%xdefine", "%assign"];
+
+    DEFINES.iter().any(|d| d.eq_ignore_ascii_case(mnemonic))
+}
+
+/// Converts an object like `#define NAME value` into the same layout as a
+/// `.equ` node, returns false for function like macros e.g. `#define F(x) x`
+/// which aren't constants.
+pub(crate) fn transform_define_node(items: &mut Vec<NodeOrToken<GreenNode, GreenToken>>) -> bool {
+    let Some(name_index) = items
+        .iter()
+        .position(|i| i.kind() == SyntaxKind::MNEMONIC.into())
+        .and_then(|mnemonic| {
+            items
+                .iter()
+                .enumerate()
+                .skip(mnemonic + 1)
+                .find(|(_, i)| i.kind() != SyntaxKind::WHITESPACE.into())
+                .map(|(index, _)| index)
+        })
+    else {
+        return false;
+    };
+
+    let function_like = items
+        .get(name_index + 1)
+        .map(|i| i.kind() == SyntaxKind::BRACKETS.into())
+        .unwrap_or(false);
+    let Some(name) = items[name_index].as_token().filter(|_| !function_like) else {
+        return false;
+    };
+
+    let mut token = NodeOrToken::Token(GreenToken::new(SyntaxKind::NAME.into(), name.text()));
+    mem::swap(&mut token, &mut items[name_index]);
+
+    let expr = items
+        .iter()
+        .enumerate()
+        .skip(name_index + 1)
+        .find(|(_, i)| i.kind() != SyntaxKind::WHITESPACE.into())
+        .map(|(index, _)| index);
+
+    if let Some(index) = expr {
+        // Any trailing comment isn't part of the value
+        let end = items
+            .iter()
+            .skip(index)
+            .position(|i| i.kind() == SyntaxKind::COMMENT.into())
+            .map(|p| p + index)
+            .unwrap_or(items.len());
+        let end = items[..end]
+            .iter()
+            .rposition(|i| i.kind() != SyntaxKind::WHITESPACE.into())
+            .map(|p| p + 1)
+            .unwrap_or(end);
+
+        let expr = items.drain(index..end).collect::<Vec<_>>();
+        let node = GreenNode::new(SyntaxKind::EXPR.into(), expr);
+        items.insert(index, NodeOrToken::Node(node));
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preprocessor_line() {
+        assert!(is_preprocessor_line("#define SIZE 16"));
+        assert!(is_preprocessor_line("  #include <asm/linkage.h>"));
+        assert!(is_preprocessor_line("#endif"));
+        assert!(!is_preprocessor_line("# %bb.0:"));
+        assert!(!is_preprocessor_line("#APP"));
+        assert!(!is_preprocessor_line("#defined in another file"));
+    }
+}
@@ -0,0 +1,107 @@
+//! Support for parsing only the lines of a file that have been edited, the
+//! new nodes can then be spliced into the existing tree rather than parsing
+//! the entire file again.
+
+use std::collections::HashSet;
+
+use rowan::{GreenNode, GreenNodeData, GreenTokenData, Language, NodeOrToken};
+use syntax::alias::Alias;
+use syntax::ast::{AssemblyLanguage, SyntaxKind};
+use syntax::utils::is_token_include;
+
+use crate::combinators;
+use crate::conditional::{is_conditional_else, is_conditional_end, is_conditional_start};
+use crate::config::ParserConfig;
+use crate::macros::{is_macro_end, is_macro_start};
+use crate::section::is_section_start;
+
+/// Parses `data` which is a run of complete lines from a file that has
+/// already been parsed, `alias` and `macros` are the definitions from the
+/// rest of the file. None is returned if the lines would change how the rest
+/// of the file is parsed, in which case the whole file has to be parsed again.
+pub fn parse_lines(
+    data: &str,
+    config: &ParserConfig,
+    alias: &Alias,
+    macros: &HashSet<String>,
+) -> Option<GreenNode> {
+    let root = combinators::parse_lines(data, config, alias, macros);
+
+    (!root.children().any(is_structural)).then_some(root)
+}
+
+/// Determines if `element` affects the structure of the nodes that follow it,
+/// or defines something that is used by the rest of the file e.g. labels,
+/// sections, macros and constants.
+pub fn is_structural(element: NodeOrToken<&GreenNodeData, &GreenTokenData>) -> bool {
+    match element {
+        NodeOrToken::Node(node) => match AssemblyLanguage::kind_from_raw(node.kind()) {
+            SyntaxKind::SECTION
+            | SyntaxKind::MACRO_DEF
+            | SyntaxKind::CONDITIONAL
+            | SyntaxKind::CONDITIONAL_BRANCH
+            | SyntaxKind::CONST_DEF
+            | SyntaxKind::ALIAS
+            | SyntaxKind::LABEL
+            | SyntaxKind::LOCAL_LABEL => true,
+            SyntaxKind::DIRECTIVE if is_structural_directive(node) => true,
+            _ => node.children().any(is_structural),
+        },
+        NodeOrToken::Token(token) => match AssemblyLanguage::kind_from_raw(token.kind()) {
+            SyntaxKind::LABEL | SyntaxKind::LOCAL_LABEL => true,
+            // A multi-line comment can hide any number of the following lines
+            SyntaxKind::COMMENT => token.text().starts_with("/*"),
+            _ => false,
+        },
+    }
+}
+
+fn is_structural_directive(node: &GreenNodeData) -> bool {
+    let Some(mnemonic) = node
+        .children()
+        .filter_map(|c| c.into_token())
+        .find(|t| t.kind() == SyntaxKind::MNEMONIC.into())
+    else {
+        return false;
+    };
+
+    let mnemonic = mnemonic.text();
+    is_section_start(mnemonic)
+        || is_macro_start(mnemonic)
+        || is_macro_end(mnemonic)
+        || is_conditional_start(mnemonic)
+        || is_conditional_else(mnemonic)
+        || is_conditional_end(mnemonic)
+        || is_token_include(mnemonic)
+        || mnemonic.eq_ignore_ascii_case(".unreq")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(data: &str) -> Option<GreenNode> {
+        parse_lines(data, &Default::default(), &Alias::new(), &HashSet::new())
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert!(parse("\n\tmov x0, x1\n\tadd x0, x0, #1 // increment\n").is_some());
+        assert!(parse("\n\t.byte 1, 2\n").is_some());
+    }
+
+    #[test]
+    fn test_parse_lines_structural() {
+        assert!(parse("\nentry:\n").is_none());
+        assert!(parse("\n.Lloop:\n").is_none());
+        assert!(parse("\n\t.section .data\n").is_none());
+        assert!(parse("\n\t.equ SIZE, 16\n").is_none());
+        assert!(parse("\n\t.macro inc reg\n").is_none());
+        assert!(parse("\n\t.endm\n").is_none());
+        assert!(parse("\n\t.if SIZE\n").is_none());
+        assert!(parse("\n\t.include \"file.s\"\n").is_none());
+        assert!(parse("\n\t/* comment\n").is_none());
+        assert!(parse("\n\t.unreq counter\n").is_none());
+        assert!(parse("\nSIZE = 16\n").is_none());
+    }
+}
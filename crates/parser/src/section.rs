@@ -0,0 +1,29 @@
+//! Everything following a section directive belongs to that section until the
+//! next section directive, this includes any labels. A section from
+//! `.pushsection` is within the section that it was pushed from, which
+//! carries on after the matching `.popsection`.
+
+pub(crate) fn is_section_start(mnemonic: &str) -> bool {
+    // `section` and `segment` are the NASM equivalents of `.section`
+    const SECTIONS: [&str; 9] = [
+        ".section",
+        ".text",
+        ".data",
+        ".bss",
+        ".rodata",
+        ".pushsection",
+        ".popsection",
+        "section",
+        "segment",
+    ];
+
+    SECTIONS.iter().any(|s| s.eq_ignore_ascii_case(mnemonic))
+}
+
+pub(crate) fn is_section_push(mnemonic: &str) -> bool {
+    mnemonic.eq_ignore_ascii_case(".pushsection")
+}
+
+pub(crate) fn is_section_pop(mnemonic: &str) -> bool {
+    mnemonic.eq_ignore_ascii_case(".popsection")
+}
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use crate::ast::{SyntaxKind, SyntaxNode};
 use base::register::{RegisterKind, RegisterSize, Registers};
 use itertools::Itertools;
-use rowan::GreenNode;
+use rowan::{GreenNode, TextRange, TextSize};
 
 #[derive(Debug, Clone, PartialEq)]
 enum Kind {
@@ -11,9 +11,20 @@ enum Kind {
     Constant(String),
 }
 
+/// A definition of a name that is in effect from `offset` until the next
+/// binding of the same name, `kind` is None once `.unreq` has removed it.
+#[derive(Debug, Clone, PartialEq)]
+struct Binding {
+    offset: TextSize,
+    kind: Option<Kind>,
+}
+
+/// The register aliases and constants of a file, as names can be defined
+/// again with `.set` or removed with `.unreq` each name has a list of bindings
+/// in the order that they appear in the file.
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Alias {
-    alias_map: HashMap<String, Kind>,
+    alias_map: HashMap<String, Vec<Binding>>,
 }
 
 impl Alias {
@@ -23,13 +34,42 @@ impl Alias {
         }
     }
 
-    pub fn merge(&mut self, other: &Alias) {
+    /// Adds the names that are defined in `other` as if they were all
+    /// defined at `offset` e.g. the location of an include.
+    pub fn merge(&mut self, other: &Alias, offset: TextSize) {
         for (k, v) in &other.alias_map {
-            self.alias_map.insert(k.clone(), v.clone());
+            if let Some(binding) = v.last() {
+                self.bind(k.clone(), offset, binding.kind.clone());
+            }
+        }
+    }
+
+    /// Gets the names that are in effect at `offset`, this is used when only
+    /// part of a file is being parsed.
+    pub fn at(&self, offset: TextSize) -> Alias {
+        let alias_map = self
+            .alias_map
+            .iter()
+            .filter_map(|(k, v)| {
+                let binding = v.iter().rev().find(|b| b.offset <= offset)?;
+                Some((k.clone(), vec![binding.clone()]))
+            })
+            .collect();
+
+        Alias { alias_map }
+    }
+
+    /// Moves the bindings after `range` to account for it being replaced by
+    /// text of `length`.
+    pub fn edit(&mut self, range: TextRange, length: TextSize) {
+        for binding in self.alias_map.values_mut().flatten() {
+            if binding.offset >= range.end() {
+                binding.offset = binding.offset - range.len() + length;
+            }
         }
     }
 
-    pub fn add_alias(&mut self, node: &GreenNode) {
+    pub fn add_alias(&mut self, node: &GreenNode, offset: TextSize) {
         let node = SyntaxNode::new_root(node.clone());
 
         let name = node
@@ -43,11 +83,11 @@ impl Alias {
             .and_then(|t| t.as_token().map(|t| t.to_string()));
 
         if let (Some(name), Some(register)) = (name, register) {
-            self.alias_map.insert(name, Kind::Register(register));
+            self.bind(name, offset, Some(Kind::Register(register)));
         }
     }
 
-    pub fn add_equ(&mut self, node: &GreenNode) {
+    pub fn add_equ(&mut self, node: &GreenNode, offset: TextSize) {
         let node = SyntaxNode::new_root(node.clone());
 
         let name = node
@@ -67,28 +107,130 @@ impl Alias {
             .unwrap_or_default();
 
         if let Some(name) = name {
-            self.alias_map.insert(name, Kind::Constant(expr));
+            self.bind(name, offset, Some(Kind::Constant(expr)));
+        }
+    }
+
+    /// Removes the register alias given to `.unreq`
+    pub fn remove_alias(&mut self, node: &GreenNode, offset: TextSize) {
+        let node = SyntaxNode::new_root(node.clone());
+
+        let names = node
+            .descendants_with_tokens()
+            .filter_map(|d| d.into_token())
+            .filter(|t| matches!(t.kind(), SyntaxKind::REGISTER_ALIAS | SyntaxKind::TOKEN))
+            .map(|t| t.to_string())
+            .collect_vec();
+
+        for name in names {
+            if self.get_register_for_alias(&name, offset).is_some() {
+                self.bind(name, offset, None);
+            }
         }
     }
 
+    fn bind(&mut self, name: String, offset: TextSize, kind: Option<Kind>) {
+        let bindings = self.alias_map.entry(name).or_default();
+        let index = bindings.partition_point(|b| b.offset <= offset);
+        bindings.insert(index, Binding { offset, kind });
+    }
+
+    /// Gets the kind of `token` as of the last definition that has been
+    /// added, this is used while the file is being parsed in order.
     pub fn get_kind(&self, token: &str) -> Option<SyntaxKind> {
-        self.alias_map.get(token).map(|k| match k {
-            Kind::Register(_) => SyntaxKind::REGISTER_ALIAS,
-            Kind::Constant(_) => SyntaxKind::CONSTANT,
-        })
+        self.alias_map
+            .get(token)
+            .and_then(|v| v.last())
+            .and_then(|b| b.kind.as_ref())
+            .map(|k| match k {
+                Kind::Register(_) => SyntaxKind::REGISTER_ALIAS,
+                Kind::Constant(_) => SyntaxKind::CONSTANT,
+            })
     }
 
-    pub fn get_register_for_alias(&self, name: &str) -> Option<&String> {
-        self.alias_map.get(name).and_then(|val| match val {
+    /// Gets the register that `name` refers to at `offset`
+    pub fn get_register_for_alias(&self, name: &str, offset: TextSize) -> Option<&String> {
+        let binding = self
+            .alias_map
+            .get(name)?
+            .iter()
+            .rev()
+            .find(|b| b.offset <= offset)?;
+
+        match binding.kind.as_ref()? {
             Kind::Register(r) => Some(r),
             Kind::Constant(_) => None,
-        })
+        }
     }
 
-    pub fn get_constant_for_token(&self, name: &str) -> Option<&String> {
-        self.alias_map.get(name).and_then(|val| match val {
+    /// Gets the expression that `name` is defined as at `offset`, constants
+    /// can be used before they are defined so the first definition is used
+    /// for anything before it.
+    pub fn get_constant_for_token(&self, name: &str, offset: TextSize) -> Option<&String> {
+        let bindings = self.alias_map.get(name)?;
+        let binding = bindings
+            .iter()
+            .rev()
+            .find(|b| b.offset <= offset)
+            .or_else(|| bindings.first())?;
+
+        match binding.kind.as_ref()? {
             Kind::Register(_) => None,
             Kind::Constant(expr) => Some(expr),
+        }
+    }
+
+    /// Gets the offset of the definition of `name` that is in effect at
+    /// `offset`
+    pub fn definition_offset(&self, name: &str, offset: TextSize) -> Option<TextSize> {
+        let bindings = self.alias_map.get(name)?;
+        bindings
+            .iter()
+            .rev()
+            .find(|b| b.offset <= offset)
+            .or_else(|| bindings.first())
+            .filter(|b| b.kind.is_some())
+            .map(|b| b.offset)
+    }
+
+    /// Evaluates `expr` as if it were written at `offset` resolving any
+    /// constants that it refers to, `lookup` is used to get the value of any
+    /// other symbols.
+    pub fn evaluate(
+        &self,
+        expr: &str,
+        offset: TextSize,
+        lookup: &dyn Fn(&str) -> Option<i128>,
+    ) -> Option<i128> {
+        self.evaluate_depth(expr, offset, lookup, 0)
+    }
+
+    fn evaluate_depth(
+        &self,
+        expr: &str,
+        offset: TextSize,
+        lookup: &dyn Fn(&str) -> Option<i128>,
+        depth: usize,
+    ) -> Option<i128> {
+        // A constant that refers to itself would otherwise recurse forever
+        const MAX_DEPTH: usize = 32;
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        crate::expr::evaluate(expr, &|name| {
+            match self.get_constant_for_token(name, offset) {
+                // A redefinition e.g. `.set SIZE, SIZE + 4` refers to the
+                // previous value
+                Some(value) => {
+                    let offset = self
+                        .definition_offset(name, offset)
+                        .and_then(|o| o.checked_sub(TextSize::from(1)))
+                        .unwrap_or(offset);
+                    self.evaluate_depth(value, offset, lookup, depth + 1)
+                }
+                None => lookup(name),
+            }
         })
     }
 
@@ -98,11 +240,13 @@ impl Alias {
         size: RegisterSize,
         registers: &'a (dyn Registers + 'a),
     ) -> impl Iterator<Item = &String> + 'a {
-        self.alias_map.iter().filter_map(move |(k, v)| match v {
-            Kind::Register(v) => {
-                (kind.contains(registers.get_kind(v)) && registers.get_size(v) == size).then_some(k)
-            }
-            _ => None,
-        })
+        self.alias_map
+            .iter()
+            .filter_map(move |(k, v)| match v.last()?.kind.as_ref()? {
+                Kind::Register(v) => (kind.contains(registers.get_kind(v))
+                    && registers.get_size(v) == size)
+                    .then_some(k),
+                _ => None,
+            })
     }
 }
@@ -1,6 +1,10 @@
 use std::fmt::Debug;
 use std::iter;
 
+mod nodes;
+
+pub use nodes::*;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(non_camel_case_types)]
 #[allow(clippy::upper_case_acronyms)]
@@ -33,15 +37,39 @@ pub enum SyntaxKind {
     LOCAL_LABEL,
     COMMENT,
     IMMEDIATE,
+    KEYWORD,
 
     INSTRUCTION,
     DIRECTIVE,
     BRACKETS,
+    /// An address e.g. `[x0, #8]!` or `8(%rax,%rbx,4)`, this contains the
+    /// BRACKETS along with any displacement or writeback outside of them
+    MEMORY_OPERAND,
+    /// The parts of the address within a MEMORY_OPERAND e.g. `x0`, `x1` and
+    /// `lsl #3` in `[x0, x1, lsl #3]` or `8` in `8(%rax)`
+    BASE,
+    INDEX,
+    SCALE,
+    DISPLACEMENT,
+    /// The `!` of a pre-index address or the offset of a post-index address
+    /// e.g. `#16` in `[x0], #16`
+    WRITEBACK,
     ALIAS,
     CONST_DEF,
+    MACRO_DEF,
+    MACRO_CALL,
+    CONDITIONAL,
+    CONDITIONAL_BRANCH,
+    SECTION,
+    /// Text that couldn't be parsed e.g. an unterminated string
+    ERROR,
 
     METADATA,
     OBJDUMP_OFFSET,
+    /// A line of source interleaved with the disassembly by `objdump -S`
+    SOURCE_LINE,
+    /// A `file:line` marker from `objdump -l` e.g. `/src/main.c:3`
+    SOURCE_LOCATION,
     // ROOT should be the last element
     ROOT,
 }
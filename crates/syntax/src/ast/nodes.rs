@@ -0,0 +1,696 @@
+//! Typed wrappers over the nodes of the syntax tree, these give names to the
+//! parts of a statement so that they don't need to be found by position.
+
+use itertools::Itertools;
+use rowan::TextRange;
+
+use super::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+
+pub trait AstNode {
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized;
+
+    fn cast(node: SyntaxNode) -> Option<Self>
+    where
+        Self: Sized;
+
+    fn syntax(&self) -> &SyntaxNode;
+}
+
+macro_rules! ast_node {
+    ($(#[$meta:meta])* $name:ident, $($kind:ident)|+) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $name {
+            syntax: SyntaxNode,
+        }
+
+        impl AstNode for $name {
+            fn can_cast(kind: SyntaxKind) -> bool {
+                matches!(kind, $(SyntaxKind::$kind)|+)
+            }
+
+            fn cast(node: SyntaxNode) -> Option<Self> {
+                Self::can_cast(node.kind()).then_some(Self { syntax: node })
+            }
+
+            fn syntax(&self) -> &SyntaxNode {
+                &self.syntax
+            }
+        }
+    };
+}
+
+ast_node!(
+    /// An instruction, calls to a macro are included as they are written in
+    /// the same way e.g. `add x0, x1, #1`
+    Instruction,
+    INSTRUCTION | MACRO_CALL
+);
+ast_node!(
+    /// A directive other than one that defines a constant or register alias
+    /// e.g. `.loc 1 10 0`
+    Directive,
+    DIRECTIVE
+);
+ast_node!(
+    /// A label or local label e.g. `entry:`
+    Label,
+    LABEL | LOCAL_LABEL
+);
+ast_node!(
+    /// The definition of a constant e.g. `.equ SIZE, 16` or `SIZE EQU 16`
+    ConstDef,
+    CONST_DEF
+);
+ast_node!(
+    /// A name given to a register e.g. `counter .req x0`
+    RegisterAlias,
+    ALIAS
+);
+ast_node!(
+    /// A memory access e.g. `[x0, x1, lsl #3]!`, `8(%rax,%rbx,4)` or
+    /// `[rax+rbx*4+8]`
+    MemoryOperand,
+    MEMORY_OPERAND
+);
+
+impl Instruction {
+    pub fn mnemonic(&self) -> Option<SyntaxToken> {
+        child_token(&self.syntax, SyntaxKind::MNEMONIC)
+    }
+
+    /// The comma separated operands of the instruction
+    pub fn operands(&self) -> Vec<Operand> {
+        operands(&self.syntax)
+    }
+
+    pub fn operand(&self, index: usize) -> Option<Operand> {
+        self.operands().into_iter().nth(index)
+    }
+}
+
+impl Directive {
+    pub fn mnemonic(&self) -> Option<SyntaxToken> {
+        child_token(&self.syntax, SyntaxKind::MNEMONIC)
+    }
+
+    /// The name of the directive in lower case e.g. `.loc`
+    pub fn name(&self) -> Option<String> {
+        self.mnemonic().map(|m| m.text().to_lowercase())
+    }
+
+    /// The comma separated operands of the directive
+    pub fn operands(&self) -> Vec<Operand> {
+        operands(&self.syntax)
+    }
+
+    /// Each of the tokens and brackets that follow the mnemonic, this is for
+    /// directives such as `.loc` that separate their arguments with spaces.
+    pub fn arguments(&self) -> impl Iterator<Item = SyntaxElement> {
+        after_mnemonic(&self.syntax).filter(|e| !is_separator(e.kind()))
+    }
+}
+
+impl Label {
+    pub fn token(&self) -> Option<SyntaxToken> {
+        self.syntax
+            .children_with_tokens()
+            .filter_map(|c| c.into_token())
+            .find(|t| matches!(t.kind(), SyntaxKind::LABEL | SyntaxKind::LOCAL_LABEL))
+    }
+
+    /// The name of the label without the trailing `:`
+    pub fn name(&self) -> Option<String> {
+        self.token()
+            .map(|t| t.text().trim_end_matches(':').to_string())
+    }
+
+    pub fn is_local(&self) -> bool {
+        self.syntax.kind() == SyntaxKind::LOCAL_LABEL
+    }
+}
+
+impl ConstDef {
+    pub fn name(&self) -> Option<SyntaxToken> {
+        child_token(&self.syntax, SyntaxKind::NAME)
+    }
+
+    /// The directive used to define the constant, this isn't present for a
+    /// C preprocessor `#define`
+    pub fn mnemonic(&self) -> Option<SyntaxToken> {
+        child_token(&self.syntax, SyntaxKind::MNEMONIC)
+    }
+
+    pub fn expr(&self) -> Option<SyntaxNode> {
+        self.syntax
+            .children()
+            .find(|c| c.kind() == SyntaxKind::EXPR)
+    }
+}
+
+impl RegisterAlias {
+    /// The name that is given to the register
+    pub fn alias(&self) -> Option<SyntaxToken> {
+        child_token(&self.syntax, SyntaxKind::REGISTER_ALIAS)
+    }
+
+    pub fn mnemonic(&self) -> Option<SyntaxToken> {
+        child_token(&self.syntax, SyntaxKind::MNEMONIC)
+    }
+
+    pub fn register(&self) -> Option<SyntaxToken> {
+        child_token(&self.syntax, SyntaxKind::REGISTER)
+    }
+}
+
+impl MemoryOperand {
+    /// The brackets that hold the registers of the address
+    pub fn brackets(&self) -> Option<SyntaxNode> {
+        self.syntax
+            .children()
+            .find(|c| c.kind() == SyntaxKind::BRACKETS)
+    }
+
+    /// The register that the address is based on
+    pub fn base(&self) -> Option<SyntaxToken> {
+        self.register(SyntaxKind::BASE)
+    }
+
+    pub fn index(&self) -> Option<SyntaxToken> {
+        self.register(SyntaxKind::INDEX)
+    }
+
+    /// The amount the index is multiplied by e.g. `4` in `(%rax,%rbx,4)` or
+    /// `[rax+rbx*4]`
+    pub fn scale(&self) -> Option<String> {
+        self.part(SyntaxKind::SCALE)
+            .filter(|_| !self.is_arm_style())
+            .map(|s| s.to_string())
+    }
+
+    /// The shift or extend applied to the index e.g. `lsl #3` in
+    /// `[x0, x1, lsl #3]`
+    pub fn extend(&self) -> Option<String> {
+        self.part(SyntaxKind::SCALE)
+            .filter(|_| self.is_arm_style())
+            .map(|s| s.to_string())
+    }
+
+    /// The constant part of the address e.g. `8` in `8(%rax)`, `#8` in
+    /// `[x0, #8]` or `- 8` in `[rbp - 8]`
+    pub fn displacement(&self) -> Option<String> {
+        self.syntax
+            .descendants()
+            .filter(|d| d.kind() == SyntaxKind::DISPLACEMENT)
+            .map(|d| d.to_string())
+            .reduce(|a, b| {
+                if b.starts_with('-') {
+                    format!("{a} {b}")
+                } else {
+                    format!("{a} + {b}")
+                }
+            })
+    }
+
+    /// The base register is updated before the access e.g. `[x0, #16]!`
+    pub fn is_pre_index(&self) -> bool {
+        self.syntax
+            .children()
+            .any(|c| c.kind() == SyntaxKind::WRITEBACK)
+    }
+
+    /// The offset that is added to the base register after the access e.g.
+    /// `#16` in `ldr x1, [x0], #16` or `x1` in `ld1 {v0.16b}, [x0], x1`
+    pub fn post_index(&self) -> Option<Operand> {
+        let mut siblings = std::iter::successors(self.syntax.next_sibling_or_token(), |s| {
+            s.next_sibling_or_token()
+        })
+        .filter(|s| s.kind() != SyntaxKind::WHITESPACE);
+
+        siblings.next().filter(|s| s.kind() == SyntaxKind::COMMA)?;
+        let writeback = siblings
+            .next()?
+            .into_node()
+            .filter(|n| n.kind() == SyntaxKind::WRITEBACK)?;
+
+        Some(Operand {
+            elements: vec![SyntaxElement::Node(writeback)],
+        })
+    }
+
+    /// The base register is written back to with the address
+    pub fn is_writeback(&self) -> bool {
+        self.is_pre_index() || self.post_index().is_some()
+    }
+
+    fn part(&self, kind: SyntaxKind) -> Option<SyntaxNode> {
+        self.syntax.descendants().find(|d| d.kind() == kind)
+    }
+
+    fn register(&self, kind: SyntaxKind) -> Option<SyntaxToken> {
+        self.part(kind)?
+            .descendants_with_tokens()
+            .filter_map(|d| d.into_token())
+            .find(|t| is_register(t.kind()))
+    }
+
+    /// Arm separates the parts of the address with commas within square
+    /// brackets, so the scale is a shift or extend e.g. `[x0, x1, lsl #3]`
+    fn is_arm_style(&self) -> bool {
+        self.brackets().is_some_and(|b| {
+            b.first_token()
+                .is_some_and(|t| t.kind() == SyntaxKind::L_SQ)
+                && b.children_with_tokens()
+                    .any(|c| c.kind() == SyntaxKind::COMMA)
+        })
+    }
+}
+
+/// An operand of an instruction or directive, this is a run of elements
+/// between commas rather than a node within the tree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Operand {
+    elements: Vec<SyntaxElement>,
+}
+
+impl Operand {
+    pub fn elements(&self) -> &[SyntaxElement] {
+        &self.elements
+    }
+
+    pub fn text(&self) -> String {
+        self.elements.iter().map(|e| e.to_string()).join("")
+    }
+
+    pub fn text_range(&self) -> TextRange {
+        let start = self.elements.first().map(|e| e.text_range());
+        let end = self.elements.last().map(|e| e.text_range());
+
+        match (start, end) {
+            (Some(start), Some(end)) => start.cover(end),
+            _ => TextRange::default(),
+        }
+    }
+
+    /// The registers, including register aliases, that are used by the operand
+    pub fn registers(&self) -> impl Iterator<Item = SyntaxToken> + '_ {
+        self.elements.iter().flat_map(|e| match e {
+            SyntaxElement::Node(node) => registers(node).collect_vec(),
+            SyntaxElement::Token(token) if is_register(token.kind()) => vec![token.clone()],
+            SyntaxElement::Token(_) => Vec::new(),
+        })
+    }
+
+    /// The memory access if this operand is one
+    pub fn memory(&self) -> Option<MemoryOperand> {
+        self.elements
+            .iter()
+            .filter_map(|e| e.as_node())
+            .find_map(|n| MemoryOperand::cast(n.clone()))
+    }
+}
+
+fn child_token(node: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken> {
+    node.children_with_tokens()
+        .filter_map(|c| c.into_token())
+        .find(|t| t.kind() == kind)
+}
+
+fn after_mnemonic(node: &SyntaxNode) -> impl Iterator<Item = SyntaxElement> {
+    node.children_with_tokens()
+        .skip_while(|c| c.kind() != SyntaxKind::MNEMONIC)
+        .skip(1)
+}
+
+fn operands(node: &SyntaxNode) -> Vec<Operand> {
+    split_operands(after_mnemonic(node))
+}
+
+fn split_operands(elements: impl Iterator<Item = SyntaxElement>) -> Vec<Operand> {
+    let mut operands = vec![];
+    let mut current = vec![];
+
+    for element in elements {
+        match element.kind() {
+            SyntaxKind::COMMA => operands.push(std::mem::take(&mut current)),
+            SyntaxKind::COMMENT => {}
+            // The relocations from `objdump -r` that follow an instruction
+            SyntaxKind::RELOCATION if element.as_node().is_some() => {}
+            _ => current.push(element),
+        }
+    }
+    operands.push(current);
+
+    operands
+        .into_iter()
+        .map(|elements| {
+            let start = elements.iter().position(|e| !is_separator(e.kind()));
+            let end = elements.iter().rposition(|e| !is_separator(e.kind()));
+            match (start, end) {
+                (Some(start), Some(end)) => elements[start..=end].to_vec(),
+                _ => Vec::new(),
+            }
+        })
+        .filter(|elements| !elements.is_empty())
+        .map(|elements| Operand { elements })
+        .collect()
+}
+
+fn registers(node: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|d| d.into_token())
+        .filter(|t| is_register(t.kind()))
+}
+
+fn is_register(kind: SyntaxKind) -> bool {
+    matches!(kind, SyntaxKind::REGISTER | SyntaxKind::REGISTER_ALIAS)
+}
+
+fn is_separator(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::WHITESPACE | SyntaxKind::COMMA | SyntaxKind::COMMENT
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rowan::GreenNodeBuilder;
+
+    use super::*;
+
+    enum Element {
+        Node(SyntaxKind, Vec<Element>),
+        Token(SyntaxKind, &'static str),
+    }
+    use Element::{Node, Token};
+
+    fn build(children: Vec<Element>) -> SyntaxNode {
+        fn add(builder: &mut GreenNodeBuilder, element: Element) {
+            match element {
+                Node(kind, children) => {
+                    builder.start_node(kind.into());
+                    children.into_iter().for_each(|c| add(builder, c));
+                    builder.finish_node();
+                }
+                Token(kind, text) => builder.token(kind.into(), text),
+            }
+        }
+
+        let mut builder = GreenNodeBuilder::new();
+        add(&mut builder, Node(SyntaxKind::ROOT, children));
+        SyntaxNode::new_root(builder.finish())
+    }
+
+    fn ws() -> Element {
+        Token(SyntaxKind::WHITESPACE, " ")
+    }
+
+    fn comma() -> Element {
+        Token(SyntaxKind::COMMA, ",")
+    }
+
+    #[test]
+    fn test_instruction() {
+        // ldr x0, [x1, #8] // load
+        let root = build(vec![Node(
+            SyntaxKind::INSTRUCTION,
+            vec![
+                Token(SyntaxKind::MNEMONIC, "ldr"),
+                ws(),
+                Token(SyntaxKind::REGISTER, "x0"),
+                comma(),
+                ws(),
+                Node(
+                    SyntaxKind::MEMORY_OPERAND,
+                    vec![Node(
+                        SyntaxKind::BRACKETS,
+                        vec![
+                            Token(SyntaxKind::L_SQ, "["),
+                            Node(SyntaxKind::BASE, vec![Token(SyntaxKind::REGISTER, "x1")]),
+                            comma(),
+                            ws(),
+                            Node(
+                                SyntaxKind::DISPLACEMENT,
+                                vec![
+                                    Token(SyntaxKind::IMMEDIATE, "#"),
+                                    Token(SyntaxKind::NUMBER, "8"),
+                                ],
+                            ),
+                            Token(SyntaxKind::R_SQ, "]"),
+                        ],
+                    )],
+                ),
+                ws(),
+                Token(SyntaxKind::COMMENT, "// load"),
+            ],
+        )]);
+
+        let instruction = root.children().find_map(Instruction::cast).unwrap();
+        assert_eq!(instruction.mnemonic().unwrap().text(), "ldr");
+
+        let operands = instruction.operands();
+        assert_eq!(
+            operands.iter().map(|o| o.text()).collect_vec(),
+            vec!["x0", "[x1, #8]"]
+        );
+        assert_eq!(operands[0].registers().next().unwrap().text(), "x0");
+        assert!(operands[0].memory().is_none());
+
+        let memory = operands[1].memory().unwrap();
+        assert_eq!(memory.base().unwrap().text(), "x1");
+        assert!(memory.index().is_none());
+        assert_eq!(memory.displacement().as_deref(), Some("#8"));
+        assert!(!memory.is_writeback());
+    }
+
+    #[test]
+    fn test_memory_writeback() {
+        // ldr x2, [x0, x1, lsl #3]!
+        // ldr x2, [x0], #16
+        let brackets = |children| {
+            Node(
+                SyntaxKind::BRACKETS,
+                [
+                    vec![Token(SyntaxKind::L_SQ, "[")],
+                    children,
+                    vec![Token(SyntaxKind::R_SQ, "]")],
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            )
+        };
+        let root = build(vec![
+            Node(
+                SyntaxKind::INSTRUCTION,
+                vec![
+                    Token(SyntaxKind::MNEMONIC, "ldr"),
+                    ws(),
+                    Token(SyntaxKind::REGISTER, "x2"),
+                    comma(),
+                    ws(),
+                    Node(
+                        SyntaxKind::MEMORY_OPERAND,
+                        vec![
+                            brackets(vec![
+                                Node(SyntaxKind::BASE, vec![Token(SyntaxKind::REGISTER, "x0")]),
+                                comma(),
+                                ws(),
+                                Node(SyntaxKind::INDEX, vec![Token(SyntaxKind::REGISTER, "x1")]),
+                                comma(),
+                                ws(),
+                                Node(
+                                    SyntaxKind::SCALE,
+                                    vec![
+                                        Token(SyntaxKind::TOKEN, "lsl"),
+                                        ws(),
+                                        Token(SyntaxKind::IMMEDIATE, "#"),
+                                        Token(SyntaxKind::NUMBER, "3"),
+                                    ],
+                                ),
+                            ]),
+                            Node(SyntaxKind::WRITEBACK, vec![Token(SyntaxKind::TOKEN, "!")]),
+                        ],
+                    ),
+                ],
+            ),
+            Node(
+                SyntaxKind::INSTRUCTION,
+                vec![
+                    Token(SyntaxKind::MNEMONIC, "ldr"),
+                    ws(),
+                    Token(SyntaxKind::REGISTER, "x2"),
+                    comma(),
+                    ws(),
+                    Node(
+                        SyntaxKind::MEMORY_OPERAND,
+                        vec![brackets(vec![Node(
+                            SyntaxKind::BASE,
+                            vec![Token(SyntaxKind::REGISTER, "x0")],
+                        )])],
+                    ),
+                    comma(),
+                    ws(),
+                    Node(
+                        SyntaxKind::WRITEBACK,
+                        vec![
+                            Token(SyntaxKind::IMMEDIATE, "#"),
+                            Token(SyntaxKind::NUMBER, "16"),
+                        ],
+                    ),
+                ],
+            ),
+        ]);
+
+        let memory = root
+            .descendants()
+            .filter_map(MemoryOperand::cast)
+            .collect_vec();
+
+        assert_eq!(memory[0].base().unwrap().text(), "x0");
+        assert_eq!(memory[0].index().unwrap().text(), "x1");
+        assert_eq!(memory[0].extend().as_deref(), Some("lsl #3"));
+        assert!(memory[0].is_pre_index());
+        assert!(memory[0].post_index().is_none());
+
+        assert_eq!(memory[1].base().unwrap().text(), "x0");
+        assert!(memory[1].index().is_none());
+        assert!(!memory[1].is_pre_index());
+        assert_eq!(memory[1].post_index().unwrap().text(), "#16");
+        assert!(memory[1].is_writeback());
+    }
+
+    #[test]
+    fn test_memory_x86() {
+        // 8(%rax,%rbx,4)
+        // [rax+rbx*4+8]
+        let root = build(vec![
+            Node(
+                SyntaxKind::MEMORY_OPERAND,
+                vec![
+                    Node(
+                        SyntaxKind::DISPLACEMENT,
+                        vec![Token(SyntaxKind::NUMBER, "8")],
+                    ),
+                    Node(
+                        SyntaxKind::BRACKETS,
+                        vec![
+                            Token(SyntaxKind::L_PAREN, "("),
+                            Node(SyntaxKind::BASE, vec![Token(SyntaxKind::REGISTER, "%rax")]),
+                            comma(),
+                            Node(SyntaxKind::INDEX, vec![Token(SyntaxKind::REGISTER, "%rbx")]),
+                            comma(),
+                            Node(SyntaxKind::SCALE, vec![Token(SyntaxKind::NUMBER, "4")]),
+                            Token(SyntaxKind::R_PAREN, ")"),
+                        ],
+                    ),
+                ],
+            ),
+            Node(
+                SyntaxKind::MEMORY_OPERAND,
+                vec![Node(
+                    SyntaxKind::BRACKETS,
+                    vec![
+                        Token(SyntaxKind::L_SQ, "["),
+                        Node(SyntaxKind::BASE, vec![Token(SyntaxKind::REGISTER, "rax")]),
+                        Token(SyntaxKind::OPERATOR, "+"),
+                        Node(SyntaxKind::INDEX, vec![Token(SyntaxKind::REGISTER, "rbx")]),
+                        Token(SyntaxKind::OPERATOR, "*"),
+                        Node(SyntaxKind::SCALE, vec![Token(SyntaxKind::NUMBER, "4")]),
+                        Token(SyntaxKind::OPERATOR, "+"),
+                        Node(
+                            SyntaxKind::DISPLACEMENT,
+                            vec![Token(SyntaxKind::NUMBER, "8")],
+                        ),
+                        Token(SyntaxKind::R_SQ, "]"),
+                    ],
+                )],
+            ),
+        ]);
+
+        for memory in root.children().filter_map(MemoryOperand::cast) {
+            assert_eq!(memory.base().unwrap().text().trim_start_matches('%'), "rax");
+            assert_eq!(
+                memory.index().unwrap().text().trim_start_matches('%'),
+                "rbx"
+            );
+            assert_eq!(memory.scale().as_deref(), Some("4"));
+            assert_eq!(memory.displacement().as_deref(), Some("8"));
+            assert!(memory.extend().is_none());
+            assert!(!memory.is_writeback());
+        }
+    }
+
+    #[test]
+    fn test_directive_arguments() {
+        // .loc 1 10 0
+        let root = build(vec![Node(
+            SyntaxKind::DIRECTIVE,
+            vec![
+                Token(SyntaxKind::MNEMONIC, ".LOC"),
+                ws(),
+                Token(SyntaxKind::NUMBER, "1"),
+                ws(),
+                Token(SyntaxKind::NUMBER, "10"),
+                ws(),
+                Token(SyntaxKind::NUMBER, "0"),
+            ],
+        )]);
+
+        let directive = root.children().find_map(Directive::cast).unwrap();
+        assert_eq!(directive.name().as_deref(), Some(".loc"));
+        assert_eq!(
+            directive.arguments().map(|a| a.to_string()).collect_vec(),
+            vec!["1", "10", "0"]
+        );
+        assert_eq!(directive.operands().len(), 1);
+    }
+
+    #[test]
+    fn test_definitions() {
+        // SIZE .equ 16
+        // counter .req x0
+        // entry:
+        let root = build(vec![
+            Node(
+                SyntaxKind::CONST_DEF,
+                vec![
+                    Token(SyntaxKind::NAME, "SIZE"),
+                    ws(),
+                    Token(SyntaxKind::MNEMONIC, ".equ"),
+                    Node(
+                        SyntaxKind::EXPR,
+                        vec![ws(), Token(SyntaxKind::NUMBER, "16")],
+                    ),
+                ],
+            ),
+            Node(
+                SyntaxKind::ALIAS,
+                vec![
+                    Token(SyntaxKind::REGISTER_ALIAS, "counter"),
+                    ws(),
+                    Token(SyntaxKind::MNEMONIC, ".req"),
+                    ws(),
+                    Token(SyntaxKind::REGISTER, "x0"),
+                ],
+            ),
+            Node(SyntaxKind::LABEL, vec![Token(SyntaxKind::LABEL, "entry:")]),
+        ]);
+
+        let constant = root.children().find_map(ConstDef::cast).unwrap();
+        assert_eq!(constant.name().unwrap().text(), "SIZE");
+        assert_eq!(constant.mnemonic().unwrap().text(), ".equ");
+        assert_eq!(constant.expr().unwrap().to_string(), " 16");
+
+        let alias = root.children().find_map(RegisterAlias::cast).unwrap();
+        assert_eq!(alias.alias().unwrap().text(), "counter");
+        assert_eq!(alias.register().unwrap().text(), "x0");
+
+        let label = root.children().find_map(Label::cast).unwrap();
+        assert_eq!(label.name().as_deref(), Some("entry"));
+        assert!(!label.is_local());
+    }
+}
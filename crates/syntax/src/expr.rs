@@ -0,0 +1,251 @@
+//! Evaluation of GAS style constant expressions e.g. `(SIZE + 4) << 2`
+
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Number(i128),
+    Symbol(&'a str),
+    Operator(&'a str),
+    LParen,
+    RParen,
+}
+
+/// The binary operators grouped by precedence, lowest first. GAS only has a
+/// few levels of precedence so this doesn't match C.
+const PRECEDENCE: [&[&str]; 4] = [
+    &["&&", "||"],
+    &["+", "-", "==", "<>", "!=", "<", ">", "<=", ">="],
+    &["|", "&", "^", "!"],
+    &["*", "/", "%", "<<", ">>"],
+];
+
+/// Evaluates a constant expression, `lookup` is used to get the value of any
+/// symbols that are used. None is returned if the expression can't be fully
+/// evaluated.
+pub fn evaluate(expr: &str, lookup: &dyn Fn(&str) -> Option<i128>) -> Option<i128> {
+    let tokens = tokenize(expr)?;
+    let mut evaluator = Evaluator {
+        tokens: &tokens,
+        position: 0,
+        lookup,
+    };
+
+    let value = evaluator.binary(0)?;
+    (evaluator.position == tokens.len()).then_some(value)
+}
+
+fn tokenize(expr: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut chars: Peekable<CharIndices> = expr.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            // Immediate prefixes e.g. `#16` or `$SIZE`, a `$` on its own is the
+            // NASM location counter.
+            '#' | '$' if chars.peek().map(|(_, c)| is_operand_start(*c)) == Some(true) => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            c if c.is_ascii_digit() => {
+                Token::Number(parse_number(take_while(expr, &mut chars, start, |c| {
+                    c.is_ascii_alphanumeric()
+                }))?)
+            }
+            c if is_symbol_char(c) => {
+                Token::Symbol(take_while(expr, &mut chars, start, is_symbol_char))
+            }
+            '<' | '>' | '=' | '!' | '&' | '|' => {
+                let next = chars.peek().map(|(_, c)| *c);
+                let double = matches!(
+                    (c, next),
+                    ('<', Some('<' | '=' | '>'))
+                        | ('>', Some('>' | '='))
+                        | ('=' | '!', Some('='))
+                        | ('&', Some('&'))
+                        | ('|', Some('|'))
+                );
+
+                if double {
+                    chars.next();
+                    Token::Operator(&expr[start..start + 2])
+                } else {
+                    Token::Operator(&expr[start..start + 1])
+                }
+            }
+            '+' | '-' | '*' | '/' | '%' | '^' | '~' => Token::Operator(&expr[start..start + 1]),
+            _ => return None,
+        };
+
+        tokens.push(token);
+    }
+
+    Some(tokens)
+}
+
+fn is_operand_start(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '(' || c == '-' || c == '~'
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '$'
+}
+
+/// Consumes characters while `f` matches, returning the text from `start`
+fn take_while<'a>(
+    expr: &'a str,
+    chars: &mut Peekable<CharIndices>,
+    start: usize,
+    f: impl Fn(char) -> bool,
+) -> &'a str {
+    let mut end = start + expr[start..].chars().next().map_or(0, |c| c.len_utf8());
+    while let Some((i, c)) = chars.peek().cloned() {
+        if !f(c) {
+            break;
+        }
+        end = i + c.len_utf8();
+        chars.next();
+    }
+    &expr[start..end]
+}
+
+fn parse_number(text: &str) -> Option<i128> {
+    let lower = text.to_ascii_lowercase();
+
+    if let Some(hex) = lower.strip_prefix("0x") {
+        i128::from_str_radix(hex, 16).ok()
+    } else if let Some(binary) = lower.strip_prefix("0b") {
+        i128::from_str_radix(binary, 2).ok()
+    } else if lower.len() > 1 && lower.starts_with('0') {
+        i128::from_str_radix(&lower[1..], 8).ok()
+    } else {
+        lower.parse().ok()
+    }
+}
+
+struct Evaluator<'t, 'a> {
+    tokens: &'t [Token<'a>],
+    position: usize,
+    lookup: &'t dyn Fn(&str) -> Option<i128>,
+}
+
+impl<'t, 'a> Evaluator<'t, 'a> {
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&Token<'a>> {
+        self.position += 1;
+        self.tokens.get(self.position - 1)
+    }
+
+    fn binary(&mut self, level: usize) -> Option<i128> {
+        if level == PRECEDENCE.len() {
+            return self.unary();
+        }
+
+        let mut lhs = self.binary(level + 1)?;
+        while let Some(Token::Operator(op)) = self.peek().cloned() {
+            if !PRECEDENCE[level].contains(&op) {
+                break;
+            }
+
+            self.next();
+            let rhs = self.binary(level + 1)?;
+            lhs = apply(op, lhs, rhs)?;
+        }
+
+        Some(lhs)
+    }
+
+    fn unary(&mut self) -> Option<i128> {
+        match self.next()?.clone() {
+            Token::Number(value) => Some(value),
+            Token::Symbol(name) => (self.lookup)(name),
+            Token::Operator("-") => self.unary().map(|v| v.wrapping_neg()),
+            Token::Operator("+") => self.unary(),
+            Token::Operator("~") => self.unary().map(|v| !v),
+            Token::Operator("!") => self.unary().map(|v| i128::from(v == 0)),
+            Token::LParen => {
+                let value = self.binary(0)?;
+                matches!(self.next(), Some(Token::RParen)).then_some(value)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn apply(op: &str, lhs: i128, rhs: i128) -> Option<i128> {
+    // Comparisons result in -1 for true as GAS does
+    let truth = |b: bool| if b { -1 } else { 0 };
+
+    Some(match op {
+        "*" => lhs.wrapping_mul(rhs),
+        "/" => lhs.checked_div(rhs)?,
+        "%" => lhs.checked_rem(rhs)?,
+        "<<" => lhs.checked_shl(u32::try_from(rhs).ok()?)?,
+        ">>" => lhs.checked_shr(u32::try_from(rhs).ok()?)?,
+        "|" => lhs | rhs,
+        "&" => lhs & rhs,
+        "^" => lhs ^ rhs,
+        "!" => lhs | !rhs,
+        "+" => lhs.wrapping_add(rhs),
+        "-" => lhs.wrapping_sub(rhs),
+        "==" => truth(lhs == rhs),
+        "!=" | "<>" => truth(lhs != rhs),
+        "<" => truth(lhs < rhs),
+        ">" => truth(lhs > rhs),
+        "<=" => truth(lhs <= rhs),
+        ">=" => truth(lhs >= rhs),
+        "&&" => i128::from(lhs != 0 && rhs != 0),
+        "||" => i128::from(lhs != 0 || rhs != 0),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expr: &str) -> Option<i128> {
+        evaluate(expr, &|name| match name {
+            "SIZE" => Some(16),
+            "." => Some(32),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(eval("1 + 2"), Some(3));
+        assert_eq!(eval("(SIZE + 4) << 2"), Some(80));
+        assert_eq!(eval("SIZE * 2 + 1"), Some(33));
+        assert_eq!(eval("0x10 | 0b1"), Some(17));
+        assert_eq!(eval("-#4"), Some(-4));
+        assert_eq!(eval("#(SIZE - 1)"), Some(15));
+        assert_eq!(eval("$SIZE"), Some(16));
+        assert_eq!(eval("~0"), Some(-1));
+    }
+
+    #[test]
+    fn test_location() {
+        assert_eq!(eval(". - 4"), Some(28));
+        assert_eq!(eval("(. + 8) & ~7"), Some(40));
+    }
+
+    #[test]
+    fn test_comparison() {
+        assert_eq!(eval("SIZE == 16"), Some(-1));
+        assert_eq!(eval("SIZE != 16"), Some(0));
+        assert_eq!(eval("SIZE > 8 && SIZE < 32"), Some(1));
+    }
+
+    #[test]
+    fn test_unknown() {
+        assert_eq!(eval("OTHER + 1"), None);
+        assert_eq!(eval("1 / 0"), None);
+        assert_eq!(eval("(1 + 2"), None);
+        assert_eq!(eval("\\arg"), None);
+    }
+}
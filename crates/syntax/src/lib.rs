@@ -1,4 +1,5 @@
 pub mod alias;
 pub mod ast;
 pub mod edit;
+pub mod expr;
 pub mod utils;
@@ -6,6 +6,7 @@ pub fn is_token_include(token: &str) -> bool {
         || token.eq_ignore_ascii_case("include")
         || token.eq_ignore_ascii_case("get")
         || token.eq_ignore_ascii_case("#include")
+        || token.eq_ignore_ascii_case("%include")
 }
 
 #[inline]
@@ -13,6 +14,17 @@ pub fn token_is_local_label(token: &SyntaxToken) -> bool {
     matches!(token.kind(), SyntaxKind::TOKEN | SyntaxKind::LABEL) && token.text().starts_with('.')
 }
 
+/// Determines if the token is the name of a macro, either where the macro is
+/// defined or where it is called.
+#[inline]
+pub fn token_is_macro_name(token: &SyntaxToken) -> bool {
+    matches!(
+        (token.kind(), token.parent().map(|p| p.kind())),
+        (SyntaxKind::NAME, Some(SyntaxKind::MACRO_DEF))
+            | (SyntaxKind::MNEMONIC, Some(SyntaxKind::MACRO_CALL))
+    )
+}
+
 pub fn find_token_containing(root: &SyntaxNode, text: &str) -> Option<SyntaxToken> {
     root.descendants_with_tokens().find_map(|elem| match elem {
         rowan::NodeOrToken::Node(_) => None,